@@ -29,5 +29,39 @@ fn main() {
             "Eq".into(),
         ])
         .user_defined_type("crate::user_defined_state::TheState")
+        .incremental()
+        .run_exit_on_error();
+    peginator::buildscript::Compile::file("src/byte_input/grammar.not_ebnf")
+        .format()
+        .use_peginator_build_time()
+        .derives(vec![
+            "Debug".into(),
+            "Clone".into(),
+            "PartialEq".into(),
+            "Eq".into(),
+        ])
+        .byte_input()
+        .run_exit_on_error();
+    peginator::buildscript::Compile::file("src/borrowed_strings/grammar.not_ebnf")
+        .format()
+        .use_peginator_build_time()
+        .derives(vec![
+            "Debug".into(),
+            "Clone".into(),
+            "PartialEq".into(),
+            "Eq".into(),
+        ])
+        .borrowed_strings()
+        .run_exit_on_error();
+    peginator::buildscript::Compile::file("src/incremental/grammar.not_ebnf")
+        .format()
+        .use_peginator_build_time()
+        .derives(vec![
+            "Debug".into(),
+            "Clone".into(),
+            "PartialEq".into(),
+            "Eq".into(),
+        ])
+        .incremental()
         .run_exit_on_error();
 }
@@ -0,0 +1,98 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::{PegBorrowedParser, PegParser};
+
+pub fn parse_digits(s: &str) -> Result<(&str, usize), &'static str> {
+    let length = s.bytes().take_while(u8::is_ascii_digit).count();
+    if length == 0 {
+        return Err("expected digits");
+    }
+    Ok((&s[..length], length))
+}
+
+pub fn parse_integer(s: &str) -> Result<(u32, usize), &'static str> {
+    let (digits, length) = parse_digits(s)?;
+    Ok((digits.parse().map_err(|_| "number too large")?, length))
+}
+
+#[test]
+fn test() {
+    let text = String::from(
+        r#"
+        [server]
+        host = "localhost";
+        port = 8080;
+        [paths]
+        search = (usr_bin, "/opt", (1, 2));
+        "#,
+    );
+    let parsed = Config::parse_borrowed(&text).unwrap();
+    assert_eq!(
+        parsed,
+        Config {
+            sections: vec![
+                Section {
+                    name: "server",
+                    entries: vec![
+                        Entry {
+                            key: "host",
+                            value: Value::QuotedString(QuotedString {
+                                string: "\"localhost\"",
+                                position: 33..44,
+                            }),
+                        },
+                        Entry {
+                            key: "port",
+                            value: Value::Number(Number { value: "8080" }),
+                        },
+                    ],
+                },
+                Section {
+                    name: "paths",
+                    entries: vec![Entry {
+                        key: "search",
+                        value: Value::List(List {
+                            items: vec![
+                                Value::Identifier("usr_bin"),
+                                Value::QuotedString(QuotedString {
+                                    string: "\"/opt\"",
+                                    position: 110..116,
+                                }),
+                                Value::List(List {
+                                    items: vec![
+                                        Value::Number(Number { value: "1" }),
+                                        Value::Number(Number { value: "2" }),
+                                    ],
+                                }),
+                            ],
+                        }),
+                    }],
+                },
+            ],
+        }
+    );
+    // The strings point into the input, no copies are made.
+    let key = parsed.sections[0].entries[0].key;
+    assert!(text.as_bytes().as_ptr_range().contains(&key.as_ptr()));
+}
+
+#[test]
+fn test_override() {
+    let flags = Flags::parse_borrowed("-all -in_place").unwrap();
+    assert_eq!(flags.flags, vec!["all", "in_place"]);
+}
+
+#[test]
+fn test_owned() {
+    // Types that do not contain borrowed strings have no lifetime parameter, and can still be
+    // parsed with the owned parser trait, without any ambiguity.
+    fn parse_sum(s: &str) -> Sum {
+        Sum::parse(s).unwrap()
+    }
+    assert_eq!(parse_sum(&String::from("12 + 30")), Sum { a: 12, b: 30 });
+    assert_eq!(Sum::parse_borrowed("1 + 2").unwrap(), Sum { a: 1, b: 2 });
+}
@@ -0,0 +1,58 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::PegParser;
+
+#[test]
+fn test_exact() {
+    assert_eq!(
+        Unicode::parse("\\u12aF").unwrap().digits,
+        ['1', '2', 'a', 'F']
+    );
+    // Only exactly 4 repetitions are matched
+    assert_eq!(Unicode::parse_prefix("\\u12345").unwrap().1, 6);
+    let err = Unicode::parse("\\u12g4").unwrap_err();
+    assert_eq!(err.position, 4);
+    assert_eq!(
+        err.specifics,
+        [
+            peginator::ParseErrorSpecifics::ExpectedCharacterClass { name: "HexDigit" },
+            peginator::ParseErrorSpecifics::ExpectedRepetitions { min: 4, matched: 2 }
+        ]
+    );
+    assert!(err
+        .to_string()
+        .contains("expected at least 4 repetitions, matched 2"));
+}
+
+#[test]
+fn test_range() {
+    assert_eq!(Color::parse("#ff").unwrap().components.len(), 1);
+    assert_eq!(Color::parse("#00ff00").unwrap().components.len(), 3);
+    assert_eq!(Color::parse_prefix("#00ff00aa").unwrap().1, 7);
+    assert_eq!(Color::parse_prefix("#00f").unwrap().1, 3);
+    assert!(Color::parse("#").is_err());
+}
+
+#[test]
+fn test_unbounded() {
+    assert!(Tags::parse("a").is_err());
+    assert_eq!(Tags::parse("a b").unwrap().tags, ["a", "b"]);
+    assert_eq!(Tags::parse("a b c d").unwrap().tags.len(), 4);
+}
+
+#[test]
+fn test_separated() {
+    assert!(Args::parse("()").unwrap().args.is_empty());
+    assert_eq!(Args::parse("(a, b)").unwrap().args, ["a", "b"]);
+    assert_eq!(Args::parse("(a, b,)").unwrap().args, ["a", "b"]);
+    assert!(Args::parse("(a, b, c)").is_err());
+}
+
+#[test]
+fn test_unicode_escapes() {
+    assert!(Escapes::parse("ABC😀").is_ok());
+}
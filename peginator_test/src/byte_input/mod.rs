@@ -0,0 +1,88 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::{ParseErrorFound, PegByteParser};
+
+pub fn parse_number(s: &[u8]) -> Result<(u32, usize), &'static str> {
+    let length = s.iter().take_while(|b| b.is_ascii_digit()).count();
+    if length == 0 {
+        return Err("expected a number");
+    }
+    let number = std::str::from_utf8(&s[..length])
+        .unwrap()
+        .parse()
+        .map_err(|_| "number too large")?;
+    Ok((number, length))
+}
+
+#[test]
+fn test_binary() {
+    let parsed = File::parse(b"\x89IMG\r\n1.2 R\xe9sum\xe9\x00\x01\xff\x00").unwrap();
+    assert_eq!(
+        parsed,
+        File {
+            version: Version {
+                major: b'1',
+                minor: b'2',
+            },
+            name: b"R\xe9sum\xe9".to_vec(),
+            data: vec![0x01, 0xff, 0x00],
+        }
+    );
+
+    let parsed = File::parse(b"\x89img\r\n3.4 A\x00").unwrap();
+    assert_eq!(parsed.name, b"A");
+    assert_eq!(parsed.data, b"");
+}
+
+#[test]
+fn test_errors() {
+    let err = File::parse(b"\x89IMG\r\nx.2 A\x00").unwrap_err();
+    assert_eq!(err.position, 6);
+    assert_eq!(err.found, Some(ParseErrorFound::Token("x".into())));
+    assert_eq!(
+        err.message(),
+        "expected character from character class Digit but found 'x'"
+    );
+
+    let err = File::parse(b"\x89IMG\n").unwrap_err();
+    assert_eq!(err.position, 4);
+    assert_eq!(err.found, Some(ParseErrorFound::Byte(b'\n')));
+    assert_eq!(err.message(), "expected b\"\\r\\n\" but found b'\\n'");
+
+    let err = File::parse(b"\x89IMG\r\n1.2 A").unwrap_err();
+    assert_eq!(err.found, Some(ParseErrorFound::EndOfInput));
+
+    let err = File::parse(&[b"\x89IMG\r\n".as_slice(), &[b'x'; 100]].concat()).unwrap_err();
+    assert_eq!(
+        err.found,
+        Some(ParseErrorFound::Token(format!("{}...", "x".repeat(32))))
+    );
+    assert_eq!(err.span, 6..38);
+}
+
+#[test]
+fn test_whitespace_skipping() {
+    assert_eq!(
+        Words::parse(b" \xc9t\xe9 \t caf\xe9\n").unwrap(),
+        Words {
+            words: vec![b"\xc9t\xe9".to_vec(), b"caf\xe9".to_vec()],
+        }
+    );
+    assert!(Words::parse(b"a_b").is_err());
+}
+
+#[test]
+fn test_extern() {
+    assert_eq!(
+        Numbers::parse(b"12 34\n5").unwrap(),
+        Numbers {
+            numbers: vec![12, 34, 5],
+        }
+    );
+    let err = Numbers::parse(b"12 x").unwrap_err();
+    assert_eq!(err.position, 3);
+}
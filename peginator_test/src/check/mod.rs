@@ -4,7 +4,9 @@
 
 mod grammar;
 use grammar::*;
-use peginator::PegParser;
+use std::borrow::Cow;
+
+use peginator::{ParseErrorSpecifics, PegParser};
 
 #[test]
 fn test() {
@@ -33,6 +35,28 @@ fn test() {
     assert!(Test::parse("lŰ").is_err());
 }
 
+#[test]
+fn test_message() {
+    assert_eq!(Test::parse("m123").unwrap().m.unwrap(), "123".to_string());
+    let err = Test::parse("m99999999999").unwrap_err();
+    assert_eq!(err.position, 12);
+    assert!(err
+        .specifics
+        .contains(&ParseErrorSpecifics::CheckFunctionFailed {
+            function_name: "crate::check::message_check",
+            message: Some("integer literal 99999999999 overflows u32".into()),
+        }));
+    assert!(err
+        .message()
+        .contains("integer literal 99999999999 overflows u32"));
+
+    let err = Test::parse("fa").unwrap_err();
+    assert_eq!(
+        err.message(),
+        "check function 'crate::check::unit_checker_failing' failed"
+    );
+}
+
 pub fn unit_checker(_: &UnitRule) -> bool {
     true
 }
@@ -55,3 +79,10 @@ pub fn enum_override_check(dn: &DualNumber) -> bool {
         DualNumber::Number(n) => n.contains('1'),
     }
 }
+
+pub fn message_check(n: &MessageNumber) -> Result<(), Cow<'static, str>> {
+    match n.parse::<u32>() {
+        Ok(_) => Ok(()),
+        Err(_) => Err(format!("integer literal {n} overflows u32").into()),
+    }
+}
@@ -0,0 +1,68 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::{ParseErrorSpecifics, PegParser};
+
+#[test]
+fn test_choice() {
+    assert!(ChoiceCut::parse("ab").is_ok());
+    assert!(ChoiceCut::parse("x").is_ok());
+    assert!(ChoiceCut::parse("ac").is_err());
+
+    assert!(ChoiceNoCut::parse("ab").is_ok());
+    assert!(ChoiceNoCut::parse("x").is_ok());
+    assert!(ChoiceNoCut::parse("ac").is_ok());
+}
+
+#[test]
+fn test_closure() {
+    assert!(ClosureCut::parse("c").is_ok());
+    assert!(ClosureCut::parse("ababc").is_ok());
+    let err = ClosureCut::parse("abac").unwrap_err();
+    assert_eq!(err.position, 3);
+    assert_eq!(
+        err.specifics,
+        vec![ParseErrorSpecifics::ExpectedCharacter { c: 'b' }]
+    );
+}
+
+#[test]
+fn test_closure_around_choice() {
+    assert!(ClosureChoiceCut::parse("d").is_ok());
+    assert!(ClosureChoiceCut::parse("cabd").is_ok());
+    // The cut inside the choice makes the closure fail instead of stopping before the 'a'.
+    let err = ClosureChoiceCut::parse("cad").unwrap_err();
+    assert_eq!(err.position, 2);
+    assert_eq!(
+        err.specifics,
+        vec![ParseErrorSpecifics::ExpectedCharacter { c: 'b' }]
+    );
+}
+
+#[test]
+fn test_optional() {
+    assert!(OptionalCut::parse("abac").is_ok());
+    assert!(OptionalCut::parse("c").is_ok());
+    assert!(OptionalCut::parse("ac").is_err());
+}
+
+#[test]
+fn test_rule_boundary() {
+    assert!(RuleBoundary::parse("ab").is_ok());
+    assert!(RuleBoundary::parse("ac").is_ok());
+}
+
+#[test]
+fn test_error_position() {
+    assert!(Statement::parse("if (x) y = z;").is_ok());
+    assert!(Statement::parse("x = y;").is_ok());
+    let err = Statement::parse("if x) y = z;").unwrap_err();
+    assert_eq!(err.position, 3);
+    assert_eq!(
+        err.specifics,
+        vec![ParseErrorSpecifics::ExpectedCharacter { c: '(' }]
+    );
+}
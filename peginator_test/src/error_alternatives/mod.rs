@@ -0,0 +1,88 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::{ParseErrorFound, ParseErrorSpecifics, PegParser, PrettyParseError};
+
+#[test]
+fn test_alternatives() {
+    assert!(Call::parse("foo(1, 2)").is_ok());
+    let err = Call::parse("foo(1 2)").unwrap_err();
+    assert_eq!(err.position, 6);
+    assert_eq!(
+        err.specifics,
+        vec![
+            ParseErrorSpecifics::ExpectedCharacter { c: ',' },
+            ParseErrorSpecifics::ExpectedCharacter { c: ')' },
+        ]
+    );
+    assert_eq!(err.message(), "expected one of ',', ')' but found '2'");
+}
+
+#[test]
+fn test_single() {
+    let err = Call::parse("foo(1,").unwrap_err();
+    assert_eq!(err.position, 6);
+    assert_eq!(
+        err.specifics,
+        vec![ParseErrorSpecifics::ExpectedCharacterRange { from: '0', to: '9' }]
+    );
+    assert_eq!(
+        err.message(),
+        "expected character from range '0'-'9' but found end of input"
+    );
+}
+
+#[test]
+fn test_deduplicated() {
+    let err = Duplicated::parse("ax").unwrap_err();
+    assert_eq!(err.position, 1);
+    assert_eq!(
+        err.specifics,
+        vec![ParseErrorSpecifics::ExpectedCharacter { c: 'b' }]
+    );
+
+    let err = Duplicated::parse("abx").unwrap_err();
+    assert_eq!(err.position, 2);
+    assert_eq!(
+        err.message(),
+        "expected one of 'c', 'd', end of input but found 'x'"
+    );
+}
+
+#[test]
+fn test_found() {
+    let err = Call::parse("foo(1 bar)").unwrap_err();
+    assert_eq!(err.found, Some(ParseErrorFound::Token("bar".to_string())));
+    let err = Call::parse("foo(1 ;)").unwrap_err();
+    assert_eq!(err.found, Some(ParseErrorFound::Token(";".to_string())));
+    let err = Call::parse("foo(1").unwrap_err();
+    assert_eq!(err.found, Some(ParseErrorFound::EndOfInput));
+
+    let text = "foo(1 bar)";
+    let err = Call::parse(text).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "Parse error on byte position 6 while parsing: expected one of ',', ')' but found 'bar'"
+    );
+    let pretty = PrettyParseError::from_parse_error(&err, text, None).to_string();
+    assert!(pretty.contains("expected one of ',', ')' but found 'bar'"));
+}
+
+#[test]
+fn test_found_long_token() {
+    let long_token = "x".repeat(100);
+    let err = Call::parse(&format!("foo(1 {long_token} 2)")).unwrap_err();
+    assert_eq!(
+        err.found,
+        Some(ParseErrorFound::Token(format!("{}...", "x".repeat(32))))
+    );
+    assert_eq!(err.span, 6..38);
+
+    let token = "é".repeat(32);
+    let err = Call::parse(&format!("foo(1 {token} 2)")).unwrap_err();
+    assert_eq!(err.found, Some(ParseErrorFound::Token(token)));
+    assert_eq!(err.span, 6..70);
+}
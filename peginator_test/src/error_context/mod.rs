@@ -0,0 +1,56 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::{
+    runtime::{NoopTracer, PegParserAdvanced},
+    ParseError, ParseSettings, PegParser, PrettyParseError,
+};
+
+fn parse_with_context(s: &str) -> Result<Program, ParseError> {
+    let mut settings = ParseSettings::default();
+    settings.record_context = true;
+    Program::parse_advanced::<NoopTracer>(s, &settings, ())
+}
+
+#[test]
+fn test_context() {
+    assert!(parse_with_context("a = (1); b = 2;").is_ok());
+
+    let err = parse_with_context("a = 1; b = (2;").unwrap_err();
+    assert_eq!(err.position, 13);
+    assert_eq!(err.span, 11..14);
+    assert_eq!(
+        err.context,
+        vec!["Parenthesized", "Expression", "Statement", "Program"]
+    );
+    assert_eq!(
+        err.context_message().unwrap(),
+        "while parsing Parenthesized in Expression in Statement in Program"
+    );
+}
+
+#[test]
+fn test_common_context() {
+    let err = parse_with_context("a = ;").unwrap_err();
+    assert_eq!(err.position, 4);
+    assert_eq!(err.context, vec!["Expression", "Statement", "Program"]);
+}
+
+#[test]
+fn test_no_context() {
+    let err = Program::parse("a = 1; b = (2;").unwrap_err();
+    assert_eq!(err.position, 13);
+    assert!(err.context.is_empty());
+    assert_eq!(err.context_message(), None);
+}
+
+#[test]
+fn test_pretty() {
+    let text = "a = 1; b = (2;";
+    let err = parse_with_context(text).unwrap_err();
+    let pretty = PrettyParseError::from_parse_error(&err, text, None).to_string();
+    assert!(pretty.contains("while parsing Parenthesized in Expression in Statement in Program"));
+}
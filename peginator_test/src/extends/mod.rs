@@ -0,0 +1,76 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+mod dialect {
+    pub mod grammar;
+}
+
+use peginator::{grammar::LoadedGrammar, PegParser};
+
+fn test_file(name: &str) -> String {
+    format!("{}/src/extends/{name}", env!("CARGO_MANIFEST_DIR"))
+}
+
+#[test]
+fn test_base() {
+    let result = grammar::Program::parse("x = 5; print x;").unwrap();
+    assert_eq!(
+        result.statements,
+        [
+            grammar::Statement::Assignment(grammar::Assignment {
+                name: "x".into(),
+                value: grammar::Expression::Number("5".into()),
+            }),
+            grammar::Statement::Print(grammar::Print {
+                value: grammar::Expression::Identifier("x".into()),
+            }),
+        ]
+    );
+    assert!(grammar::Program::parse("x := 5;").is_err());
+    assert!(grammar::Program::parse("print \"hello\";").is_err());
+}
+
+#[test]
+fn test_dialect() {
+    use dialect::grammar::*;
+    // The inherited Print and Program rules use the overridden Expression and Assignment rules,
+    // and the new Whitespace rule.
+    let result = Program::parse(
+        "x := \"hello\";  // Comments are only supported in the dialect\n\
+         print x;",
+    )
+    .unwrap();
+    assert_eq!(
+        result.statements,
+        [
+            Statement::Assignment(Assignment {
+                name: "x".into(),
+                value: Expression::QuotedString("\"hello\"".into()),
+            }),
+            Statement::Print(Print {
+                value: Expression::Identifier("x".into()),
+            }),
+        ]
+    );
+    assert!(Program::parse("x = 5;").is_err());
+}
+
+#[test]
+fn test_loaded_files() {
+    let loaded = LoadedGrammar::load(test_file("dialect/grammar.ebnf")).unwrap();
+    assert_eq!(loaded.sources.len(), 2);
+    assert_eq!(loaded.grammar.rules.len(), 10);
+    assert!(loaded.grammar.directives.is_empty());
+
+    let err = LoadedGrammar::load(test_file("circular.not_ebnf")).unwrap_err();
+    assert!(err.to_string().contains("Circular @extends"), "{err}");
+
+    let err = LoadedGrammar::load(test_file("modified_path.not_ebnf")).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("The @extends directive does not accept case insensitive or keyword strings"),
+        "{err}"
+    );
+}
@@ -4,7 +4,7 @@
 
 mod grammar;
 use grammar::*;
-use peginator::PegParser;
+use peginator::{ParseErrorSpecifics, PegParser};
 
 #[test]
 fn test() {
@@ -17,9 +17,24 @@ fn test() {
     );
 }
 
+#[test]
+fn test_dynamic_error() {
+    assert_eq!(
+        SmallNumberTest::parse("1234").unwrap(),
+        SmallNumberTest { n: 1234 }
+    );
+    let err = SmallNumberTest::parse("99999999999").unwrap_err();
+    assert_eq!(
+        err.specifics,
+        vec![ParseErrorSpecifics::ExternRuleFailed {
+            error_string: "integer literal 99999999999 overflows u32".into()
+        }]
+    );
+}
+
 pub fn raw_string_parser(s: &str) -> Result<(&str, usize), &'static str> {
     let sb = s.as_bytes();
-    if *sb.get(0).ok_or("expected 'r', found end of string")? != b'r' {
+    if *sb.first().ok_or("expected 'r', found end of string")? != b'r' {
         return Err("expected 'r'");
     }
     let num_hashes = sb[1..]
@@ -50,3 +65,15 @@ pub struct TheStruct {
 pub fn struct_maker(s: &str) -> Result<(TheStruct, usize), &'static str> {
     Ok((TheStruct { s: s.into() }, s.len()))
 }
+
+pub fn small_number_parser(s: &str) -> Result<(u32, usize), String> {
+    let length = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    if length == 0 {
+        return Err("expected a number".into());
+    }
+    let number = &s[..length];
+    match number.parse() {
+        Ok(n) => Ok((n, length)),
+        Err(_) => Err(format!("integer literal {number} overflows u32")),
+    }
+}
@@ -0,0 +1,104 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::{
+    codegen::{generate_loaded_source_header, generate_source_header},
+    grammar::LoadedGrammar,
+    PegParser,
+};
+
+fn test_file(name: &str) -> String {
+    format!("{}/src/import/{name}", env!("CARGO_MANIFEST_DIR"))
+}
+
+#[test]
+fn test_imported_rules() {
+    let result = Assignments::parse(
+        "x = 5;  # Comment from lexical.not_ebnf\n\
+         greeting = \"hello\";",
+    )
+    .unwrap();
+    assert_eq!(
+        result.assignments,
+        [
+            Assignment {
+                name: "x".into(),
+                value: Literal::Number("5".into()),
+            },
+            Assignment {
+                name: "greeting".into(),
+                value: Literal::QuotedString("\"hello\"".into()),
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_loaded_files() {
+    // lexical.not_ebnf is imported twice, but only loaded once.
+    let loaded = LoadedGrammar::load(test_file("grammar.ebnf")).unwrap();
+    assert_eq!(loaded.sources.len(), 3);
+    assert_eq!(loaded.grammar.rules.len(), 8);
+    assert!(loaded.grammar.directives.is_empty());
+}
+
+#[test]
+fn test_conflict() {
+    let err = LoadedGrammar::load(test_file("conflict.not_ebnf")).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("Rule Identifier is defined in both"));
+    assert!(message.contains("conflict.not_ebnf"));
+    assert!(message.contains("lexical.not_ebnf"));
+}
+
+#[test]
+fn test_error_in_imported_file() {
+    let err = LoadedGrammar::load(test_file("imports_invalid.not_ebnf")).unwrap_err();
+    let message = err.to_string();
+    assert!(message.contains("invalid.not_ebnf:9:"), "{message}");
+    assert!(!message.contains("imports_invalid"), "{message}");
+
+    let err = LoadedGrammar::load(test_file("missing.not_ebnf")).unwrap_err();
+    assert!(err.to_string().contains("Could not read"));
+}
+
+#[test]
+fn test_modified_path() {
+    let err = LoadedGrammar::load(test_file("modified_path.not_ebnf")).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("The @import directive does not accept case insensitive or keyword strings"),
+        "{err}"
+    );
+}
+
+#[test]
+fn test_dotted_path() {
+    // The same file through a different path is still only loaded once, without conflicts.
+    let loaded = LoadedGrammar::load(test_file("dotted_path.not_ebnf")).unwrap();
+    assert_eq!(loaded.sources.len(), 2);
+
+    let err = LoadedGrammar::load(test_file("imports_duplicate.not_ebnf")).unwrap_err();
+    let message = err.to_string();
+    assert!(
+        message.contains("Rule Identifier is defined twice in"),
+        "{message}"
+    );
+    assert!(message.contains("duplicate.not_ebnf"), "{message}");
+}
+
+#[test]
+fn test_source_header() {
+    // Grammars without imports keep the header format of single grammar files.
+    let loaded = LoadedGrammar::load(test_file("lexical.not_ebnf")).unwrap();
+    let header = generate_loaded_source_header(&loaded.sources, false);
+    assert_eq!(header, generate_source_header(&loaded.sources[0], false));
+    assert!(header.contains("Hash of the grammar file:"));
+
+    let loaded = LoadedGrammar::load(test_file("grammar.ebnf")).unwrap();
+    let header = generate_loaded_source_header(&loaded.sources, false);
+    assert!(header.contains("Hash of the grammar files:"));
+}
@@ -0,0 +1,176 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use std::cell::Cell;
+
+use grammar::*;
+use peginator::{
+    runtime::{NoopTracer, PegIncrementalParserAdvanced, PegParserAdvanced},
+    ParseSettings, PegIncrementalParser, PegParser, TextEdit,
+};
+
+thread_local! {
+    static INTEGER_CALLS: Cell<usize> = const { Cell::new(0) };
+    static WORD_CALLS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Also returns how much of the input was looked at: the digits and the character after them.
+pub fn parse_integer(s: &str) -> (Result<(u32, usize), &'static str>, usize) {
+    INTEGER_CALLS.with(|calls| calls.set(calls.get() + 1));
+    let length = s.bytes().take_while(u8::is_ascii_digit).count();
+    let result = match s[..length].parse() {
+        Ok(value) => Ok((value, length)),
+        Err(_) if length == 0 => Err("expected digits"),
+        Err(_) => Err("number too large"),
+    };
+    (result, length + 1)
+}
+
+/// Does not report how much of the input was looked at.
+pub fn parse_lowercase_word(s: &str) -> Result<(&str, usize), &'static str> {
+    WORD_CALLS.with(|calls| calls.set(calls.get() + 1));
+    let length = s.bytes().take_while(u8::is_ascii_lowercase).count();
+    if length == 0 {
+        return Err("expected a word");
+    }
+    Ok((&s[..length], length))
+}
+
+fn integer_calls() -> usize {
+    INTEGER_CALLS.with(|calls| calls.replace(0))
+}
+
+fn word_calls() -> usize {
+    WORD_CALLS.with(|calls| calls.replace(0))
+}
+
+fn apply_edit(text: &str, range: std::ops::Range<usize>, replacement: &str) -> String {
+    let mut result = text.to_string();
+    result.replace_range(range, replacement);
+    result
+}
+
+const TEXT: &str = "a = 1 + 2;\nb = a + 3 + c;\nc = 4;\nd = 5 + 6 + 7;\n";
+
+#[test]
+fn test_reparse() {
+    let edits = [
+        (4..5, "10"),
+        (15..16, "xyz + 1"),
+        (11..26, ""),
+        (26..26, "e = 8;"),
+        (0..0, "  "),
+        (TEXT.len()..TEXT.len(), "f = 9;"),
+        (30..31, "12"),
+        (8..9, "q"),
+    ];
+    for (range, replacement) in edits {
+        let new_text = apply_edit(TEXT, range.clone(), replacement);
+        let previous = Document::parse_incremental(TEXT);
+        assert_eq!(previous.result, Document::parse(TEXT));
+        let reparsed = Document::reparse(previous, &new_text, &TextEdit::new(range, replacement));
+        assert_eq!(reparsed.result, Document::parse(&new_text), "{new_text:?}");
+    }
+}
+
+#[test]
+fn test_reuse() {
+    let text: String = (0..100).map(|i| format!("x = {i} + y + {i};\n")).collect();
+    let previous = Document::parse_incremental(&text);
+    assert_eq!(previous.result.as_ref().unwrap().items.len(), 100);
+    let full_calls = integer_calls();
+
+    let position = text.find("x = 50 ").unwrap() + 4;
+    let new_text = apply_edit(&text, position..position + 2, "1234");
+    let reparsed = Document::reparse(
+        previous,
+        &new_text,
+        &TextEdit::new(position..position + 2, "1234"),
+    );
+    let reparse_calls = integer_calls();
+    let parsed = reparsed.result.unwrap();
+    assert_eq!(parsed, Document::parse(&new_text).unwrap());
+    assert!(reparse_calls < full_calls / 10);
+
+    let Expression::Add(add) = &parsed.items[50].value else {
+        panic!("Expected Add, got {:?}", parsed.items[50].value)
+    };
+    assert_eq!(add.position, position..position + "1234 + y + 50".len());
+    assert_eq!(parsed.items[99].position.end, new_text.len() - 1);
+}
+
+#[test]
+fn test_error() {
+    let previous = Document::parse_incremental(TEXT);
+    let new_text = apply_edit(TEXT, 24..25, "");
+    let reparsed = Document::reparse(previous, &new_text, &TextEdit::new(24..25, ""));
+    assert_eq!(reparsed.result, Document::parse(&new_text));
+    assert!(reparsed.result.is_err());
+
+    let fixed = Document::reparse(reparsed, TEXT, &TextEdit::new(24..24, ";"));
+    assert_eq!(fixed.result, Document::parse(TEXT));
+}
+
+#[test]
+fn test_repeated_reparse() {
+    // Only the results used by a parse are kept, the results inside reused items are dropped.
+    // Later edits still only reparse the edited items.
+    let mut text: String = (0..100).map(|i| format!("x = {i} + y + {i};\n")).collect();
+    let mut parsed = Document::parse_incremental(&text);
+    let full_calls = integer_calls();
+    for (item, replacement) in [(20, "77"), (80, "1 + 2"), (21, "5"), (79, "z")] {
+        let position = text.find(&format!("x = {item} ")).unwrap() + 4;
+        let edit = TextEdit::new(position..position + 2, replacement);
+        text.replace_range(position..position + 2, replacement);
+        parsed = Document::reparse(parsed, &text, &edit);
+        assert!(integer_calls() < full_calls / 10);
+        assert_eq!(parsed.result, Document::parse(&text), "{text:?}");
+        integer_calls();
+    }
+}
+
+#[test]
+fn test_advanced() {
+    let mut settings = ParseSettings::default();
+    settings.record_context = true;
+    let previous = Document::parse_advanced_with_cache::<NoopTracer>(
+        TEXT,
+        Default::default(),
+        &TextEdit::default(),
+        &settings,
+        (),
+    );
+    assert_eq!(previous.result, Document::parse(TEXT));
+    let new_text = apply_edit(TEXT, 24..25, "");
+    let reparsed = Document::parse_advanced_with_cache::<NoopTracer>(
+        &new_text,
+        previous.cache,
+        &TextEdit::new(24..25, ""),
+        &settings,
+        (),
+    );
+    let expected = Document::parse_advanced::<NoopTracer>(&new_text, &settings, ());
+    assert_eq!(reparsed.result, expected);
+    assert!(!reparsed.result.unwrap_err().context.is_empty());
+}
+
+#[test]
+fn test_extern_without_examined_length() {
+    // The function might have looked at the whole input, so the results before the edit can't
+    // be reused, only the ones after it.
+    let text = "aa;bb;cc;dd;";
+    let previous = Words::parse_incremental(text);
+    word_calls();
+    let new_text = apply_edit(text, 9..11, "x");
+    let reparsed = Words::reparse(previous, &new_text, &TextEdit::new(9..11, "x"));
+    assert_eq!(word_calls(), 4);
+    assert_eq!(reparsed.result, Words::parse(&new_text));
+    word_calls();
+
+    let new_text = apply_edit(&new_text, 0..2, "y");
+    let reparsed = Words::reparse(reparsed, &new_text, &TextEdit::new(0..2, "y"));
+    assert_eq!(word_calls(), 1);
+    assert_eq!(reparsed.result, Words::parse(&new_text));
+}
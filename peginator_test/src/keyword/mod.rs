@@ -0,0 +1,55 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::{ParseErrorSpecifics, PegParser};
+
+#[test]
+fn test_keywords() {
+    let result = Statement::parse("if x then y").unwrap();
+    assert_eq!(
+        result,
+        Statement::IfStatement(IfStatement {
+            condition: "x".into(),
+            body: "y".into()
+        })
+    );
+    let result = Statement::parse("iffy = thenx").unwrap();
+    assert_eq!(
+        result,
+        Statement::Assignment(Assignment {
+            name: "iffy".into(),
+            value: "thenx".into()
+        })
+    );
+    let err = Statement::parse("if x thenx").unwrap_err();
+    assert_eq!(err.position, 5);
+    assert!(err
+        .specifics
+        .contains(&ParseErrorSpecifics::ExpectedKeyword { keyword: "then" }));
+}
+
+#[test]
+fn test_insensitive_keywords() {
+    assert!(Statement::parse("SELECT a").is_ok());
+    assert!(Statement::parse("Select a").is_ok());
+    assert!(Statement::parse("selected = a").is_ok());
+}
+
+#[test]
+fn test_not_keyword() {
+    let err = Statement::parse("x = if").unwrap_err();
+    assert_eq!(err.position, 4);
+    assert_eq!(
+        err.specifics,
+        [ParseErrorSpecifics::UnexpectedKeyword { keyword: "if" }]
+    );
+    let err = Statement::parse("x = SeLeCt").unwrap_err();
+    assert_eq!(
+        err.specifics,
+        [ParseErrorSpecifics::UnexpectedKeyword { keyword: "select" }]
+    );
+    assert!(Statement::parse("x = selection").is_ok());
+}
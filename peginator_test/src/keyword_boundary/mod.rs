@@ -0,0 +1,21 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::PegParser;
+
+#[test]
+fn test_custom_boundary() {
+    let result = Expression::parse("(define x y)").unwrap();
+    assert_eq!(
+        result,
+        Expression::Definition(Definition {
+            name: "x".into(),
+            value: "y".into()
+        })
+    );
+    assert!(Expression::parse("(define-syntax x y)").is_err());
+    assert!(Expression::parse("(define? x y)").is_err());
+}
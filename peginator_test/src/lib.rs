@@ -6,6 +6,9 @@
 pub(crate) mod test_utils;
 
 mod additional_traits;
+mod borrowed_strings;
+mod bounded_closure;
+mod byte_input;
 mod calculator_example;
 mod char_rule;
 pub mod check;
@@ -13,23 +16,42 @@ mod choice;
 mod closure;
 mod custom_derives_empty;
 mod custom_whitespace;
+mod cut;
 mod enums;
 mod eoi;
+mod error_alternatives;
+mod error_context;
+mod extends;
 pub mod extern_directive;
 mod field;
 mod fndef_example;
+mod import;
 mod include_rule;
+mod incremental;
+mod keyword;
+mod keyword_boundary;
+mod line_index;
 mod lookahead;
 mod memoization;
+mod named_rule;
 mod operator_example;
 mod optional;
 mod palindrome;
+mod parameterized_rules;
+mod parse_iter;
 mod position;
 mod precedence;
+mod precedence_climbing;
+mod prefix;
+mod pretty_error;
+mod recover;
 mod rust_keywords;
+mod separated_closure;
 mod sequence;
 mod simple;
 mod skip_ws;
+mod stream;
 mod string;
 mod string_insensitive;
+mod unicode_class;
 pub mod user_defined_state;
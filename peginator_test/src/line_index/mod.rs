@@ -0,0 +1,73 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::{ColumnEncoding, LineColumn, LineIndex, PegParser, PegPosition};
+
+const TEXT: &str = "a: b;\r\n  üñ: 😀x;\n";
+
+fn lc(line: usize, column: usize) -> LineColumn {
+    LineColumn { line, column }
+}
+
+#[test]
+fn test_lines() {
+    let index = LineIndex::new(TEXT);
+    assert_eq!(index.line_count(), 3);
+    assert_eq!(index.line_text(0), Some("a: b;"));
+    assert_eq!(index.line_text(1), Some("  üñ: 😀x;"));
+    assert_eq!(index.line_text(2), Some(""));
+    assert_eq!(index.line_text(3), None);
+    assert_eq!(index.line_range(1), Some(7..21));
+}
+
+#[test]
+fn test_positions() {
+    let document = Document::parse(TEXT).unwrap();
+    let index = LineIndex::new(TEXT);
+    let position = document.items[1].position().clone();
+    assert_eq!(position, 9..21);
+
+    assert_eq!(
+        index.line_column_range(position.clone(), ColumnEncoding::Chars),
+        lc(1, 2)..lc(1, 9)
+    );
+    assert_eq!(
+        index.line_column_range(position.clone(), ColumnEncoding::Utf8),
+        lc(1, 2)..lc(1, 14)
+    );
+    assert_eq!(
+        index.line_column_range(position.clone(), ColumnEncoding::Utf16),
+        lc(1, 2)..lc(1, 10)
+    );
+
+    for encoding in [
+        ColumnEncoding::Chars,
+        ColumnEncoding::Utf8,
+        ColumnEncoding::Utf16,
+    ] {
+        let line_column = index.line_column_range(position.clone(), encoding);
+        assert_eq!(
+            index.offset_range(line_column, encoding),
+            Some(position.clone())
+        );
+    }
+}
+
+#[test]
+fn test_edge_cases() {
+    let index = LineIndex::new(TEXT);
+    // Inside the emoji
+    assert_eq!(index.line_column(17, ColumnEncoding::Utf16), lc(1, 6));
+    assert_eq!(index.offset(lc(1, 7), ColumnEncoding::Utf16), Some(15));
+    // Line endings and the end of the text
+    assert_eq!(index.line_column(5, ColumnEncoding::Chars), lc(0, 5));
+    assert_eq!(index.line_column(6, ColumnEncoding::Chars), lc(0, 6));
+    assert_eq!(index.line_column(22, ColumnEncoding::Chars), lc(2, 0));
+    assert_eq!(index.line_column(100, ColumnEncoding::Chars), lc(2, 0));
+    // Past the end of the line
+    assert_eq!(index.offset(lc(0, 100), ColumnEncoding::Chars), Some(5));
+    assert_eq!(index.offset(lc(3, 0), ColumnEncoding::Chars), None);
+}
@@ -0,0 +1,60 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::{ParseErrorSpecifics, PegParser, PrettyParseError};
+
+#[test]
+fn test_ok() {
+    let result = Assignment::parse("x = 12.5;").unwrap();
+    assert_eq!(result.name, "x");
+    assert_eq!(result.value, Value::Number("12.5".to_string()));
+}
+
+#[test]
+fn test_named() {
+    let err = Assignment::parse("= x;").unwrap_err();
+    assert_eq!(err.position, 0);
+    assert_eq!(
+        err.specifics,
+        vec![ParseErrorSpecifics::ExpectedRule { name: "identifier" }]
+    );
+    assert_eq!(err.message(), "expected identifier but found '='");
+}
+
+#[test]
+fn test_named_alternatives() {
+    let err = Assignment::parse("x = ;").unwrap_err();
+    assert_eq!(err.position, 4);
+    assert_eq!(
+        err.specifics,
+        vec![
+            ParseErrorSpecifics::ExpectedRule { name: "identifier" },
+            ParseErrorSpecifics::ExpectedRule { name: "number" },
+        ]
+    );
+    assert_eq!(
+        err.message(),
+        "expected one of identifier, number but found ';'"
+    );
+}
+
+#[test]
+fn test_inside_rule() {
+    let err = Assignment::parse("x = 1.;").unwrap_err();
+    assert_eq!(err.position, 6);
+    assert_eq!(
+        err.specifics,
+        vec![ParseErrorSpecifics::ExpectedCharacterRange { from: '0', to: '9' }]
+    );
+}
+
+#[test]
+fn test_pretty() {
+    let text = "x = ;";
+    let err = Assignment::parse(text).unwrap_err();
+    let pretty = PrettyParseError::from_parse_error(&err, text, None).to_string();
+    assert!(pretty.contains("expected one of identifier, number but found ';'"));
+}
@@ -0,0 +1,49 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::PegParser;
+
+#[test]
+fn test_comma_list() {
+    let call = Call::parse("f(a, 12, g(b,),)").unwrap();
+    assert_eq!(call.name, "f");
+    let args = call.args.unwrap().items;
+    assert_eq!(args.len(), 3);
+    assert!(matches!(&args[0], Expr::Identifier(s) if s == "a"));
+    assert!(matches!(&args[1], Expr::Number(s) if s == "12"));
+    let Expr::Call(inner) = &args[2] else {
+        panic!("Expected a call, got {:?}", args[2]);
+    };
+    assert_eq!(inner.args.as_ref().unwrap().items.len(), 1);
+
+    assert!(Call::parse("f()").unwrap().args.is_none());
+    assert!(Call::parse("f(,)").is_err());
+}
+
+#[test]
+fn test_multiple_parameters() {
+    let table = Table::parse("[1, 2]; []; [3]").unwrap();
+    let rows: Vec<Vec<String>> = table
+        .rows
+        .items
+        .into_iter()
+        .map(|row| row.cells.map(|c| c.items).unwrap_or_default())
+        .collect();
+    assert_eq!(rows, vec![vec!["1", "2"], vec![], vec!["3"]]);
+
+    let pairs = Pairs::parse("a: 1 bc: 23").unwrap().pairs;
+    assert_eq!(pairs.len(), 2);
+    assert_eq!(pairs[1].key, "bc");
+    assert_eq!(pairs[1].value, "23");
+}
+
+#[test]
+fn test_nested_arguments() {
+    let nested = Nested::parse("([1, 2], [3,])").unwrap();
+    let lists: Vec<Vec<String>> = nested.lists.items.into_iter().map(|l| l.items).collect();
+    assert_eq!(lists, vec![vec!["1", "2"], vec!["3"]]);
+    assert!(Nested::parse("([])").is_err());
+}
@@ -0,0 +1,59 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::PegParser;
+
+#[test]
+fn test_iter() {
+    let s = "INFO: started;\n  ERROR: failed;\nINFO:done;\n\n";
+    let entries: Vec<Entry> = Entry::parse_iter(s).collect::<Result<_, _>>().unwrap();
+    assert_eq!(
+        entries,
+        vec![
+            Entry {
+                level: Level::Info(Info),
+                message: "started".into(),
+                position: 0..14,
+            },
+            Entry {
+                level: Level::Error(Error),
+                message: "failed".into(),
+                position: 17..31,
+            },
+            Entry {
+                level: Level::Info(Info),
+                message: "done".into(),
+                position: 32..42,
+            },
+        ]
+    );
+    assert_eq!(Entry::parse_iter("").count(), 0);
+    assert_eq!(Entry::parse_iter(" \n ").count(), 0);
+}
+
+#[test]
+fn test_error() {
+    let s = "INFO: started;\nWARNING: what;\nINFO: done;";
+    let mut iter = Entry::parse_iter(s);
+    assert!(iter.next().unwrap().is_ok());
+    assert_eq!(iter.position(), 14);
+    let err = iter.next().unwrap().unwrap_err();
+    assert_eq!(err.position, 15);
+    assert!(iter.next().is_none());
+}
+
+#[test]
+fn test_empty_item() {
+    let results: Vec<_> = Empty::parse_iter("INFO INFO x").collect();
+    assert_eq!(
+        results,
+        vec![
+            Ok(Empty { x: Some(Info) }),
+            Ok(Empty { x: Some(Info) }),
+            Ok(Empty { x: None })
+        ]
+    );
+}
@@ -0,0 +1,114 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::PegParser;
+
+fn calculate(expression: &Expression) -> i64 {
+    match expression {
+        Expression::Binary(ExpressionBinary {
+            left,
+            operator,
+            right,
+        }) => {
+            let left = calculate(left);
+            let right = calculate(right);
+            match operator {
+                ExpressionBinaryOperator::Add => left + right,
+                ExpressionBinaryOperator::Sub => left - right,
+                ExpressionBinaryOperator::Mul => left * right,
+                ExpressionBinaryOperator::Div => left / right,
+                ExpressionBinaryOperator::Pow => left.pow(right as u32),
+            }
+        }
+        Expression::Unary(ExpressionUnary { operator, operand }) => {
+            let operand = calculate(operand);
+            match operator {
+                ExpressionUnaryOperator::Neg => -operand,
+                ExpressionUnaryOperator::Factorial => (1..=operand).product(),
+            }
+        }
+        Expression::Factor(Factor::Group(group)) => calculate(&group.body),
+        Expression::Factor(Factor::Number(number)) => number.parse().unwrap(),
+    }
+}
+
+fn number(n: &str) -> Box<Expression> {
+    Box::new(Expression::Factor(Factor::Number(n.into())))
+}
+
+#[test]
+fn test_tree() {
+    assert_eq!(
+        Expression::parse("1 - 2 * 3").unwrap(),
+        Expression::Binary(ExpressionBinary {
+            left: number("1"),
+            operator: ExpressionBinaryOperator::Sub,
+            right: Box::new(Expression::Binary(ExpressionBinary {
+                left: number("2"),
+                operator: ExpressionBinaryOperator::Mul,
+                right: number("3"),
+            })),
+        })
+    );
+    assert_eq!(
+        Expression::parse("-2!").unwrap(),
+        Expression::Unary(ExpressionUnary {
+            operator: ExpressionUnaryOperator::Neg,
+            operand: Box::new(Expression::Unary(ExpressionUnary {
+                operator: ExpressionUnaryOperator::Factorial,
+                operand: number("2"),
+            })),
+        })
+    );
+}
+
+#[test]
+fn test_calculate() {
+    let calc = |s| calculate(&Statement::parse(s).unwrap().expr);
+    assert_eq!(calc("1 + 2 * 3"), 7);
+    assert_eq!(calc("(1 + 2) * 3"), 9);
+    // Left associative
+    assert_eq!(calc("10 - 4 - 3"), 3);
+    assert_eq!(calc("100 / 10 / 5"), 2);
+    // Right associative
+    assert_eq!(calc("2 ^ 3 ^ 2"), 512);
+    // Prefix operators bind looser than the tighter levels
+    assert_eq!(calc("-2 ^ 2"), -4);
+    assert_eq!(calc("-2 * 3"), -6);
+    assert_eq!(calc("1 - -2"), 3);
+    assert_eq!(calc("3! * 2"), 12);
+    assert_eq!(calc("2 ^ 3!"), 64);
+    assert_eq!(calc("(1 + 2)!"), 6);
+}
+
+#[test]
+fn test_errors() {
+    // The unfinished operation is not part of the expression
+    let err = Statement::parse("1 + 2 *").unwrap_err();
+    assert_eq!(err.position, 7);
+    assert!(Statement::parse("1 + * 2").is_err());
+    assert!(Statement::parse("*").is_err());
+}
+
+#[test]
+fn test_keyword_operators() {
+    assert_eq!(
+        Condition::parse("not a and b").unwrap(),
+        Condition::Binary(ConditionBinary {
+            left: Box::new(Condition::Unary(ConditionUnary {
+                operator: ConditionUnaryOperator::Not,
+                operand: Box::new(Condition::Variable("a".into())),
+            })),
+            operator: ConditionBinaryOperator::And,
+            right: Box::new(Condition::Variable("b".into())),
+        })
+    );
+    assert_eq!(
+        Condition::parse("nota").unwrap(),
+        Condition::Variable("nota".into())
+    );
+    assert!(Condition::parse("or").is_err());
+}
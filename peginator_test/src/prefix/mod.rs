@@ -0,0 +1,45 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::PegParser;
+
+#[test]
+fn test_prefix() {
+    assert_eq!(
+        Assignment::parse_prefix("x = 12 rest").unwrap(),
+        (
+            Assignment {
+                name: "x".into(),
+                value: "12".into(),
+                position: 0..6,
+            },
+            6
+        )
+    );
+    assert_eq!(Assignment::parse_prefix("abc=1").unwrap().1, 5);
+    assert_eq!(Assignment::parse_prefix("x = 12  ").unwrap().1, 6);
+    assert!(Assignment::parse_prefix("x = ").is_err());
+}
+
+#[test]
+fn test_at() {
+    let s = "let x = 12; let yy = 3;";
+    let (first, end) = Assignment::parse_at(s, 4).unwrap();
+    assert_eq!(first.name, "x");
+    assert_eq!(first.position, 4..10);
+    assert_eq!(end, 10);
+    let (second, end) = Assignment::parse_at(s, s[end..].find("yy").unwrap() + end).unwrap();
+    assert_eq!(second.name, "yy");
+    assert_eq!(second.position, 16..22);
+    assert_eq!(end, 22);
+    assert_eq!(
+        Assignment::parse_at(s, s.len()).unwrap_err().position,
+        s.len()
+    );
+
+    let err = Assignment::parse_at(s, 8).unwrap_err();
+    assert_eq!(err.position, 8);
+}
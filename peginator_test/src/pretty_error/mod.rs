@@ -0,0 +1,197 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::{
+    runtime::{NoopTracer, PegParserAdvanced},
+    Diagnostic, DiagnosticLine, ParseSettings, PegParser, PrettyParseError,
+    PrettyParseErrorSettings,
+};
+
+/// Remove the color codes, so that the tests do not depend on the terminal
+fn strip_colors(s: &str) -> String {
+    let mut result = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            for c in chars.by_ref() {
+                if c == 'm' {
+                    break;
+                }
+            }
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+fn pretty(text: &str, settings: &PrettyParseErrorSettings) -> String {
+    let err = Program::parse(text).unwrap_err();
+    strip_colors(
+        &PrettyParseError::from_parse_error_with_settings(&err, text, None, settings).to_string(),
+    )
+}
+
+#[test]
+fn test_span() {
+    let text = "a = b;\nc = d e;";
+    let err = Program::parse(text).unwrap_err();
+    assert_eq!(err.position, 13);
+    assert_eq!(err.span, 13..14);
+    assert_eq!(
+        pretty(text, &Default::default()),
+        "expected ';' but found 'e'\n\
+         --> Line 2 character 7\n \
+         |  \n \
+         |  c = d e;\n \
+         |        ^\n"
+    );
+}
+
+#[test]
+fn test_tabs() {
+    let text = "a = b;\n\tc\t=\t;";
+    assert_eq!(
+        pretty(text, &Default::default()),
+        "expected identifier but found ';'\n\
+         --> Line 2 character 6\n \
+         |  \n \
+         |      c   =   ;\n \
+         |              ^\n"
+    );
+}
+
+#[test]
+fn test_wide_characters() {
+    let text = "漢字 = 漢 字;";
+    assert_eq!(
+        pretty(text, &Default::default()),
+        "expected ';' but found '字'\n\
+         --> Line 1 character 8\n \
+         |  \n \
+         |  漢字 = 漢 字;\n \
+         |            ^^\n"
+    );
+}
+
+#[test]
+fn test_crlf() {
+    let text = "a = b;\r\nc = ;\r\nd = e;\r\n";
+    let mut settings = PrettyParseErrorSettings::default();
+    settings.lines_before = 1;
+    settings.lines_after = 1;
+    assert_eq!(
+        pretty(text, &settings),
+        "expected identifier but found ';'\n\
+         --> Line 2 character 5\n \
+         |  \n \
+         |  a = b;\n \
+         |  c = ;\n \
+         |      ^\n \
+         |  d = e;\n"
+    );
+}
+
+#[test]
+fn test_end_of_input() {
+    let text = "{\n  a = b;\n  c = d;\n";
+    let mut settings = PrettyParseErrorSettings::default();
+    settings.lines_before = 3;
+    assert_eq!(
+        pretty(text, &settings),
+        "expected one of '{', identifier, '}' but found end of input\n\
+         --> Line 4 character 1\n \
+         |  \n \
+         |  {\n \
+         |    a = b;\n \
+         |    c = d;\n \
+         |  \n \
+         |  ^\n"
+    );
+}
+
+#[test]
+fn test_multiline_span() {
+    let text = "{\n  a = b;\n  c = d;\n";
+    let mut settings = ParseSettings::default();
+    settings.record_context = true;
+    let err = Program::parse_advanced::<NoopTracer>(text, &settings, ()).unwrap_err();
+    // The span starts at the innermost rule all the expected items were tried in
+    assert_eq!(err.span, 0..20);
+    assert_eq!(
+        strip_colors(&PrettyParseError::from_parse_error(&err, text, Some("test.txt")).to_string()),
+        "expected one of '{', identifier, '}' but found end of input\n\
+         --> test.txt:4:1\n \
+         |  \n \
+         |  {\n \
+         |  ^\n \
+         |    a = b;\n \
+         |  ^^^^^^^^\n \
+         |    c = d;\n \
+         |  ^^^^^^^^\n \
+         = note: while parsing Block in Statement in Program\n"
+    );
+}
+
+#[test]
+fn test_diagnostic() {
+    let text = "a = b;\nc = d e;";
+    let err = Program::parse(text).unwrap_err();
+    let pretty = PrettyParseError::from_parse_error(&err, text, Some("test.txt"));
+    let diagnostic = pretty.diagnostic();
+    assert_eq!(
+        diagnostic,
+        &Diagnostic {
+            message: "expected ';' but found 'e'".to_string(),
+            source_file: Some("test.txt".to_string()),
+            line: 2,
+            column: 7,
+            span: 13..14,
+            note: None,
+            snippet: vec![DiagnosticLine {
+                line_number: 2,
+                text: "c = d e;".to_string(),
+                underline: Some(6..7),
+            }],
+        }
+    );
+    assert_eq!(
+        diagnostic.render_plain(),
+        "expected ';' but found 'e'\n\
+         --> test.txt:2:7\n \
+         |  \n \
+         |  c = d e;\n \
+         |        ^\n"
+    );
+    assert_eq!(
+        diagnostic.render_ansi(),
+        "\x1b[1;37mexpected ';' but found 'e'\x1b[0m\n\
+         \x1b[1;34m--> \x1b[0mtest.txt:2:7\n\
+         \x1b[1;34m |  \x1b[0m\n\
+         \x1b[1;34m |  \x1b[0mc = d e;\n\
+         \x1b[1;34m |  \x1b[0m      \x1b[1;31m^\x1b[0m\n"
+    );
+    assert_eq!(
+        diagnostic.render_json(),
+        r#"{"message":"expected ';' but found 'e'","file":"test.txt","line":2,"column":7,"#
+            .to_string()
+            + r#""span":{"start":13,"end":14},"note":null,"#
+            + r#""snippet":[{"line":2,"text":"c = d e;","underline":{"start":6,"end":7}}]}"#
+    );
+}
+
+#[test]
+fn test_diagnostic_json_escapes() {
+    let text = "\"\\\" = b;";
+    let err = Program::parse(text).unwrap_err();
+    let diagnostic = Diagnostic::from_parse_error(&err, text, None, &Default::default());
+    assert_eq!(
+        diagnostic.render_json(),
+        r#"{"message":"expected one of '{', identifier, end of input but found '\\\"'","file":null,"#.to_string()
+            + r#""line":1,"column":1,"span":{"start":0,"end":1},"note":null,"#
+            + r#""snippet":[{"line":1,"text":"\"\\\" = b;","underline":{"start":0,"end":1}}]}"#
+    );
+}
@@ -0,0 +1,141 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::{ParseError, ParseErrorSpecifics, PegParser};
+
+fn stmt(name: &str, value: &str) -> Result<Statement, ParseError> {
+    Ok(Statement {
+        name: name.into(),
+        value: value.into(),
+    })
+}
+
+#[test]
+fn test_no_errors() {
+    let (result, errors) = Program::parse_with_recovery("a = 1; b = 2;");
+    assert!(errors.is_empty());
+    assert_eq!(
+        result.unwrap().statements,
+        vec![stmt("a", "1"), stmt("b", "2")]
+    );
+}
+
+#[test]
+fn test_recovery() {
+    let (result, errors) = Program::parse_with_recovery("a = 1; b = ; c = 3; d 4; e = 5;");
+    assert_eq!(errors.len(), 2);
+    assert_eq!(
+        result.unwrap().statements,
+        vec![
+            stmt("a", "1"),
+            Err(errors[0].clone()),
+            stmt("c", "3"),
+            Err(errors[1].clone()),
+            stmt("e", "5"),
+        ]
+    );
+    assert_eq!(errors[0].position, 11);
+    assert_eq!(
+        errors[0].specifics,
+        vec![ParseErrorSpecifics::ExpectedCharacterRange { from: '0', to: '9' }]
+    );
+    assert_eq!(errors[1].position, 22);
+    assert_eq!(
+        errors[1].specifics,
+        vec![ParseErrorSpecifics::ExpectedCharacter { c: '=' }]
+    );
+}
+
+#[test]
+fn test_recovery_at_end() {
+    let (result, errors) = Program::parse_with_recovery("a = 1; b =");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        result.unwrap().statements,
+        vec![stmt("a", "1"), Err(errors[0].clone())]
+    );
+    assert_eq!(errors[0].position, 10);
+}
+
+#[test]
+fn test_unrecoverable() {
+    // The second statement is recovered from by skipping to the end of the input, but the
+    // closing brace cannot be.
+    let (result, errors) = Block::parse_with_recovery("{ a = 1; b = 2 ");
+    assert!(result.is_none());
+    assert_eq!(errors.len(), 2);
+    assert_eq!(errors[0].position, 15);
+    assert_eq!(
+        errors[0].specifics,
+        vec![ParseErrorSpecifics::ExpectedCharacter { c: ';' }]
+    );
+    assert_eq!(errors[1].position, 15);
+    assert!(errors[1]
+        .specifics
+        .contains(&ParseErrorSpecifics::ExpectedCharacter { c: '}' }));
+}
+
+#[test]
+fn test_recovery_in_closure() {
+    let (result, errors) = Block::parse_with_recovery("{ a = 1; b = ; c = 3; }");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        result.unwrap().statements,
+        vec![stmt("a", "1"), Err(errors[0].clone()), stmt("c", "3")]
+    );
+    assert_eq!(errors[0].position, 13);
+}
+
+#[test]
+fn test_no_recovery_by_default() {
+    let err = Program::parse("a = 1; b = ; c = 3;").unwrap_err();
+    assert_eq!(err.position, 11);
+}
+
+#[test]
+fn test_valid_input_unchanged() {
+    let expected = Block::parse("{ a = 1; b = 2; }").unwrap();
+    let (result, errors) = Block::parse_with_recovery("{ a = 1; b = 2; }");
+    assert!(errors.is_empty());
+    assert_eq!(result.unwrap(), expected);
+}
+
+#[test]
+fn test_recovered_error_has_found() {
+    let (result, errors) = Program::parse_with_recovery("a = x;");
+    let Err(error) = &result.unwrap().statements[0] else {
+        panic!("Expected an error node");
+    };
+    assert_eq!(error.position, 4);
+    assert!(error.found.is_some());
+    assert_eq!(errors, vec![error.clone()]);
+}
+
+#[test]
+fn test_no_errors_from_discarded_alternatives() {
+    // The first alternative recovers from the missing '=', but then fails on the missing '!'.
+    let (result, errors) = Script::parse_with_recovery("f(x);");
+    assert!(errors.is_empty());
+    assert_eq!(
+        result.unwrap().items,
+        vec![Item::Call(Call {
+            function: "f".into(),
+            argument: "x".into(),
+        })]
+    );
+}
+
+#[test]
+fn test_errors_from_memoized_rules() {
+    // The second alternative gets the recovered statement from the cache.
+    let (result, errors) = Listing::parse_with_recovery("{ a = ; } .");
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].position, 6);
+    assert_eq!(
+        result.unwrap().items.statements,
+        vec![Err(errors[0].clone())]
+    );
+}
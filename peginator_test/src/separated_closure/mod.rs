@@ -0,0 +1,61 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::PegParser;
+
+fn names(params: &[Param]) -> Vec<&str> {
+    params.iter().map(|p| p.name.as_str()).collect()
+}
+
+#[test]
+fn test_separated() {
+    assert!(Params::parse("()").unwrap().params.is_empty());
+    assert_eq!(names(&Params::parse("(a: x)").unwrap().params), ["a"]);
+    assert_eq!(
+        names(&Params::parse("(a: x, b: y , c:z)").unwrap().params),
+        ["a", "b", "c"]
+    );
+    assert!(Params::parse("(a: x,)").is_err());
+    assert!(Params::parse("(,)").is_err());
+    assert!(Params::parse("(a: x b: y)").is_err());
+}
+
+#[test]
+fn test_trailing_separator() {
+    assert!(TrailingParams::parse("()").unwrap().params.is_empty());
+    assert_eq!(
+        names(&TrailingParams::parse("(a: x, b: y)").unwrap().params),
+        ["a", "b"]
+    );
+    assert_eq!(
+        names(&TrailingParams::parse("(a: x, b: y,)").unwrap().params),
+        ["a", "b"]
+    );
+    assert!(TrailingParams::parse("(,)").is_err());
+    assert!(TrailingParams::parse("(a: x,,)").is_err());
+}
+
+#[test]
+fn test_at_least_one() {
+    assert_eq!(
+        names(&NonEmptyParams::parse("(a: x)").unwrap().params),
+        ["a"]
+    );
+    assert!(NonEmptyParams::parse("()").is_err());
+}
+
+#[test]
+fn test_separator_backtracking() {
+    let (path, length) = Path::parse_prefix("std::io.Read").unwrap();
+    assert_eq!(path.segments, ["std", "io"]);
+    assert_eq!(length, 7);
+    let path = Path::parse("std::io::read").unwrap();
+    assert_eq!(path.segments, ["std", "io", "read"]);
+    assert!(path.rest.is_none());
+    let path = Path::parse("std::io::*").unwrap();
+    assert_eq!(path.segments, ["std", "io"]);
+    assert!(path.rest.is_some());
+}
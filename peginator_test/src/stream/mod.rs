@@ -0,0 +1,130 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use std::io::{self, Read};
+
+use grammar::*;
+use peginator::{
+    runtime::{ChunkSource, StreamParseIter, StreamSource},
+    PegParser, StreamParseError,
+};
+
+const TEXT: &str = "alma = 12\nkörte=3 szilva = 456\n\n  ";
+
+/// Reads one byte at a time, or fails at the given position
+struct SlowReader<'a> {
+    data: &'a [u8],
+    fail_at: Option<usize>,
+    position: usize,
+}
+
+impl Read for SlowReader<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        if Some(self.position) == self.fail_at {
+            return Err(io::Error::other("broken pipe"));
+        }
+        let Some(b) = self.data.get(self.position) else {
+            return Ok(0);
+        };
+        buf[0] = *b;
+        self.position += 1;
+        Ok(1)
+    }
+}
+
+fn keys(entries: &[Entry]) -> Vec<&str> {
+    entries.iter().map(|e| e.key.as_str()).collect()
+}
+
+#[test]
+fn test_reader() {
+    let expected: Vec<Entry> = Entry::parse_iter(TEXT).collect::<Result<_, _>>().unwrap();
+    assert_eq!(keys(&expected), ["alma", "körte", "szilva"]);
+
+    let entries: Vec<Entry> = Entry::parse_reader(TEXT.as_bytes())
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(entries, expected);
+
+    let reader = SlowReader {
+        data: TEXT.as_bytes(),
+        fail_at: None,
+        position: 0,
+    };
+    let entries: Vec<Entry> = Entry::parse_reader(reader)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(entries, expected);
+}
+
+#[test]
+fn test_chunks() {
+    let expected: Vec<Entry> = Entry::parse_iter(TEXT).collect::<Result<_, _>>().unwrap();
+    for chunk_size in 1..TEXT.len() {
+        let chunks = TEXT.as_bytes().chunks(chunk_size);
+        let entries: Vec<Entry> = Entry::parse_chunks(chunks)
+            .collect::<Result<_, _>>()
+            .unwrap();
+        assert_eq!(entries, expected, "chunk size {chunk_size}");
+    }
+    assert_eq!(Entry::parse_chunks(["", "a=1", "", "", "b=2"]).count(), 2);
+    assert_eq!(Entry::parse_chunks(Vec::<&str>::new()).count(), 0);
+}
+
+/// Counts the reads, each of which is followed by a reparse of the current item
+struct CountingSource<S> {
+    source: S,
+    reads: usize,
+}
+
+impl<S: StreamSource> StreamSource for &mut CountingSource<S> {
+    fn read_chunk(&mut self, buffer: &mut Vec<u8>) -> io::Result<usize> {
+        self.reads += 1;
+        self.source.read_chunk(buffer)
+    }
+}
+
+#[test]
+fn test_small_chunks() {
+    let text = format!("a={} bb=2", "1".repeat(10000));
+    let mut source = CountingSource {
+        source: ChunkSource(text.as_bytes().chunks(1)),
+        reads: 0,
+    };
+    let entries: Vec<Entry> = StreamParseIter::<Entry, _>::new(&mut source)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(keys(&entries), ["a", "bb"]);
+    assert_eq!(entries[0].value.len(), 10000);
+    // Reading one byte at a time would reparse the first item 10000 times
+    assert!(source.reads < 40, "{} reads", source.reads);
+}
+
+#[test]
+fn test_errors() {
+    let mut iter = Entry::parse_chunks(["a=1 b=2", ".5 c=3"]);
+    assert_eq!(iter.next().unwrap().unwrap().key, "a");
+    let Some(Err(StreamParseError::Parse(err))) = iter.next() else {
+        panic!("Expected a parse error");
+    };
+    assert_eq!(err.position, 7);
+    assert!(iter.next().is_none());
+
+    let reader = SlowReader {
+        data: TEXT.as_bytes(),
+        fail_at: Some(12),
+        position: 0,
+    };
+    let results: Vec<_> = Entry::parse_reader(reader).collect();
+    assert_eq!(results.len(), 2);
+    assert!(matches!(results[1], Err(StreamParseError::Io(_))));
+
+    let results: Vec<_> = Entry::parse_chunks([b"a=1 b=2 \xff" as &[u8]]).collect();
+    assert_eq!(results.len(), 3);
+    let Err(StreamParseError::Io(err)) = &results[2] else {
+        panic!("Expected an I/O error");
+    };
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
@@ -47,3 +47,27 @@ fn test_sensitivity() {
     assert!(Char2::parse("B").is_err());
     assert!(Char2::parse("Ű").is_err());
 }
+
+#[test]
+fn test_unicode() {
+    assert!(Unicode1::parse("straße").is_ok());
+    assert!(Unicode1::parse("STRAẞE").is_ok());
+    assert!(Unicode1::parse("Straße!").is_ok());
+    assert!(Unicode1::parse("strase").is_err());
+    assert!(Unicode1::parse("straß").is_err());
+    assert_eq!(Unicode1::parse_prefix("STRAẞE").unwrap().1, "STRAẞE".len());
+
+    assert!(Unicode2::parse("école").is_ok());
+    assert!(Unicode2::parse("École").is_ok());
+    assert!(Unicode2::parse("ECOLE").is_err());
+    let err = Unicode2::parse("ecole").unwrap_err();
+    assert_eq!(err.position, 0);
+    assert_eq!(
+        err.specifics,
+        [peginator::ParseErrorSpecifics::ExpectedString { s: "école" }]
+    );
+
+    assert!(UnicodeChar::parse("ű").is_ok());
+    assert!(UnicodeChar::parse("Ű").is_ok());
+    assert!(UnicodeChar::parse("u").is_err());
+}
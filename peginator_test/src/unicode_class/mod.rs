@@ -0,0 +1,45 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+mod grammar;
+use grammar::*;
+use peginator::PegParser;
+
+#[test]
+fn test_identifiers() {
+    let result = Assignment::parse("_változó2 = 42").unwrap();
+    assert_eq!(result.name, "_változó2");
+    assert_eq!(result.value, "42");
+    let result = Assignment::parse("переменная = ١٢").unwrap();
+    assert_eq!(result.name, "переменная");
+    assert_eq!(result.value, "١٢");
+    let result = Assignment::parse("変数 = 7").unwrap();
+    assert_eq!(result.name, "変数");
+
+    let err = Assignment::parse("2x = 1").unwrap_err();
+    assert_eq!(err.position, 0);
+    assert_eq!(
+        err.specifics,
+        [peginator::ParseErrorSpecifics::ExpectedCharacterClass {
+            name: "IdentifierStart"
+        }]
+    );
+}
+
+#[test]
+fn test_inline_classes() {
+    assert_eq!(
+        Words::parse("árvíztűrő\u{3000}tükörfúró ").unwrap().words,
+        ["árvíztűrő", "tükörfúró"]
+    );
+    let err = Words::parse("abc\tdef").unwrap_err();
+    assert_eq!(err.position, 3);
+    assert!(err.to_string().contains("\\p{Zs}"));
+}
+
+#[test]
+fn test_script() {
+    assert_eq!(Greek::parse("αβ-γ").unwrap().letters, ['α', 'β', '-', 'γ']);
+    assert!(Greek::parse("abc").is_err());
+}
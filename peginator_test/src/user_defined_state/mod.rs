@@ -5,8 +5,8 @@
 mod grammar;
 use grammar::*;
 use peginator::{
-    runtime::{NoopTracer, PegParserAdvanced},
-    ParseSettings,
+    runtime::{NoopTracer, PegIncrementalParserAdvanced, PegParserAdvanced},
+    ParseSettings, TextEdit,
 };
 
 pub struct TheState {
@@ -28,3 +28,23 @@ fn test() {
         Test { f1: 43, f2: 44 }
     );
 }
+
+#[test]
+fn test_incremental() {
+    let parsed = Test::parse_advanced_with_cache::<NoopTracer>(
+        "abc",
+        Default::default(),
+        &TextEdit::default(),
+        &ParseSettings::default(),
+        TheState { a: 42 },
+    );
+    assert_eq!(parsed.result, Ok(Test { f1: 43, f2: 44 }));
+    let reparsed = Test::parse_advanced_with_cache::<NoopTracer>(
+        "xbc",
+        parsed.cache,
+        &TextEdit::new(0..1, "x"),
+        &ParseSettings::default(),
+        TheState { a: 10 },
+    );
+    assert_eq!(reparsed.result, Ok(Test { f1: 11, f2: 12 }));
+}
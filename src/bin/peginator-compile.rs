@@ -2,14 +2,14 @@
 // This file is part of peginator
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
-use std::fs;
+use std::path::Path;
 
 use anyhow::Result;
 use clap::Parser;
 use colored::*;
-use peginator::codegen::{generate_source_header, CodegenGrammar, CodegenSettings};
-use peginator::grammar::Grammar;
-use peginator::{PegParser, PrettyParseError};
+use peginator::codegen::{generate_loaded_source_header, CodegenGrammar, CodegenSettings};
+use peginator::grammar::{Grammar, LoadedGrammar};
+use peginator::PegParser;
 use quote::quote;
 
 /// Compile EBNF grammar into rust parser code.
@@ -32,20 +32,31 @@ struct Args {
     #[clap(short, long)]
     derives: Vec<String>,
 
+    /// Generate parsers that work on byte slices instead of strings
+    #[clap(short, long)]
+    byte_input: bool,
+
+    /// Make @string rules borrow from the input instead of allocating
+    #[clap(long)]
+    borrowed_strings: bool,
+
+    /// Generate the code needed for incremental reparsing
+    #[clap(long)]
+    incremental: bool,
+
     grammar_file: String,
 }
 
 fn main_wrap() -> Result<()> {
     let args = Args::parse();
-    let grammar = fs::read_to_string(&args.grammar_file)?;
-    let parsed_grammar = if args.trace {
-        Grammar::parse_with_trace(&grammar)
+    let parse = if args.trace {
+        Grammar::parse_with_trace
     } else {
-        Grammar::parse(&grammar)
-    }
-    .map_err(|err| PrettyParseError::from_parse_error(&err, &grammar, Some(&args.grammar_file)))?;
+        Grammar::parse
+    };
+    let loaded_grammar = LoadedGrammar::load_with(Path::new(&args.grammar_file), parse)?;
     if args.ast_only {
-        println!("{:#?}", parsed_grammar);
+        println!("{:#?}", loaded_grammar.grammar);
         return Ok(());
     }
 
@@ -58,9 +69,15 @@ fn main_wrap() -> Result<()> {
             args.derives
         },
         user_defined_type: quote!(()),
+        byte_input: args.byte_input,
+        borrowed_strings: args.borrowed_strings,
+        incremental: args.incremental,
     };
-    let generated_code = parsed_grammar.generate_code(&settings)?;
-    println!("{}", generate_source_header(&grammar, false));
+    let generated_code = loaded_grammar.grammar.generate_code(&settings)?;
+    println!(
+        "{}",
+        generate_loaded_source_header(&loaded_grammar.sources, false)
+    );
     println!("{}", generated_code);
     Ok(())
 }
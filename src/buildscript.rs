@@ -17,20 +17,19 @@ use std::{
 use anyhow::Result;
 use colored::*;
 
-use crate::codegen::{generate_source_header, CodegenGrammar, CodegenSettings};
-use crate::grammar::Grammar;
-use crate::{PegParser, PrettyParseError};
+use crate::codegen::{generate_loaded_source_header, CodegenGrammar, CodegenSettings};
+use crate::grammar::LoadedGrammar;
 
 /// Compiles peginator grammars into rust code with a builder interface.
 ///
 /// It only recompiles files if it detects (based on the generated file header in the `.rs` file)
-/// change in either the peginator library, or the grammar file.
+/// change in either the peginator library, or the grammar file (including the files it
+/// `@import`s or `@extends`).
 ///
 /// It is meant to be used as `peginator::buildscript::Compile`, hence the generic name.
 ///
 /// Example `build.rs` for using a single grammar file and putting the result in the target directory:
 /// ```no_run
-///# #[allow(clippy::needless_doctest_main)]
 /// fn main() {
 ///     peginator::buildscript::Compile::file("my_grammar.ebnf")
 ///        .destination(format!("{}/my_grammar.rs", std::env::var("OUT_DIR").unwrap()))
@@ -48,7 +47,6 @@ use crate::{PegParser, PrettyParseError};
 /// Example `build.rs` for multiple grammar files in the src directory, putting compiled files next to
 /// their grammar definitions:
 /// ```no_run
-///# #[allow(clippy::needless_doctest_main)]
 /// fn main() {
 ///     peginator::buildscript::Compile::directory("src")
 ///        .format()
@@ -58,6 +56,7 @@ use crate::{PegParser, PrettyParseError};
 ///
 #[derive(Debug)]
 #[must_use]
+#[allow(clippy::needless_doctest_main)]
 pub struct Compile {
     source_path: PathBuf,
     destination_path: Option<PathBuf>,
@@ -84,6 +83,9 @@ impl Compile {
     ///
     /// The whole directory is recursively searched for files with the `.ebnf` extension, and
     /// compiled to rust code with the same filename but with `.rs` extension.
+    ///
+    /// Files that are only `@import`-ed by other grammars are compiled too, so they either have to
+    /// be complete grammars themselves, or use a different extension.
     pub fn directory<T: Into<PathBuf>>(filename: T) -> Self {
         Compile {
             source_path: filename.into(),
@@ -155,11 +157,45 @@ impl Compile {
         self
     }
 
+    /// Generate parsers that work on `&[u8]` instead of `&str`.
+    ///
+    /// The exported rules implement [`PegByteParser`](crate::PegByteParser) instead of
+    /// [`PegParser`](crate::PegParser).
+    pub fn byte_input(mut self) -> Self {
+        self.settings.byte_input = true;
+        self
+    }
+
+    /// Make `@string` rules return `&'a str` (or `&'a [u8]`) borrowed from the input instead of
+    /// allocating a `String`. `@extern` rules without an explicit return type borrow too.
+    ///
+    /// All types containing these get an `'a` lifetime parameter. The exported rules implement
+    /// [`PegBorrowedParser`](crate::PegBorrowedParser).
+    pub fn borrowed_strings(mut self) -> Self {
+        self.settings.borrowed_strings = true;
+        self
+    }
+
+    /// Generate the code needed for reparsing an edited input, reusing the results of `@memoize`
+    /// and `@leftrec` rules outside the edit. The results of other rules are not kept.
+    ///
+    /// A reparse still walks the reused results one by one and clones them, so it takes time
+    /// proportional to the number of reused results and the size of their ASTs, only with less
+    /// parsing. See
+    /// [Incremental reparsing](crate#incremental-reparsing).
+    ///
+    /// The exported rules implement [`PegIncrementalParser`](crate::PegIncrementalParser) too.
+    /// Cannot be used together with [`Compile::borrowed_strings`].
+    pub fn incremental(mut self) -> Self {
+        self.settings.incremental = true;
+        self
+    }
+
     fn run_on_single_file(&self, source: &PathBuf, destination: &PathBuf) -> Result<()> {
-        let grammar = fs::read_to_string(source)?;
+        let loaded_grammar = LoadedGrammar::load(source)?;
         let source_header = format!(
             "{}\n{}",
-            generate_source_header(&grammar, self.use_peginator_build_time),
+            generate_loaded_source_header(&loaded_grammar.sources, self.use_peginator_build_time),
             self.prefix
         );
         if let Ok(f) = File::open(destination) {
@@ -173,12 +209,10 @@ impl Compile {
             }
         };
 
-        let parsed_grammar = Grammar::parse(&grammar)
-            .map_err(|err| PrettyParseError::from_parse_error(&err, &grammar, source.to_str()))?;
         let generated_code = format!(
             "{}\n{}",
             source_header,
-            parsed_grammar.generate_code(&self.settings)?
+            loaded_grammar.grammar.generate_code(&self.settings)?
         );
         fs::write(destination, &generated_code)?;
         if self.format {
@@ -8,21 +8,34 @@ use quote::{format_ident, quote};
 
 use super::{
     common::{generate_rule_parse_function, safe_ident},
+    string::byte_literal,
     CodegenSettings,
 };
 use crate::grammar::{CharRule, CharRulePart};
 
 impl CharRulePart {
-    pub fn generate_parse_call(&self) -> Result<TokenStream> {
+    pub fn generate_parse_call(&self, settings: &CodegenSettings) -> Result<TokenStream> {
         match self {
+            CharRulePart::UnicodeClass(c) => c.generate_parse_call(settings),
             CharRulePart::CharRangePart(c) => {
                 let char_literal: char = c.try_into()?;
-                Ok(quote!(parse_character_literal(state.clone(), #char_literal)))
+                if settings.byte_input {
+                    let byte_literal = byte_literal(char_literal)?;
+                    Ok(quote!(parse_byte_literal(state.clone(), #byte_literal)))
+                } else {
+                    Ok(quote!(parse_character_literal(state.clone(), #char_literal)))
+                }
             }
             CharRulePart::CharacterRange(r) => {
                 let from: char = (&r.from).try_into()?;
                 let to: char = (&r.to).try_into()?;
-                Ok(quote!(parse_character_range(state.clone(), #from, #to)))
+                if settings.byte_input {
+                    let from = byte_literal(from)?;
+                    let to = byte_literal(to)?;
+                    Ok(quote!(parse_byte_range(state.clone(), #from, #to)))
+                } else {
+                    Ok(quote!(parse_character_range(state.clone(), #from, #to)))
+                }
             }
             CharRulePart::Identifier(ident) => {
                 let parser_name = format_ident!("parse_{}", ident);
@@ -40,9 +53,9 @@ impl CharRule {
         let parser_calls = self
             .choices
             .iter()
-            .map(|c| c.generate_parse_call())
+            .map(|c| c.generate_parse_call(settings))
             .collect::<Result<Vec<TokenStream>>>()?;
-        let check_calls = self.generate_check_calls()?;
+        let check_calls = self.generate_check_calls(settings)?;
 
         let parse_body = quote!(
             #check_calls
@@ -51,13 +64,13 @@ impl CharRule {
         );
         Ok(generate_rule_parse_function(
             parser_name,
-            rule_type,
+            quote!(#rule_type),
             parse_body,
             settings,
         ))
     }
 
-    fn generate_check_calls(&self) -> Result<TokenStream> {
+    fn generate_check_calls(&self, settings: &CodegenSettings) -> Result<TokenStream> {
         if self.directives.is_empty() {
             return Ok(TokenStream::new());
         }
@@ -67,8 +80,13 @@ impl CharRule {
             quote!(#(#part_idents)::*)
         });
 
+        let first_unit = if settings.byte_input {
+            quote!(state.s().first().copied())
+        } else {
+            quote!(state.s().chars().next())
+        };
         Ok(quote!(
-            if let Some(c) = state.s().chars().next() {
+            if let Some(c) = #first_unit {
                 #(
                     if !#check_idents(c) {
                         return Err(state.report_error(ParseErrorSpecifics::ExpectedCharacterClass { name: #name }))
@@ -7,8 +7,8 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
 use super::common::{
-    generate_inner_parse_function, safe_ident, Arity, CloneState, Codegen, CodegenSettings,
-    FieldDescriptor,
+    generate_attempt, generate_inner_parse_function, safe_ident, Arity, CloneState, Codegen,
+    CodegenSettings, FieldDescriptor,
 };
 use crate::grammar::{Choice, Grammar};
 
@@ -46,7 +46,11 @@ impl Codegen for Choice {
             .collect::<Result<TokenStream>>()?;
         let parse_body =
             self.generate_parse_body(rule_fields, grammar, settings, CloneState::No)?;
-        let parse_function = generate_inner_parse_function(parse_body, settings);
+        let parse_function = generate_inner_parse_function(
+            parse_body,
+            self.parsed_type_borrows_input(rule_fields, grammar, settings)?,
+            settings,
+        );
         Ok(quote!(
             #choice_bodies
             #parse_function
@@ -140,8 +144,9 @@ impl Choice {
                 };
                 let inner_fields = choice.get_fields(grammar).unwrap();
                 let postprocess = Self::generate_result_converter(&fields, &inner_fields);
+                let parse_call = generate_attempt(quote!(#parse_call #postprocess), grammar);
                 quote!(
-                    .choice(|state| #parse_call #postprocess)
+                    .choice(|state| #parse_call)
                 )
             })
             .collect::<TokenStream>();
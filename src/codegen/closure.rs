@@ -2,15 +2,15 @@
 // This file is part of peginator
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
-use anyhow::Result;
+use anyhow::{anyhow, bail, Result};
 use proc_macro2::{Ident, TokenStream};
 use quote::quote;
 
 use super::common::{
-    generate_field_type, generate_inner_parse_function, safe_ident, Arity, CloneState, Codegen,
-    CodegenSettings, FieldDescriptor,
+    generate_attempt, generate_field_type, generate_inner_parse_function, safe_ident, Arity,
+    CloneState, Codegen, CodegenSettings, FieldDescriptor,
 };
-use crate::grammar::{Closure, Grammar};
+use crate::grammar::{Closure, Grammar, RepetitionUpperBound};
 
 impl Codegen for Closure {
     fn generate_code_spec(
@@ -32,11 +32,13 @@ impl Codegen for Closure {
             parse_call = quote!(closure::parse(state.clone(), global));
         };
 
+        let parse_call = generate_attempt(parse_call, grammar);
+
         let fields = self.body.get_filtered_rule_fields(rule_fields, grammar)?;
         let declarations: TokenStream = fields
             .iter()
             .map(|f| {
-                let typ = generate_field_type("Parsed", f, settings);
+                let typ = generate_field_type("Parsed", f, grammar, settings);
                 let name_ident = safe_ident(f.name);
                 quote!(let mut #name_ident: #typ = Vec::new();)
             })
@@ -60,50 +62,171 @@ impl Codegen for Closure {
         } else {
             quote!(Parsed{ #( #field_names,)* })
         };
-        let at_least_one_check = if self.at_least_one.is_some() {
+        let (separator_body, separator_parse) = match &self.separator {
+            Some(separator) => {
+                let (separator_body, separator_call) = if let Some(inline_body) = separator
+                    .body
+                    .generate_inline_body(rule_fields, grammar, settings, CloneState::Yes)?
+                {
+                    (TokenStream::new(), inline_body)
+                } else {
+                    let inner_body =
+                        separator
+                            .body
+                            .generate_code(rule_fields, grammar, settings)?;
+                    (
+                        quote!(mod separator{
+                            use super::*;
+                            #inner_body
+                        }),
+                        quote!(separator::parse(state.clone(), global)),
+                    )
+                };
+                let separator_call = generate_attempt(separator_call, grammar);
+                (
+                    separator_body,
+                    quote!(
+                        if iterations > 0 {
+                            match #separator_call {
+                                Ok(ParseOk{state:new_state, ..}) => state = new_state,
+                                Err(err) if err.is_committed() => return Err(err.uncommit()),
+                                Err(err) => {
+                                    state = state.record_error(err);
+                                    break;
+                                }
+                            }
+                        }
+                    ),
+                )
+            }
+            None => (TokenStream::new(), TokenStream::new()),
+        };
+        let (before_iteration, failed_iteration_state) = match &self.separator {
+            // The separator before a failed item is not part of the match, unless trailing
+            // separators are allowed.
+            Some(separator) if separator.trailing.is_none() => (
+                quote!(let before_separator = state.clone();),
+                quote!(state.rewind_to(&before_separator)),
+            ),
+            _ => (quote!(), quote!(state)),
+        };
+        let (min, max) = self.repetition_bounds()?;
+        let uses_counter = min > 0 || max.is_some() || self.separator.is_some();
+        let (iteration_counter, iteration_increment) = if uses_counter {
+            (
+                quote!(let mut iterations:usize = 0;),
+                quote!(iterations += 1;),
+            )
+        } else {
+            (quote!(), quote!())
+        };
+        let max_check = match max {
+            Some(max) => quote!(if iterations == #max {
+                break;
+            }),
+            None => quote!(),
+        };
+        // A trailing separator is matched even after the maximum number of repetitions.
+        let (max_check_before_separator, max_check_after_separator) = match &self.separator {
+            Some(separator) if separator.trailing.is_some() => (quote!(), max_check),
+            _ => (max_check, quote!()),
+        };
+        let min_check = if self.at_least_one.is_some() {
             quote!(if iterations == 0 {
                 return Err(state.report_farthest_error());
             })
+        } else if min > 0 {
+            quote!(if iterations < #min {
+                return Err(state.report_error(ParseErrorSpecifics::ExpectedRepetitions {
+                    min: #min,
+                    matched: iterations,
+                }));
+            })
         } else {
             quote!()
         };
         let parse_body = quote!(
-            let mut iterations:usize = 0;
+            #iteration_counter
             let mut state = state;
             #declarations
             loop {
+                #max_check_before_separator
+                #before_iteration
+                #separator_parse
+                #max_check_after_separator
                 match #parse_call {
                     Ok(ParseOk{result: __result, state:new_state, ..}) => {
                         #assignments
                         state = new_state;
                     },
+                    Err(err) if err.is_committed() => return Err(err.uncommit()),
                     Err(err) => {
-                        state = state.record_error(err);
+                        state = #failed_iteration_state.record_error(err);
                         break;
                     }
                 }
-                iterations += 1;
+                #iteration_increment
             }
-            #at_least_one_check
+            #min_check
             Ok(ParseOk{result:#parse_result, state})
 
         );
-        let parse_function = generate_inner_parse_function(parse_body, settings);
+        let parse_function = generate_inner_parse_function(
+            parse_body,
+            self.parsed_type_borrows_input(rule_fields, grammar, settings)?,
+            settings,
+        );
 
         Ok(quote!(
             mod closure{
                 use super::*;
                 #closure_body
             }
+            #separator_body
             #parse_function
         ))
     }
 
     fn get_fields<'a>(&'a self, grammar: &'a Grammar) -> Result<Vec<FieldDescriptor<'a>>> {
+        if let Some(separator) = &self.separator {
+            if !separator.body.get_fields(grammar)?.is_empty() {
+                bail!("Closure separators cannot have fields");
+            }
+        }
         Ok(set_arity_to_multiple(self.body.get_fields(grammar)?))
     }
 }
 
+impl Closure {
+    /// The minimum and maximum (if any) number of repetitions.
+    fn repetition_bounds(&self) -> Result<(usize, Option<usize>)> {
+        let Some(bounds) = &self.bounds else {
+            return Ok((usize::from(self.at_least_one.is_some()), None));
+        };
+        let min = parse_repetition_count(&bounds.min)?;
+        let max = match &bounds.upper {
+            None => Some(min),
+            Some(RepetitionUpperBound { max: None }) => None,
+            Some(RepetitionUpperBound { max: Some(max) }) => Some(parse_repetition_count(max)?),
+        };
+        if let Some(max) = max {
+            if max == 0 {
+                bail!("The maximum repetition count of a closure must be at least 1");
+            }
+            if max < min {
+                bail!("The maximum repetition count of a closure ({max}) is less than the minimum ({min})");
+            }
+        }
+        Ok((min, max))
+    }
+}
+
+fn parse_repetition_count(count: &str) -> Result<usize> {
+    count
+        .parse()
+        .map_err(|_| anyhow!("Invalid repetition count: {count}"))
+}
+
 fn set_arity_to_multiple(fields: Vec<FieldDescriptor>) -> Vec<FieldDescriptor> {
     let mut fields = fields;
     for value in &mut fields {
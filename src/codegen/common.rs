@@ -8,7 +8,7 @@ use anyhow::Result;
 use proc_macro2::{Ident, Span, TokenStream};
 use quote::{format_ident, quote};
 
-use crate::grammar::Grammar;
+use crate::grammar::{Grammar, Grammar_rules};
 
 #[derive(Debug, Clone)]
 pub struct CodegenSettings {
@@ -16,6 +16,13 @@ pub struct CodegenSettings {
     pub peginator_crate_name: String,
     pub derives: Vec<String>,
     pub user_defined_type: TokenStream,
+    /// Generate parsers that work on `&[u8]` instead of `&str`.
+    pub byte_input: bool,
+    /// Make `@string` rules (and `@extern` rules without a return type) borrow from the input,
+    /// instead of allocating. Types containing them get an `'a` lifetime parameter.
+    pub borrowed_strings: bool,
+    /// Generate the code needed for reparsing an edited input, reusing memoized results.
+    pub incremental: bool,
 }
 
 impl Default for CodegenSettings {
@@ -25,6 +32,9 @@ impl Default for CodegenSettings {
             peginator_crate_name: "peginator".into(),
             derives: vec!["Debug".into(), "Clone".into()],
             user_defined_type: quote!(()),
+            byte_input: false,
+            borrowed_strings: false,
+            incremental: false,
         }
     }
 }
@@ -34,6 +44,46 @@ impl CodegenSettings {
         let idents = t.split("::").map(safe_ident);
         self.user_defined_type = quote!(#(#idents)::*);
     }
+
+    /// The type of the parsed input: `str` or `[u8]`.
+    pub fn input_type(&self) -> TokenStream {
+        if self.byte_input {
+            quote!([u8])
+        } else {
+            quote!(str)
+        }
+    }
+
+    /// The input type parameter of the runtime types, including the leading comma.
+    ///
+    /// Empty for `str`, the default of the runtime types.
+    pub fn input_type_param(&self) -> TokenStream {
+        if self.byte_input {
+            quote!(, [u8])
+        } else {
+            quote!()
+        }
+    }
+
+    /// The type of `@string` rules: `String` or `Vec<u8>`, or a reference to the input with
+    /// `borrowed_strings`.
+    pub fn string_type(&self) -> TokenStream {
+        match (self.byte_input, self.borrowed_strings) {
+            (false, false) => quote!(String),
+            (true, false) => quote!(Vec<u8>),
+            (false, true) => quote!(&'a str),
+            (true, true) => quote!(&'a [u8]),
+        }
+    }
+
+    /// The type of the single units of the input, used by `@char` rules: `char` or `u8`.
+    pub fn char_type(&self) -> TokenStream {
+        if self.byte_input {
+            quote!(u8)
+        } else {
+            quote!(char)
+        }
+    }
 }
 
 pub trait CodegenGrammar {
@@ -101,11 +151,15 @@ pub trait Codegen {
         grammar: &Grammar,
         settings: &CodegenSettings,
     ) -> Result<TokenStream> {
-        let _ = grammar;
         if let Some(parse_body) =
             self.generate_inline_body(rule_fields, grammar, settings, CloneState::No)?
         {
-            Ok(generate_inner_parse_function(parse_body, settings))
+            let borrows_input = self.parsed_type_borrows_input(rule_fields, grammar, settings)?;
+            Ok(generate_inner_parse_function(
+                parse_body,
+                borrows_input,
+                settings,
+            ))
         } else {
             panic!(
                 "Neither generate_code_spec, nor generate_inline_body was implemented for {}",
@@ -166,12 +220,25 @@ pub trait Codegen {
         Ok(generate_parsed_struct_type(
             type_name,
             &fields,
+            grammar,
             settings,
             record_position,
             public_type,
         ))
     }
 
+    /// Whether the `Parsed` type of this parser borrows from the input, see
+    /// [`type_borrows_input`].
+    fn parsed_type_borrows_input(
+        &self,
+        rule_fields: &[FieldDescriptor],
+        grammar: &Grammar,
+        settings: &CodegenSettings,
+    ) -> Result<bool> {
+        let fields = self.get_filtered_rule_fields(rule_fields, grammar)?;
+        Ok(fields_borrow_input(&fields, grammar, settings))
+    }
+
     fn get_filtered_rule_fields<'a>(
         &self,
         rule_fields: &[FieldDescriptor<'a>],
@@ -186,6 +253,211 @@ pub trait Codegen {
     }
 }
 
+/// Whether the type generated for `type_name` borrows from the input, i.e. whether it has an `'a`
+/// lifetime parameter. Always false without `borrowed_strings`.
+pub fn type_borrows_input(type_name: &str, grammar: &Grammar, settings: &CodegenSettings) -> bool {
+    settings.borrowed_strings && type_borrows_input_impl(type_name, grammar, &mut BTreeSet::new())
+}
+
+fn type_borrows_input_impl<'a>(
+    type_name: &'a str,
+    grammar: &'a Grammar,
+    visited: &mut BTreeSet<&'a str>,
+) -> bool {
+    if !visited.insert(type_name) {
+        // Recursion does not make a type borrow by itself.
+        return false;
+    }
+    for rule_entry in &grammar.rules {
+        match rule_entry {
+            Grammar_rules::Rule(rule) if rule.name == type_name => {
+                if rule.flags().string {
+                    return true;
+                }
+                let Ok(fields) = rule.definition.get_fields(grammar) else {
+                    return false;
+                };
+                return fields.iter().any(|field| {
+                    field
+                        .type_names
+                        .iter()
+                        .any(|type_name| type_borrows_input_impl(type_name, grammar, visited))
+                });
+            }
+            Grammar_rules::ExternRule(extern_rule) if extern_rule.name == type_name => {
+                return extern_rule.directive.return_type.is_none();
+            }
+            Grammar_rules::PrecedenceRule(rule) if rule.name == type_name => {
+                return type_borrows_input_impl(&rule.directive.operand, grammar, visited);
+            }
+            _ => (),
+        }
+    }
+    false
+}
+
+/// Whether the type generated for `type_name` contains input positions, i.e. whether it has to
+/// implement `ShiftPositions` with `incremental`.
+///
+/// Builtin, `@char` and `@extern` types never do, `@string` rules only with `@position`.
+pub fn type_has_positions(type_name: &str, grammar: &Grammar) -> bool {
+    type_has_positions_impl(type_name, grammar, &mut BTreeSet::new())
+}
+
+fn type_has_positions_impl<'a>(
+    type_name: &'a str,
+    grammar: &'a Grammar,
+    visited: &mut BTreeSet<&'a str>,
+) -> bool {
+    if !visited.insert(type_name) {
+        return false;
+    }
+    for rule_entry in &grammar.rules {
+        if let Grammar_rules::PrecedenceRule(rule) = rule_entry {
+            if rule.name == type_name {
+                // The operator nodes only contain positions through the operands.
+                return type_has_positions_impl(&rule.directive.operand, grammar, visited);
+            }
+        }
+        if let Grammar_rules::Rule(rule) = rule_entry {
+            if rule.name != type_name {
+                continue;
+            }
+            let flags = rule.flags();
+            if flags.string {
+                return flags.position;
+            }
+            let Ok(fields) = rule.definition.get_fields(grammar) else {
+                return true;
+            };
+            // Simply overridden rules are type aliases of the overriding type.
+            return match fields.as_slice() {
+                [field] if field.name == "_override" && field.type_names.len() == 1 => {
+                    let type_name = field.type_names.iter().next().unwrap();
+                    type_has_positions_impl(type_name, grammar, visited)
+                }
+                _ => true,
+            };
+        }
+    }
+    false
+}
+
+/// Whether the type of the field contains input positions, see [`type_has_positions`].
+fn field_has_positions(field: &FieldDescriptor, grammar: &Grammar) -> bool {
+    // Fields with multiple types are generated enums.
+    field.type_names.len() > 1
+        || field
+            .type_names
+            .iter()
+            .any(|type_name| type_has_positions(type_name, grammar))
+}
+
+/// Implement `ShiftPositions` for a generated type, if `incremental` is enabled.
+///
+/// `body` shifts the positions of `self` by `offset`.
+pub fn generate_shift_positions_impl(
+    type_ident: &Ident,
+    body: TokenStream,
+    settings: &CodegenSettings,
+) -> TokenStream {
+    if !settings.incremental {
+        return quote!();
+    }
+    let peginator_crate = safe_ident(&settings.peginator_crate_name);
+    if body.is_empty() {
+        return quote!(
+            impl #peginator_crate::runtime::ShiftPositions for #type_ident {
+                fn shift_positions(&mut self, _offset: isize) {}
+            }
+        );
+    }
+    quote!(
+        impl #peginator_crate::runtime::ShiftPositions for #type_ident {
+            fn shift_positions(&mut self, offset: isize) {
+                #body
+            }
+        }
+    )
+}
+
+/// Whether any of the fields borrow from the input, see [`type_borrows_input`].
+pub fn fields_borrow_input(
+    fields: &[FieldDescriptor],
+    grammar: &Grammar,
+    settings: &CodegenSettings,
+) -> bool {
+    fields.iter().any(|field| {
+        field
+            .type_names
+            .iter()
+            .any(|type_name| type_borrows_input(type_name, grammar, settings))
+    })
+}
+
+/// The generic parameters of a type that may borrow from the input: `<'a>` or nothing.
+pub fn generate_lifetime_params(borrows_input: bool) -> TokenStream {
+    if borrows_input {
+        quote!(<'a>)
+    } else {
+        quote!()
+    }
+}
+
+/// The type generated for a rule, with its lifetime parameter, if it has one.
+pub fn generate_rule_type(
+    type_name: &str,
+    grammar: &Grammar,
+    settings: &CodegenSettings,
+) -> TokenStream {
+    let ident = safe_ident(type_name);
+    let lifetime_params =
+        generate_lifetime_params(type_borrows_input(type_name, grammar, settings));
+    quote!(#ident #lifetime_params)
+}
+
+/// Whether `type_name` is a rule with a `@recover` directive.
+pub fn rule_recovers(type_name: &str, grammar: &Grammar) -> bool {
+    grammar.rules.iter().any(|rule_entry| {
+        matches!(rule_entry, Grammar_rules::Rule(rule) if rule.name == type_name && rule.flags().recover)
+    })
+}
+
+/// Whether the grammar has any `@recover` rules. Only these parsers record recovered errors,
+/// which have to be forgotten when backtracking.
+pub fn grammar_recovers(grammar: &Grammar) -> bool {
+    grammar
+        .rules
+        .iter()
+        .any(|rule_entry| matches!(rule_entry, Grammar_rules::Rule(rule) if rule.flags().recover))
+}
+
+/// The type returned by the parser of a rule: the rule type, or for `@recover` rules, a `Result`
+/// with the error that was recovered from in place of the skipped input.
+pub fn generate_parsed_type(
+    type_name: &str,
+    grammar: &Grammar,
+    settings: &CodegenSettings,
+) -> TokenStream {
+    let rule_type = generate_rule_type(type_name, grammar, settings);
+    if rule_recovers(type_name, grammar) {
+        let peginator_crate = safe_ident(&settings.peginator_crate_name);
+        quote!(Result<#rule_type, #peginator_crate::runtime::ParseError>)
+    } else {
+        rule_type
+    }
+}
+
+/// Wrap a parser call that is backtracked from if it fails, so that the errors recovered from
+/// during it are forgotten, see `ParseGlobal::attempt`.
+pub fn generate_attempt(parse_call: TokenStream, grammar: &Grammar) -> TokenStream {
+    if grammar_recovers(grammar) {
+        quote!(global.attempt(|global| #parse_call))
+    } else {
+        parse_call
+    }
+}
+
 pub fn generate_skip_ws(
     settings: &CodegenSettings,
     parse_fn_name: &str,
@@ -222,11 +494,13 @@ pub fn generate_derives(settings: &CodegenSettings) -> TokenStream {
 fn generate_parsed_struct_type(
     type_name: &str,
     fields: &[FieldDescriptor],
+    grammar: &Grammar,
     settings: &CodegenSettings,
     record_position: RecordPosition,
     public_type: PublicType,
 ) -> TokenStream {
     let type_ident = safe_ident(type_name);
+    let lifetime_params = generate_lifetime_params(fields_borrow_input(fields, grammar, settings));
     let derives = if public_type == PublicType::Yes {
         generate_derives(settings)
     } else {
@@ -236,34 +510,61 @@ fn generate_parsed_struct_type(
     if fields.is_empty() && record_position == RecordPosition::No {
         match public_type {
             PublicType::No => quote!(pub type #type_ident = ();),
-            PublicType::Yes => quote!(
-                #derives
-                pub struct #type_ident;
-            ),
+            PublicType::Yes => {
+                let shift_positions_impl =
+                    generate_shift_positions_impl(&type_ident, quote!(), settings);
+                quote!(
+                    #derives
+                    pub struct #type_ident;
+                    #shift_positions_impl
+                )
+            }
         }
     } else if fields.len() == 1
         && record_position == RecordPosition::No
         && public_type == PublicType::No
     {
-        let field_type = generate_field_type(type_name, &fields[0], settings);
-        quote!(pub type #type_ident = #field_type;)
+        let field_type = generate_field_type(type_name, &fields[0], grammar, settings);
+        quote!(pub type #type_ident #lifetime_params = #field_type;)
     } else {
         let field_names: Vec<Ident> = fields.iter().map(|f| safe_ident(f.name)).collect();
         let field_types: Vec<TokenStream> = fields
             .iter()
-            .map(|f| generate_field_type(type_name, f, settings))
+            .map(|f| generate_field_type(type_name, f, grammar, settings))
             .collect();
         let position_field = if record_position == RecordPosition::Yes {
             quote!(pub position: std::ops::Range<usize>,)
         } else {
             quote!()
         };
+        let shift_positions_impl = if public_type == PublicType::Yes {
+            let shifted_fields = fields
+                .iter()
+                .filter(|f| field_has_positions(f, grammar))
+                .map(|f| safe_ident(f.name));
+            let shift_position = if record_position == RecordPosition::Yes {
+                quote!(self.position.shift_positions(offset);)
+            } else {
+                quote!()
+            };
+            generate_shift_positions_impl(
+                &type_ident,
+                quote!(
+                    #( self.#shifted_fields.shift_positions(offset); )*
+                    #shift_position
+                ),
+                settings,
+            )
+        } else {
+            quote!()
+        };
         quote!(
             #derives
-            pub struct #type_ident {
+            pub struct #type_ident #lifetime_params {
                 #( pub #field_names: #field_types, )*
                 #position_field
             }
+            #shift_positions_impl
         )
     }
 }
@@ -271,20 +572,21 @@ fn generate_parsed_struct_type(
 pub fn generate_field_type(
     parent_type: &str,
     field: &FieldDescriptor,
-    _settings: &CodegenSettings,
+    grammar: &Grammar,
+    settings: &CodegenSettings,
 ) -> TokenStream {
     let field_inner_type_ident: TokenStream = if field.type_names.len() > 1 {
         let field_name = &field.name;
         let ident = format_ident!("{parent_type}_{field_name}");
-        quote!(#ident)
+        let lifetime_params = generate_lifetime_params(fields_borrow_input(
+            std::slice::from_ref(field),
+            grammar,
+            settings,
+        ));
+        quote!(#ident #lifetime_params)
     } else {
         let type_name = field.type_names.iter().next().unwrap();
-        let ident = safe_ident(type_name);
-        if type_name == &"char" {
-            quote!(char)
-        } else {
-            quote!(#ident)
-        }
+        generate_variant_type(type_name, grammar, settings)
     };
     match field.arity {
         Arity::One => {
@@ -305,34 +607,81 @@ pub fn generate_field_type(
     }
 }
 
+/// The type of a single field type name, including the builtin types.
+fn generate_variant_type(
+    type_name: &str,
+    grammar: &Grammar,
+    settings: &CodegenSettings,
+) -> TokenStream {
+    if type_name == "char" {
+        quote!(char)
+    } else if settings.byte_input && type_name == "byte" {
+        quote!(u8)
+    } else {
+        generate_parsed_type(type_name, grammar, settings)
+    }
+}
+
 pub fn generate_enum_type(
     name: &str,
     field: &FieldDescriptor,
+    grammar: &Grammar,
     settings: &CodegenSettings,
 ) -> TokenStream {
     let ident = safe_ident(name);
+    let lifetime_params = generate_lifetime_params(fields_borrow_input(
+        std::slice::from_ref(field),
+        grammar,
+        settings,
+    ));
     let derives = generate_derives(settings);
     let type_idents: Vec<Ident> = field.type_names.iter().map(safe_ident).collect();
+    let inner_types = field
+        .type_names
+        .iter()
+        .map(|type_name| generate_variant_type(type_name, grammar, settings));
+    let (shifted_variants, other_variants): (Vec<&str>, Vec<&str>) = field
+        .type_names
+        .iter()
+        .partition(|type_name| type_has_positions(type_name, grammar));
+    let shift_positions_body = if shifted_variants.is_empty() {
+        quote!()
+    } else {
+        let shifted_variants = shifted_variants.iter().map(safe_ident);
+        let other_variants = other_variants.iter().map(safe_ident);
+        quote!(
+            match self {
+                #(Self::#shifted_variants(x) => x.shift_positions(offset),)*
+                #(Self::#other_variants(_) => (),)*
+            }
+        )
+    };
+    let shift_positions_impl =
+        generate_shift_positions_impl(&ident, shift_positions_body, settings);
     quote!(
         #[allow(non_camel_case_types)]
         #derives
-        pub enum #ident {
-            #(#type_idents(#type_idents),)*
+        pub enum #ident #lifetime_params {
+            #(#type_idents(#inner_types),)*
         }
+        #shift_positions_impl
     )
 }
 
 pub fn generate_inner_parse_function(
     parse_body: TokenStream,
+    parsed_type_borrows_input: bool,
     settings: &CodegenSettings,
 ) -> TokenStream {
     let user_defined_type = &settings.user_defined_type;
+    let input_type_param = settings.input_type_param();
+    let parsed_lifetime_params = generate_lifetime_params(parsed_type_borrows_input);
     quote!(
         #[inline(always)]
         pub fn parse<'a, TT: ParseTracer>(
-            state: ParseState<'a>,
+            state: ParseState<'a #input_type_param>,
             global: &mut ParseGlobal<TT, ParseCache<'a>, #user_defined_type>,
-        ) -> ParseResult<'a, Parsed> {
+        ) -> ParseResult<'a, Parsed #parsed_lifetime_params #input_type_param> {
             #parse_body
         }
     )
@@ -340,17 +689,18 @@ pub fn generate_inner_parse_function(
 
 pub fn generate_rule_parse_function(
     parser_name: Ident,
-    rule_type: Ident,
+    rule_type: TokenStream,
     parse_body: TokenStream,
     settings: &CodegenSettings,
 ) -> TokenStream {
     let user_defined_type = &settings.user_defined_type;
+    let input_type_param = settings.input_type_param();
     quote!(
         #[inline]
         pub(super) fn #parser_name <'a, TT: ParseTracer>(
-            state: ParseState<'a>,
+            state: ParseState<'a #input_type_param>,
             global: &mut ParseGlobal<TT, ParseCache<'a>, #user_defined_type>,
-        ) -> ParseResult<'a, #rule_type> {
+        ) -> ParseResult<'a, #rule_type #input_type_param> {
             #parse_body
         }
     )
@@ -0,0 +1,32 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use anyhow::Result;
+use proc_macro2::TokenStream;
+use quote::quote;
+
+use super::common::{CloneState, Codegen, CodegenSettings, FieldDescriptor};
+use crate::grammar::{Cut, Grammar};
+
+/// The cut itself always matches without consuming anything. The actual work is done by
+/// [`super::sequence`], marking the errors of the parts after the cut as committed.
+impl Codegen for Cut {
+    fn generate_inline_body(
+        &self,
+        _rule_fields: &[FieldDescriptor],
+        _grammar: &Grammar,
+        _settings: &CodegenSettings,
+        clone_state: CloneState,
+    ) -> Result<Option<TokenStream>> {
+        let state = match clone_state {
+            CloneState::No => quote!(state),
+            CloneState::Yes => quote!(state: state.clone()),
+        };
+        Ok(Some(quote!(Ok(ParseOk { result: (), #state }))))
+    }
+
+    fn get_fields(&self, _grammar: &Grammar) -> Result<Vec<FieldDescriptor<'_>>> {
+        Ok(Vec::new())
+    }
+}
@@ -24,7 +24,7 @@ impl Codegen for EndOfInput {
         )))
     }
 
-    fn get_fields(&self, _grammar: &Grammar) -> Result<Vec<FieldDescriptor>> {
+    fn get_fields(&self, _grammar: &Grammar) -> Result<Vec<FieldDescriptor<'_>>> {
         Ok(Vec::new())
     }
 }
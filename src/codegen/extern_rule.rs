@@ -7,18 +7,24 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
 use super::{
-    common::{generate_rule_parse_function, safe_ident},
+    common::{
+        generate_lifetime_params, generate_rule_parse_function, safe_ident, type_borrows_input,
+    },
     CodegenSettings,
 };
-use crate::grammar::{ExternDirective_function, ExternRule};
+use crate::grammar::{ExternDirective_function, ExternRule, Grammar};
 
 impl ExternRule {
-    pub fn generate_code(&self, settings: &CodegenSettings) -> Result<(TokenStream, TokenStream)> {
+    pub fn generate_code(
+        &self,
+        grammar: &Grammar,
+        settings: &CodegenSettings,
+    ) -> Result<(TokenStream, TokenStream)> {
         let return_type = if let Some(return_type) = &self.directive.return_type {
             let part_idents = return_type.iter().map(safe_ident);
             quote!(#(#part_idents)::*)
         } else {
-            quote!(String)
+            settings.string_type()
         };
         let function_ident = match &self.directive.function {
             ExternDirective_function::NamespacedRustName(name) => {
@@ -32,10 +38,17 @@ impl ExternRule {
         };
 
         let rule_type = safe_ident(&self.name);
+        let lifetime_params =
+            generate_lifetime_params(type_borrows_input(&self.name, grammar, settings));
         let parser_name = format_ident!("parse_{}", self.name);
 
+        // Extern functions that don't report how much of the input they looked at are assumed
+        // to look at all of it, including its end.
         let parse_body = quote!(
-            match #function_ident(state.s()) {
+            let result = #function_ident(state.s());
+            let examined = result.examined_length().unwrap_or_else(|| state.s().len() + 1);
+            let state = state.examine(examined);
+            match result.into_extern_result() {
                 Ok((result, advance)) => {
                     Ok(ParseOk {
                         result: result.into(),
@@ -43,15 +56,18 @@ impl ExternRule {
                     })
                 },
                 Err(error_string) => {
-                    Err(state.report_error(ParseErrorSpecifics::ExternRuleFailed {
-                        error_string,
-                    }))
+                    Err(state.report_error(ParseErrorSpecifics::ExternRuleFailed { error_string }))
                 }
             }
         );
         Ok((
-            quote!(pub type #rule_type = #return_type;),
-            generate_rule_parse_function(parser_name, rule_type, parse_body, settings),
+            quote!(pub type #rule_type #lifetime_params = #return_type;),
+            generate_rule_parse_function(
+                parser_name,
+                quote!(#rule_type #lifetime_params),
+                parse_body,
+                settings,
+            ),
         ))
     }
 }
@@ -2,7 +2,7 @@
 // This file is part of peginator
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
-use anyhow::Result;
+use anyhow::{bail, Result};
 use proc_macro2::TokenStream;
 use quote::{format_ident, quote};
 
@@ -19,6 +19,7 @@ impl Codegen for Field {
         settings: &CodegenSettings,
         clone_state: CloneState,
     ) -> Result<Option<TokenStream>> {
+        check_builtin_type(&self.typ, settings)?;
         let postprocess = if let Some(field_name) = &self.name {
             generate_postprocess_calls(field_name, &self.typ, rule_fields)
         } else {
@@ -33,7 +34,7 @@ impl Codegen for Field {
         Ok(Some(quote!(#parser_call #postprocess)))
     }
 
-    fn get_fields(&self, _grammar: &Grammar) -> Result<Vec<FieldDescriptor>> {
+    fn get_fields(&self, _grammar: &Grammar) -> Result<Vec<FieldDescriptor<'_>>> {
         if let Some(field_name) = &self.name {
             Ok(vec![FieldDescriptor {
                 name: field_name,
@@ -55,6 +56,7 @@ impl Codegen for OverrideField {
         settings: &CodegenSettings,
         clone_state: CloneState,
     ) -> Result<Option<TokenStream>> {
+        check_builtin_type(&self.typ, settings)?;
         let postprocess = generate_postprocess_calls("_override", &self.typ, rule_fields);
         let parser_call = generate_skip_ws(
             settings,
@@ -65,7 +67,7 @@ impl Codegen for OverrideField {
         Ok(Some(quote!(#parser_call #postprocess)))
     }
 
-    fn get_fields(&self, _grammar: &Grammar) -> Result<Vec<FieldDescriptor>> {
+    fn get_fields(&self, _grammar: &Grammar) -> Result<Vec<FieldDescriptor<'_>>> {
         Ok(vec![FieldDescriptor {
             name: "_override",
             type_names: [&self.typ as &str].into(),
@@ -75,6 +77,13 @@ impl Codegen for OverrideField {
     }
 }
 
+fn check_builtin_type(type_name: &str, settings: &CodegenSettings) -> Result<()> {
+    if settings.byte_input && type_name == "char" {
+        bail!("The 'char' builtin cannot be used with byte input. Use 'byte' instead.");
+    }
+    Ok(())
+}
+
 fn generate_postprocess_calls(
     field_name: &str,
     field_type_name: &str,
@@ -2,71 +2,108 @@
 // This file is part of peginator
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
-use anyhow::Result;
-use proc_macro2::TokenStream;
+use anyhow::{bail, Result};
+use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 
-use super::common::{safe_ident, CodegenGrammar, CodegenRule, CodegenSettings};
-use crate::grammar::{Grammar, Grammar_rules};
+use super::common::{
+    generate_rule_type, safe_ident, type_has_positions, CodegenGrammar, CodegenRule,
+    CodegenSettings,
+};
+use super::parameterized::instantiate_parameterized_rules;
+use crate::grammar::{Grammar, GrammarDirective, Grammar_rules};
 
 impl CodegenGrammar for Grammar {
     fn generate_code(&self, settings: &CodegenSettings) -> Result<TokenStream> {
+        if settings.incremental && settings.borrowed_strings {
+            bail!("Incremental parsing cannot be used together with borrowed strings");
+        }
+        if self.directives.iter().any(|d| {
+            matches!(
+                d,
+                GrammarDirective::ImportDirective(_) | GrammarDirective::ExtendsDirective(_)
+            )
+        }) {
+            bail!(
+                "@import and @extends can only be used in grammar files loaded with LoadedGrammar"
+            );
+        }
+        let grammar = &instantiate_parameterized_rules(self)?;
         let mut all_types = TokenStream::new();
         let mut all_parsers = TokenStream::new();
         let mut all_impls = TokenStream::new();
         let mut cache_entries = TokenStream::new();
+        let mut cached_rules = Vec::new();
         let peginator_crate = safe_ident(&settings.peginator_crate_name);
-        for rule_entry in &self.rules {
+        for rule_entry in &grammar.rules {
             match rule_entry {
                 Grammar_rules::Rule(rule) => {
                     let flags = rule.flags();
-                    let (types, impls) = rule.generate_code(self, settings)?;
+                    let (types, impls) = rule.generate_code(grammar, settings)?;
                     all_types.extend(types);
                     all_impls.extend(impls);
-                    let rule_ident = safe_ident(&rule.name);
+                    let rule_type = generate_rule_type(&rule.name, grammar, settings);
                     let internal_parser_name = format_ident!("parse_{}", rule.name);
-                    let user_defined_type = &settings.user_defined_type;
                     if flags.export {
-                        all_parsers.extend(quote!(
-                            impl peginator_generated::PegParserAdvanced<#user_defined_type> for #rule_ident {
-                                fn parse_advanced<TT: peginator_generated::ParseTracer>(
-                                    s: &str,
-                                    settings: &peginator_generated::ParseSettings,
-                                    user_defined: #user_defined_type,
-                                ) -> Result<Self, peginator_generated::ParseError> {
-                                    Ok(peginator_generated::#internal_parser_name(
-                                        peginator_generated::ParseState::new(s, settings),
-                                        &mut peginator_generated
-                                            ::ParseGlobal
-                                            ::<TT, peginator_generated::ParseCache, #user_defined_type>
-                                            ::new(
-                                                Default::default(),
-                                                user_defined,
-                                        ),
-                                    )?.result)
-                                }
-                            }
-                        ))
+                        all_parsers.extend(generate_exported_parser(
+                            &rule_type,
+                            &internal_parser_name,
+                            settings,
+                        ));
                     }
 
                     if flags.memoize || flags.left_recursive {
+                        let input_type_param = settings.input_type_param();
                         let cache_entry_ident = format_ident!("c_{}", rule.name);
-                        cache_entries
-                            .extend(quote!(pub #cache_entry_ident: CacheEntries<'a, #rule_ident>,));
+                        let cache_entries_type = if settings.incremental {
+                            quote!(IncrementalCacheEntries)
+                        } else {
+                            quote!(CacheEntries)
+                        };
+                        cache_entries.extend(quote!(
+                            pub #cache_entry_ident: #cache_entries_type<'a, #rule_type #input_type_param>,
+                        ));
+                        cached_rules.push((
+                            cache_entry_ident,
+                            rule_type,
+                            type_has_positions(&rule.name, grammar),
+                        ));
                     }
                 }
                 Grammar_rules::CharRule(char_rule) => {
                     let rule_ident = safe_ident(&char_rule.name);
-                    all_types.extend(quote!(pub type #rule_ident = char;));
+                    let char_type = settings.char_type();
+                    all_types.extend(quote!(pub type #rule_ident = #char_type;));
                     all_impls.extend(char_rule.generate_code(settings));
                 }
                 Grammar_rules::ExternRule(extern_rule) => {
-                    let (types, impls) = extern_rule.generate_code(settings)?;
+                    let (types, impls) = extern_rule.generate_code(grammar, settings)?;
                     all_types.extend(types);
                     all_impls.extend(impls);
                 }
+                Grammar_rules::PrecedenceRule(precedence_rule) => {
+                    let (types, impls) = precedence_rule.generate_code(grammar, settings)?;
+                    all_types.extend(types);
+                    all_impls.extend(impls);
+                    if precedence_rule.is_exported() {
+                        let rule_type =
+                            generate_rule_type(&precedence_rule.name, grammar, settings);
+                        let internal_parser_name = format_ident!("parse_{}", precedence_rule.name);
+                        all_parsers.extend(generate_exported_parser(
+                            &rule_type,
+                            &internal_parser_name,
+                            settings,
+                        ));
+                    }
+                }
             }
         }
+        let skip_item_whitespace = generate_skip_item_whitespace(settings);
+        let detached_cache = if settings.incremental {
+            generate_detached_cache(&cached_rules, settings)
+        } else {
+            quote!()
+        };
         Ok(quote!(
             #all_types
             #all_parsers
@@ -76,12 +113,14 @@ impl CodegenGrammar for Grammar {
                 unused_imports,
                 unused_mut,
                 dead_code,
+                clippy::module_inception,
+                clippy::useless_conversion,
             )]
             mod peginator_generated {
                 use super::*;
                 pub use #peginator_crate::runtime::{
-                    ParseError, ParseSettings, ParseState, PegParser, IndentedTracer, ParseTracer,
-                    PegPosition, ParseGlobal, PegParserAdvanced,
+                    ParseError, ParseSettings, ParseState, PegParser, PegByteParser, PegBorrowedParser,
+                    IndentedTracer, ParseTracer, PegPosition, ParseGlobal, PegParserAdvanced, ParsedItem,
                 };
                 use #peginator_crate::runtime::*;
 
@@ -90,8 +129,204 @@ impl CodegenGrammar for Grammar {
                     #cache_entries
                     _please_dont_complain: std::marker::PhantomData<&'a ()>,
                 }
+                #detached_cache
+                #skip_item_whitespace
                 #all_impls
             }
         ))
     }
 }
+
+/// The `PegParser` implementations of an exported rule.
+fn generate_exported_parser(
+    rule_type: &TokenStream,
+    internal_parser_name: &Ident,
+    settings: &CodegenSettings,
+) -> TokenStream {
+    let user_defined_type = &settings.user_defined_type;
+    let input_type = settings.input_type();
+    let input_type_param = settings.input_type_param();
+    let mut result = quote!(
+        impl<'a> peginator_generated::PegParserAdvanced<'a, #user_defined_type #input_type_param> for #rule_type {
+            fn parse_advanced_at<TT: peginator_generated::ParseTracer>(
+                s: &'a #input_type,
+                offset: usize,
+                settings: &peginator_generated::ParseSettings,
+                user_defined: #user_defined_type,
+            ) -> Result<(Self, usize), peginator_generated::ParseError> {
+                let result = peginator_generated::#internal_parser_name(
+                    peginator_generated::ParseState::new_at(s, offset, settings),
+                    &mut peginator_generated
+                        ::ParseGlobal
+                        ::<TT, peginator_generated::ParseCache, #user_defined_type>
+                        ::new(
+                            Default::default(),
+                            user_defined,
+                            settings,
+                    ),
+                ).map_err(|err| err.with_found(s))?;
+                Ok((result.result, result.state.position()))
+            }
+            fn parse_advanced_item<TT: peginator_generated::ParseTracer>(
+                s: &'a #input_type,
+                base: usize,
+                settings: &peginator_generated::ParseSettings,
+                user_defined: #user_defined_type,
+            ) -> Option<Result<peginator_generated::ParsedItem<Self>, peginator_generated::ParseError>> {
+                let mut global = peginator_generated
+                    ::ParseGlobal
+                    ::<TT, peginator_generated::ParseCache, #user_defined_type>
+                    ::new(
+                        Default::default(),
+                        user_defined,
+                        settings,
+                );
+                let state = peginator_generated::skip_item_whitespace(
+                    peginator_generated::ParseState::new_with_base(s, base, settings),
+                    &mut global,
+                );
+                if state.is_empty() {
+                    return None;
+                }
+                let start = state.position();
+                Some(
+                    peginator_generated::#internal_parser_name(state, &mut global)
+                        .map(|result| peginator_generated::ParsedItem {
+                            result: result.result,
+                            range: start..result.state.position(),
+                            examined_until: result.state.examined_until(),
+                        })
+                        .map_err(|err| err.with_found_in(s, base))
+                )
+            }
+            fn parse_advanced_with_recovery<TT: peginator_generated::ParseTracer>(
+                s: &'a #input_type,
+                settings: &peginator_generated::ParseSettings,
+                user_defined: #user_defined_type,
+            ) -> (Option<Self>, Vec<peginator_generated::ParseError>) {
+                let mut global = peginator_generated
+                    ::ParseGlobal
+                    ::<TT, peginator_generated::ParseCache, #user_defined_type>
+                    ::new(
+                        Default::default(),
+                        user_defined,
+                        settings,
+                );
+                global.recover = true;
+                let result = peginator_generated::#internal_parser_name(
+                    peginator_generated::ParseState::new(s, settings),
+                    &mut global,
+                );
+                global.into_recovery_result(result, s)
+            }
+        }
+    );
+    if settings.incremental {
+        result.extend(generate_incremental_parser(
+            rule_type,
+            internal_parser_name,
+            settings,
+        ));
+    }
+    result
+}
+
+/// Skip the whitespace before the items of `parse_iter`, if whitespace skipping is enabled.
+fn generate_skip_item_whitespace(settings: &CodegenSettings) -> TokenStream {
+    let user_defined_type = &settings.user_defined_type;
+    let input_type_param = settings.input_type_param();
+    let body = if settings.skip_whitespace {
+        quote!(match parse_Whitespace(state.clone(), global) {
+            Ok(ParseOk { state, .. }) => state,
+            Err(_) => state,
+        })
+    } else {
+        quote!(state)
+    };
+    quote!(
+        pub fn skip_item_whitespace<'a, TT: ParseTracer>(
+            state: ParseState<'a #input_type_param>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, #user_defined_type>,
+        ) -> ParseState<'a #input_type_param> {
+            #body
+        }
+    )
+}
+
+/// The cache of an incremental parse, with conversions from and to `ParseCache`.
+fn generate_detached_cache(
+    cached_rules: &[(Ident, TokenStream, bool)],
+    settings: &CodegenSettings,
+) -> TokenStream {
+    let input_type = settings.input_type();
+    let entry_idents: Vec<&Ident> = cached_rules.iter().map(|(ident, _, _)| ident).collect();
+    let rule_types = cached_rules.iter().map(|(_, rule_type, _)| rule_type);
+    let shift_fns = cached_rules.iter().map(|(_, _, has_positions)| {
+        if *has_positions {
+            quote!(|result, offset| result.shift_positions(offset))
+        } else {
+            quote!(|_, _| ())
+        }
+    });
+    quote!(
+        #[derive(Default)]
+        pub struct DetachedParseCache {
+            #(pub #entry_idents: DetachedCacheEntries<#rule_types>,)*
+        }
+
+        impl<'a> ParseCache<'a> {
+            pub fn detach(self) -> DetachedParseCache {
+                DetachedParseCache {
+                    #(#entry_idents: self.#entry_idents.detach(),)*
+                }
+            }
+        }
+
+        impl DetachedParseCache {
+            pub fn attach<'a>(self, s: &'a #input_type, edit: &TextEdit) -> ParseCache<'a> {
+                ParseCache {
+                    #(#entry_idents: IncrementalCacheEntries::attach(self.#entry_idents, s, edit, #shift_fns),)*
+                    _please_dont_complain: std::marker::PhantomData,
+                }
+            }
+        }
+    )
+}
+
+fn generate_incremental_parser(
+    rule_type: &TokenStream,
+    internal_parser_name: &Ident,
+    settings: &CodegenSettings,
+) -> TokenStream {
+    let peginator_crate = safe_ident(&settings.peginator_crate_name);
+    let user_defined_type = &settings.user_defined_type;
+    let input_type = settings.input_type();
+    let input_type_param = settings.input_type_param();
+    quote!(
+        impl #peginator_crate::runtime::PegIncrementalParserAdvanced<#user_defined_type #input_type_param> for #rule_type {
+            type Cache = peginator_generated::DetachedParseCache;
+            fn parse_advanced_with_cache<TT: #peginator_crate::runtime::ParseTracer>(
+                s: &#input_type,
+                cache: Self::Cache,
+                edit: &#peginator_crate::runtime::TextEdit,
+                settings: &peginator_generated::ParseSettings,
+                user_defined: #user_defined_type,
+            ) -> #peginator_crate::runtime::IncrementalParse<Self, Self::Cache> {
+                let mut global = peginator_generated
+                    ::ParseGlobal
+                    ::<TT, peginator_generated::ParseCache, #user_defined_type>
+                    ::new(cache.attach(s, edit), user_defined, settings);
+                let result = peginator_generated::#internal_parser_name(
+                    peginator_generated::ParseState::new(s, settings),
+                    &mut global,
+                )
+                .map(|ok| ok.result)
+                .map_err(|err| err.with_found(s));
+                #peginator_crate::runtime::IncrementalParse {
+                    result,
+                    cache: global.cache.detach(),
+                }
+            }
+        }
+    )
+}
@@ -6,12 +6,9 @@ use sha2::{Digest, Sha256};
 
 use super::{BUILD_TIME, VERSION};
 
+/// The header of a file generated from a single grammar file.
 pub fn generate_source_header(grammar: &str, use_build_time: bool) -> String {
-    let version = if use_build_time {
-        format!("{VERSION}@{BUILD_TIME}")
-    } else {
-        VERSION.to_string()
-    };
+    let version = version(use_build_time);
     let hash = Sha256::digest(grammar);
     format!(
         "// This file was generated by Peginator v{version}\n\
@@ -19,3 +16,35 @@ pub fn generate_source_header(grammar: &str, use_build_time: bool) -> String {
          // Any changes to it will be lost on regeneration\n",
     )
 }
+
+/// The header of the generated file. The hash covers the contents of all grammar files that the
+/// code was generated from (see [`LoadedGrammar::sources`](crate::grammar::LoadedGrammar)), so
+/// that it changes when any of the imported files change.
+///
+/// Grammars without `@import`s and `@extends` get the same header as from
+/// [`generate_source_header`], so that existing generated files are not seen as stale.
+pub fn generate_loaded_source_header(grammar_sources: &[String], use_build_time: bool) -> String {
+    if let [grammar] = grammar_sources {
+        return generate_source_header(grammar, use_build_time);
+    }
+    let version = version(use_build_time);
+    let mut hasher = Sha256::new();
+    for source in grammar_sources {
+        hasher.update(source.len().to_le_bytes());
+        hasher.update(source);
+    }
+    let hash = hasher.finalize();
+    format!(
+        "// This file was generated by Peginator v{version}\n\
+         // Hash of the grammar files: {hash:X}\n\
+         // Any changes to it will be lost on regeneration\n",
+    )
+}
+
+fn version(use_build_time: bool) -> String {
+    if use_build_time {
+        format!("{VERSION}@{BUILD_TIME}")
+    } else {
+        VERSION.to_string()
+    }
+}
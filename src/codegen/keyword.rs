@@ -0,0 +1,132 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use anyhow::{bail, Result};
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use super::common::CodegenSettings;
+use crate::grammar::{
+    Choice, DelimitedExpression, Grammar, GrammarDirective, Grammar_rules, Sequence, StringLiteral,
+};
+
+impl StringLiteral {
+    /// The value of a keyword literal, as used in error messages and `@not_keyword` checks.
+    /// Lowercase for case insensitive keywords.
+    pub fn keyword_value(&self) -> Result<String> {
+        let value = self.value()?;
+        if self.insensitive.is_some() {
+            Ok(value.to_lowercase())
+        } else {
+            Ok(value)
+        }
+    }
+}
+
+/// Generate the check for a word character after a keyword literal, for `parse_keyword`.
+pub fn generate_keyword_boundary_check(grammar: &Grammar) -> Result<TokenStream> {
+    let mut boundary_rules = grammar.directives.iter().filter_map(|d| match d {
+        GrammarDirective::KeywordBoundaryDirective(d) => Some(&d.rule),
+        _ => None,
+    });
+    let Some(boundary_rule) = boundary_rules.next() else {
+        return Ok(quote!(followed_by_word_char));
+    };
+    if boundary_rules.next().is_some() {
+        bail!("Only one @keyword_boundary directive can be used in a grammar");
+    }
+    let is_char_rule = grammar
+        .rules
+        .iter()
+        .any(|r| matches!(r, Grammar_rules::CharRule(r) if &r.name == boundary_rule));
+    if !is_char_rule {
+        bail!("The @keyword_boundary rule {boundary_rule} is not a @char rule");
+    }
+    let parser_name = format_ident!("parse_{}", boundary_rule);
+    Ok(quote!(|state| #parser_name(state.clone(), &mut *global).is_ok()))
+}
+
+/// Generate the check of `@not_keyword` rules, rejecting matches that are equal to one of the
+/// keyword literals in the grammar.
+pub fn generate_not_keyword_check(
+    grammar: &Grammar,
+    settings: &CodegenSettings,
+) -> Result<TokenStream> {
+    let mut keywords = Vec::new();
+    let mut insensitive_keywords = Vec::new();
+    let mut collect_keyword = |literal: &StringLiteral| {
+        let keyword = literal.keyword_value()?;
+        if settings.byte_input && !keyword.is_ascii() {
+            bail!("@not_keyword cannot be used with non-ASCII keywords in byte input mode ({keyword:?})");
+        }
+        let keywords = if literal.insensitive.is_some() {
+            &mut insensitive_keywords
+        } else {
+            &mut keywords
+        };
+        if !keywords.contains(&keyword) {
+            keywords.push(keyword);
+        }
+        Ok(())
+    };
+    for rule in &grammar.rules {
+        match rule {
+            Grammar_rules::Rule(rule) => visit_keywords(&rule.definition, &mut collect_keyword)?,
+            Grammar_rules::PrecedenceRule(rule) => {
+                for level in &rule.levels {
+                    for operator in &level.operators {
+                        visit_keywords_in_sequence(&operator.body, &mut collect_keyword)?;
+                    }
+                }
+            }
+            _ => (),
+        }
+    }
+    Ok(quote!(
+        let result = check_not_keyword(
+            &rule_start_state,
+            result,
+            &[#(#keywords),*],
+            &[#(#insensitive_keywords),*],
+        );
+    ))
+}
+
+fn visit_keywords(choice: &Choice, f: &mut impl FnMut(&StringLiteral) -> Result<()>) -> Result<()> {
+    for sequence in &choice.choices {
+        visit_keywords_in_sequence(sequence, f)?;
+    }
+    Ok(())
+}
+
+fn visit_keywords_in_sequence(
+    sequence: &Sequence,
+    f: &mut impl FnMut(&StringLiteral) -> Result<()>,
+) -> Result<()> {
+    for part in &sequence.parts {
+        visit_keywords_in_expression(part, f)?;
+    }
+    Ok(())
+}
+
+fn visit_keywords_in_expression(
+    expression: &DelimitedExpression,
+    f: &mut impl FnMut(&StringLiteral) -> Result<()>,
+) -> Result<()> {
+    match expression {
+        DelimitedExpression::Group(a) => visit_keywords(&a.body, f),
+        DelimitedExpression::Optional(a) => visit_keywords(&a.body, f),
+        DelimitedExpression::Closure(a) => {
+            visit_keywords(&a.body, f)?;
+            match &a.separator {
+                Some(separator) => visit_keywords(&separator.body, f),
+                None => Ok(()),
+            }
+        }
+        DelimitedExpression::NegativeLookahead(a) => visit_keywords_in_expression(&a.expr, f),
+        DelimitedExpression::PositiveLookahead(a) => visit_keywords_in_expression(&a.expr, f),
+        DelimitedExpression::StringLiteral(a) if a.keyword.is_some() => f(a),
+        _ => Ok(()),
+    }
+}
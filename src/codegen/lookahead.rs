@@ -6,7 +6,9 @@ use anyhow::{bail, Result};
 use proc_macro2::TokenStream;
 use quote::quote;
 
-use super::common::{generate_inner_parse_function, Codegen, CodegenSettings, FieldDescriptor};
+use super::common::{
+    generate_inner_parse_function, grammar_recovers, Codegen, CodegenSettings, FieldDescriptor,
+};
 use crate::grammar::{Grammar, NegativeLookahead, PositiveLookahead};
 
 impl Codegen for NegativeLookahead {
@@ -17,11 +19,24 @@ impl Codegen for NegativeLookahead {
         settings: &CodegenSettings,
     ) -> Result<TokenStream> {
         let body = self.expr.generate_code(rule_fields, grammar, settings)?;
-        let parse_body = quote!(match negative_lookahead::parse(state.clone(), global) {
-            Ok(_) => Err(state.report_error(ParseErrorSpecifics::NegativeLookaheadFailed)),
-            Err(_) => Ok(ParseOk { result: (), state }),
+        // The input looked at by the lookahead body is still recorded, even though the
+        // results of the body are thrown away.
+        let parse_call = generate_lookahead_call(
+            quote!(negative_lookahead::parse(state.clone(), global)),
+            grammar,
+        );
+        let parse_body = quote!(match #parse_call {
+            Ok(ParseOk {
+                state: inner_state, ..
+            }) => Err(state
+                .merge_examined(inner_state.examined_until())
+                .report_error(ParseErrorSpecifics::NegativeLookaheadFailed)),
+            Err(err) => Ok(ParseOk {
+                result: (),
+                state: state.merge_examined(err.examined_until())
+            }),
         });
-        let parse_function = generate_inner_parse_function(parse_body, settings);
+        let parse_function = generate_inner_parse_function(parse_body, false, settings);
         Ok(quote!(
             mod negative_lookahead{
                 use super::*;
@@ -31,7 +46,7 @@ impl Codegen for NegativeLookahead {
         ))
     }
 
-    fn get_fields(&self, grammar: &Grammar) -> Result<Vec<FieldDescriptor>> {
+    fn get_fields(&self, grammar: &Grammar) -> Result<Vec<FieldDescriptor<'_>>> {
         if !self.expr.get_fields(grammar)?.is_empty() {
             bail!("The body of negative lookaheads should not contain named fields")
         }
@@ -47,11 +62,16 @@ impl Codegen for PositiveLookahead {
         settings: &CodegenSettings,
     ) -> Result<TokenStream> {
         let body = self.expr.generate_code(rule_fields, grammar, settings)?;
+        let parse_call = generate_lookahead_call(
+            quote!(positive_lookahead::parse(state.clone(), global)),
+            grammar,
+        );
         let parse_body = quote!(
-            positive_lookahead::parse (state.clone(), global)?;
-            Ok(ParseOk{result:(), state})
+            let ParseOk{state: inner_state, ..} = #parse_call
+                .map_err(ParseFailure::uncommit)?;
+            Ok(ParseOk{result:(), state: state.merge_examined(inner_state.examined_until())})
         );
-        let parse_function = generate_inner_parse_function(parse_body, settings);
+        let parse_function = generate_inner_parse_function(parse_body, false, settings);
         Ok(quote!(
             mod positive_lookahead{
                 use super::*;
@@ -61,10 +81,20 @@ impl Codegen for PositiveLookahead {
         ))
     }
 
-    fn get_fields(&self, grammar: &Grammar) -> Result<Vec<FieldDescriptor>> {
+    fn get_fields(&self, grammar: &Grammar) -> Result<Vec<FieldDescriptor<'_>>> {
         if !self.expr.get_fields(grammar)?.is_empty() {
             bail!("The body of positive lookaheads should not contain named fields")
         }
         Ok(Vec::new())
     }
 }
+
+/// The results of lookaheads are thrown away, so the errors recovered from during them are
+/// forgotten, see `ParseGlobal::lookahead`.
+fn generate_lookahead_call(parse_call: TokenStream, grammar: &Grammar) -> TokenStream {
+    if grammar_recovers(grammar) {
+        quote!(global.lookahead(|global| #parse_call))
+    } else {
+        parse_call
+    }
+}
@@ -27,6 +27,9 @@ impl Codegen for DelimitedExpression {
             DelimitedExpression::PositiveLookahead(a) => {
                 a.generate_code_spec(rule_fields, grammar, settings)
             }
+            DelimitedExpression::UnicodeClass(a) => {
+                a.generate_code_spec(rule_fields, grammar, settings)
+            }
             DelimitedExpression::CharacterRange(a) => {
                 a.generate_code_spec(rule_fields, grammar, settings)
             }
@@ -36,6 +39,7 @@ impl Codegen for DelimitedExpression {
             DelimitedExpression::EndOfInput(a) => {
                 a.generate_code_spec(rule_fields, grammar, settings)
             }
+            DelimitedExpression::Cut(a) => a.generate_code_spec(rule_fields, grammar, settings),
             DelimitedExpression::OverrideField(a) => {
                 a.generate_code_spec(rule_fields, grammar, settings)
             }
@@ -60,6 +64,9 @@ impl Codegen for DelimitedExpression {
             DelimitedExpression::Closure(a) => {
                 a.generate_inline_body(rule_fields, grammar, settings, clone_state)
             }
+            DelimitedExpression::Cut(a) => {
+                a.generate_inline_body(rule_fields, grammar, settings, clone_state)
+            }
             DelimitedExpression::EndOfInput(a) => {
                 a.generate_inline_body(rule_fields, grammar, settings, clone_state)
             }
@@ -87,6 +94,9 @@ impl Codegen for DelimitedExpression {
             DelimitedExpression::StringLiteral(a) => {
                 a.generate_inline_body(rule_fields, grammar, settings, clone_state)
             }
+            DelimitedExpression::UnicodeClass(a) => {
+                a.generate_inline_body(rule_fields, grammar, settings, clone_state)
+            }
         }
     }
 
@@ -97,9 +107,11 @@ impl Codegen for DelimitedExpression {
             DelimitedExpression::Closure(a) => a.get_fields(grammar),
             DelimitedExpression::NegativeLookahead(a) => a.get_fields(grammar),
             DelimitedExpression::PositiveLookahead(a) => a.get_fields(grammar),
+            DelimitedExpression::UnicodeClass(a) => a.get_fields(grammar),
             DelimitedExpression::CharacterRange(a) => a.get_fields(grammar),
             DelimitedExpression::StringLiteral(a) => a.get_fields(grammar),
             DelimitedExpression::EndOfInput(a) => a.get_fields(grammar),
+            DelimitedExpression::Cut(a) => a.get_fields(grammar),
             DelimitedExpression::OverrideField(a) => a.get_fields(grammar),
             DelimitedExpression::IncludeRule(a) => a.get_fields(grammar),
             DelimitedExpression::Field(a) => a.get_fields(grammar),
@@ -7,21 +7,26 @@ mod char_rule;
 mod choice;
 mod closure;
 mod common;
+mod cut;
 mod eoi;
 mod extern_rule;
 mod field;
 mod grammar;
 mod header;
 mod include_rule;
+mod keyword;
 mod lookahead;
 mod misc;
 mod optional;
+mod parameterized;
+mod precedence;
 mod rule;
 mod sequence;
 mod string;
+mod unicode_class;
 
 pub use common::{CodegenGrammar, CodegenSettings};
-pub use header::generate_source_header;
+pub use header::{generate_loaded_source_header, generate_source_header};
 
 pub const VERSION: &str = env!("CARGO_PKG_VERSION");
 pub const BUILD_TIME: &str = build_time_utc!();
@@ -7,8 +7,8 @@ use proc_macro2::TokenStream;
 use quote::quote;
 
 use super::common::{
-    generate_inner_parse_function, safe_ident, Arity, CloneState, Codegen, CodegenSettings,
-    FieldDescriptor,
+    generate_attempt, generate_inner_parse_function, safe_ident, Arity, CloneState, Codegen,
+    CodegenSettings, FieldDescriptor,
 };
 use crate::grammar::{Grammar, Optional};
 
@@ -36,8 +36,13 @@ impl Codegen for Optional {
             });
             parse_call = quote!(optional::parse(state.clone(), global));
         };
+        let parse_call = generate_attempt(parse_call, grammar);
         let parse_body = quote!(#parse_call #postprocess);
-        let parse_function = generate_inner_parse_function(parse_body, settings);
+        let parse_function = generate_inner_parse_function(
+            parse_body,
+            self.parsed_type_borrows_input(rule_fields, grammar, settings)?,
+            settings,
+        );
         Ok(quote!(
             #body
             #parse_function
@@ -58,6 +63,7 @@ impl Codegen for Optional {
                 .generate_inline_body(rule_fields, grammar, settings, CloneState::Yes)?
         {
             let postprocess = self.generate_postprocess_calls(rule_fields, grammar)?;
+            let inline_body = generate_attempt(inline_body, grammar);
             Ok(Some(quote!(#inline_body #postprocess)))
         } else {
             Ok(None)
@@ -78,21 +84,25 @@ impl Optional {
         let fields = self.body.get_filtered_rule_fields(rule_fields, grammar)?;
         let result = if fields.is_empty() {
             quote!(
-                .or_else(|err|
+                .or_else(|err| if err.is_committed() {
+                    Err(err.uncommit())
+                } else {
                     Ok(ParseOk{
                         result: (),
                         state: state.record_error(err),
                     })
-                )
+                })
             )
         } else if fields.len() == 1 {
             quote!(
-                .or_else(|err|
+                .or_else(|err| if err.is_committed() {
+                    Err(err.uncommit())
+                } else {
                     Ok(ParseOk{
                         result: Default::default(),
                         state: state.record_error(err),
                     })
-                )
+                })
             )
         } else {
             let happy_case_fields: TokenStream = fields
@@ -111,12 +121,14 @@ impl Optional {
                 .collect();
             quote!(
                 .map_inner(|result| Parsed{#happy_case_fields})
-                .or_else(|err|
+                .or_else(|err| if err.is_committed() {
+                    Err(err.uncommit())
+                } else {
                     Ok(ParseOk{
                         result: Parsed{#unhappy_case_fields},
                         state: state.record_error(err),
                     })
-                )
+                })
             )
         };
         Ok(result)
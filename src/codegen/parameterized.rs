@@ -0,0 +1,249 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use anyhow::{bail, Result};
+
+use crate::grammar::{
+    Choice, DelimitedExpression, Grammar, Grammar_rules, PrecedenceRule, Rule, Sequence,
+    TypeArguments, TypeReference,
+};
+
+/// The maximum number of parameterized rule instances, to catch rules that instantiate
+/// themselves with ever growing type arguments.
+const MAX_INSTANCES: usize = 1000;
+
+/// Replace the uses of parameterized rules (e.g. `CommaList<Expr>`) with concrete instances of
+/// them (e.g. `CommaListExpr`), and remove the parameterized rules themselves.
+pub fn instantiate_parameterized_rules(grammar: &Grammar) -> Result<Grammar> {
+    let mut instantiator = Instantiator {
+        generic_rules: HashMap::new(),
+        rule_names: HashSet::new(),
+        instances: HashMap::new(),
+        pending: VecDeque::new(),
+    };
+    for rule_entry in &grammar.rules {
+        let name = match rule_entry {
+            Grammar_rules::Rule(rule) => {
+                if rule.parameters.is_some() {
+                    instantiator.generic_rules.insert(&rule.name, rule);
+                }
+                &rule.name
+            }
+            Grammar_rules::CharRule(rule) => &rule.name,
+            Grammar_rules::ExternRule(rule) => &rule.name,
+            Grammar_rules::PrecedenceRule(rule) => &rule.name,
+        };
+        instantiator.rule_names.insert(name);
+    }
+    let mut rules = Vec::new();
+    for rule_entry in &grammar.rules {
+        match rule_entry {
+            Grammar_rules::Rule(rule) if rule.parameters.is_some() => (),
+            Grammar_rules::Rule(rule) => {
+                let mut rule = rule.clone();
+                instantiator.resolve_rule(&mut rule)?;
+                rules.push(Grammar_rules::Rule(rule));
+            }
+            Grammar_rules::PrecedenceRule(rule) => {
+                let mut rule = rule.clone();
+                instantiator.resolve_precedence_rule(&mut rule)?;
+                rules.push(Grammar_rules::PrecedenceRule(rule));
+            }
+            other => rules.push(other.clone()),
+        }
+    }
+    while let Some(mut rule) = instantiator.pending.pop_front() {
+        instantiator.resolve_rule(&mut rule)?;
+        rules.push(Grammar_rules::Rule(rule));
+    }
+    Ok(Grammar {
+        rules,
+        directives: grammar.directives.clone(),
+    })
+}
+
+struct Instantiator<'a> {
+    generic_rules: HashMap<&'a str, &'a Rule>,
+    rule_names: HashSet<&'a str>,
+    /// Instance name -> the type it was instantiated for, e.g. `CommaList<Expr>`
+    instances: HashMap<String, String>,
+    pending: VecDeque<Rule>,
+}
+
+impl<'a> Instantiator<'a> {
+    fn resolve_rule(&mut self, rule: &mut Rule) -> Result<()> {
+        visit_references(&mut rule.definition, &mut |name, type_arguments| {
+            self.resolve_reference(name, type_arguments.take())
+        })
+    }
+
+    fn resolve_precedence_rule(&mut self, rule: &mut PrecedenceRule) -> Result<()> {
+        self.resolve_reference(&mut rule.directive.operand, None)?;
+        for level in &mut rule.levels {
+            for operator in &mut level.operators {
+                visit_references_in_sequence(&mut operator.body, &mut |name, type_arguments| {
+                    self.resolve_reference(name, type_arguments.take())
+                })?;
+            }
+        }
+        Ok(())
+    }
+
+    fn resolve_reference(
+        &mut self,
+        name: &mut String,
+        type_arguments: Option<TypeArguments>,
+    ) -> Result<()> {
+        let generic_rule = self.generic_rules.get(name.as_str()).copied();
+        let (generic_rule, type_arguments) = match (generic_rule, type_arguments) {
+            (None, None) => return Ok(()),
+            (Some(_), None) => bail!("Parameterized rule {name} used without type arguments"),
+            (None, Some(_)) => bail!("Type arguments used on {name}, which has no parameters"),
+            (Some(rule), Some(type_arguments)) => (rule, type_arguments),
+        };
+        let parameters = &generic_rule
+            .parameters
+            .as_ref()
+            .expect("Generic rules have parameters")
+            .names;
+        if parameters.len() != type_arguments.arguments.len() {
+            bail!(
+                "Rule {name} expects {} type arguments, but {} were given",
+                parameters.len(),
+                type_arguments.arguments.len()
+            );
+        }
+        let instance_name = instance_name(name, &type_arguments);
+        let instance_type = type_display(name, Some(&type_arguments));
+        match self.instances.get(&instance_name) {
+            Some(existing_type) if *existing_type != instance_type => bail!(
+                "The instance names of {existing_type} and {instance_type} are the same ({instance_name})"
+            ),
+            Some(_) => (),
+            None => {
+                if self.rule_names.contains(instance_name.as_str()) {
+                    bail!("The instance name of {instance_type} conflicts with rule {instance_name}");
+                }
+                if self.instances.len() >= MAX_INSTANCES {
+                    bail!("Too many parameterized rule instances (recursive instantiation?)");
+                }
+                let mut instance = generic_rule.clone();
+                instance.name = instance_name.clone();
+                instance.parameters = None;
+                let substitutions: HashMap<&str, &TypeReference> = parameters
+                    .iter()
+                    .map(String::as_str)
+                    .zip(&type_arguments.arguments)
+                    .collect();
+                visit_references(&mut instance.definition, &mut |name, type_arguments| {
+                    substitute(name, type_arguments, &substitutions)
+                })?;
+                self.instances.insert(instance_name.clone(), instance_type);
+                self.pending.push_back(instance);
+            }
+        }
+        *name = instance_name;
+        Ok(())
+    }
+}
+
+fn substitute(
+    name: &mut String,
+    type_arguments: &mut Option<TypeArguments>,
+    substitutions: &HashMap<&str, &TypeReference>,
+) -> Result<()> {
+    if let Some(substitution) = substitutions.get(name.as_str()) {
+        if type_arguments.is_some() {
+            bail!("Type parameter {name} cannot have type arguments");
+        }
+        *name = substitution.name.clone();
+        *type_arguments = substitution.type_arguments.as_deref().cloned();
+    } else if let Some(type_arguments) = type_arguments {
+        for argument in &mut type_arguments.arguments {
+            let mut argument_arguments = argument.type_arguments.take().map(|a| *a);
+            substitute(&mut argument.name, &mut argument_arguments, substitutions)?;
+            argument.type_arguments = argument_arguments.map(Box::new);
+        }
+    }
+    Ok(())
+}
+
+fn instance_name(name: &str, type_arguments: &TypeArguments) -> String {
+    let mut result = name.to_string();
+    for argument in &type_arguments.arguments {
+        result.push_str(&instance_name_of_reference(argument));
+    }
+    result
+}
+
+fn instance_name_of_reference(reference: &TypeReference) -> String {
+    match &reference.type_arguments {
+        Some(type_arguments) => instance_name(&reference.name, type_arguments),
+        None => reference.name.clone(),
+    }
+}
+
+fn type_display(name: &str, type_arguments: Option<&TypeArguments>) -> String {
+    match type_arguments {
+        Some(type_arguments) => {
+            let arguments: Vec<String> = type_arguments
+                .arguments
+                .iter()
+                .map(|a| type_display(&a.name, a.type_arguments.as_deref()))
+                .collect();
+            format!("{name}<{}>", arguments.join(", "))
+        }
+        None => name.to_string(),
+    }
+}
+
+/// Call `f` on all rule references (field types and included rules) in `choice`.
+fn visit_references(
+    choice: &mut Choice,
+    f: &mut impl FnMut(&mut String, &mut Option<TypeArguments>) -> Result<()>,
+) -> Result<()> {
+    for sequence in &mut choice.choices {
+        visit_references_in_sequence(sequence, f)?;
+    }
+    Ok(())
+}
+
+fn visit_references_in_sequence(
+    sequence: &mut Sequence,
+    f: &mut impl FnMut(&mut String, &mut Option<TypeArguments>) -> Result<()>,
+) -> Result<()> {
+    for part in &mut sequence.parts {
+        visit_references_in_expression(part, f)?;
+    }
+    Ok(())
+}
+
+fn visit_references_in_expression(
+    expression: &mut DelimitedExpression,
+    f: &mut impl FnMut(&mut String, &mut Option<TypeArguments>) -> Result<()>,
+) -> Result<()> {
+    match expression {
+        DelimitedExpression::Group(a) => visit_references(&mut a.body, f),
+        DelimitedExpression::Optional(a) => visit_references(&mut a.body, f),
+        DelimitedExpression::Closure(a) => {
+            visit_references(&mut a.body, f)?;
+            match &mut a.separator {
+                Some(separator) => visit_references(&mut separator.body, f),
+                None => Ok(()),
+            }
+        }
+        DelimitedExpression::NegativeLookahead(a) => visit_references_in_expression(&mut a.expr, f),
+        DelimitedExpression::PositiveLookahead(a) => visit_references_in_expression(&mut a.expr, f),
+        DelimitedExpression::Field(a) => f(&mut a.typ, &mut a.type_arguments),
+        DelimitedExpression::OverrideField(a) => f(&mut a.typ, &mut a.type_arguments),
+        DelimitedExpression::IncludeRule(a) => f(&mut a.rule, &mut a.type_arguments),
+        DelimitedExpression::UnicodeClass(_)
+        | DelimitedExpression::CharacterRange(_)
+        | DelimitedExpression::StringLiteral(_)
+        | DelimitedExpression::EndOfInput(_)
+        | DelimitedExpression::Cut(_) => Ok(()),
+    }
+}
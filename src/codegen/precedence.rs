@@ -0,0 +1,526 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use std::collections::HashSet;
+
+use anyhow::{bail, Result};
+use proc_macro2::{Ident, TokenStream};
+use quote::{format_ident, quote};
+
+use super::common::{
+    generate_attempt, generate_derives, generate_lifetime_params, generate_parsed_type,
+    generate_rule_parse_function, generate_rule_type, generate_shift_positions_impl,
+    generate_skip_ws, grammar_recovers, safe_ident, type_borrows_input, type_has_positions,
+    CloneState, Codegen, CodegenSettings,
+};
+use crate::grammar::{
+    Grammar, Grammar_rules, PrecedenceLevel, PrecedenceLevelKind, PrecedenceOperator,
+    PrecedenceRule, PrecedenceRuleDirective,
+};
+
+/// How an operator binds to its operands. An operator with a higher binding power binds
+/// tighter.
+#[derive(Debug, Clone, Copy)]
+enum Binding {
+    Binary { left: u32, right: u32 },
+    Prefix { right: u32 },
+    Postfix { left: u32 },
+}
+
+impl Binding {
+    fn is_binary(&self) -> bool {
+        matches!(self, Binding::Binary { .. })
+    }
+}
+
+impl PrecedenceLevel {
+    /// The binding of the operators of the level at `index`, counted from the loosest one.
+    ///
+    /// Each level gets two binding powers above the ones of the looser levels. The right side of
+    /// left associative operators binds tighter than their left side (and vice versa), so that
+    /// `a - b - c` is parsed as `(a - b) - c`.
+    fn binding(&self, index: usize) -> Binding {
+        let base = 2 * (index as u32 + 1);
+        match self.kind {
+            PrecedenceLevelKind::LeftAssociative(_) => Binding::Binary {
+                left: base,
+                right: base + 1,
+            },
+            PrecedenceLevelKind::RightAssociative(_) => Binding::Binary {
+                left: base + 1,
+                right: base,
+            },
+            PrecedenceLevelKind::PrefixOperators(_) => Binding::Prefix { right: base },
+            PrecedenceLevelKind::PostfixOperators(_) => Binding::Postfix { left: base },
+        }
+    }
+}
+
+impl PrecedenceRule {
+    pub fn is_exported(&self) -> bool {
+        self.directives
+            .iter()
+            .any(|d| matches!(d, PrecedenceRuleDirective::ExportDirective(_)))
+    }
+
+    fn skips_whitespace(&self) -> bool {
+        !self
+            .directives
+            .iter()
+            .any(|d| matches!(d, PrecedenceRuleDirective::NoSkipWsDirective(_)))
+    }
+
+    fn operators(&self) -> impl Iterator<Item = (&PrecedenceOperator, Binding)> {
+        self.levels.iter().enumerate().flat_map(|(index, level)| {
+            let binding = level.binding(index);
+            level
+                .operators
+                .iter()
+                .map(move |operator| (operator, binding))
+        })
+    }
+
+    fn binary_ident(&self) -> Ident {
+        format_ident!("{}Binary", self.name)
+    }
+
+    fn binary_operator_ident(&self) -> Ident {
+        format_ident!("{}BinaryOperator", self.name)
+    }
+
+    fn unary_ident(&self) -> Ident {
+        format_ident!("{}Unary", self.name)
+    }
+
+    fn unary_operator_ident(&self) -> Ident {
+        format_ident!("{}UnaryOperator", self.name)
+    }
+
+    fn has_binary_operators(&self) -> bool {
+        self.operators().any(|(_, binding)| binding.is_binary())
+    }
+
+    fn has_unary_operators(&self) -> bool {
+        self.operators().any(|(_, binding)| !binding.is_binary())
+    }
+
+    fn check(&self, grammar: &Grammar) -> Result<()> {
+        let operand = &self.directive.operand;
+        let rule_names: HashSet<&str> = grammar
+            .rules
+            .iter()
+            .map(|rule| match rule {
+                Grammar_rules::Rule(r) => r.name.as_str(),
+                Grammar_rules::CharRule(r) => r.name.as_str(),
+                Grammar_rules::ExternRule(r) => r.name.as_str(),
+                Grammar_rules::PrecedenceRule(r) => r.name.as_str(),
+            })
+            .collect();
+        if operand == &self.name || !rule_names.contains(operand.as_str()) {
+            bail!(
+                "The operand of @precedence rule {} must be another rule",
+                self.name
+            );
+        }
+        if operand == "Binary" || operand == "Unary" {
+            bail!(
+                "The operand of @precedence rule {} cannot be called {operand}",
+                self.name
+            );
+        }
+        for type_ident in [
+            self.binary_ident(),
+            self.binary_operator_ident(),
+            self.unary_ident(),
+            self.unary_operator_ident(),
+        ] {
+            if rule_names.contains(type_ident.to_string().as_str()) {
+                bail!(
+                    "The type {type_ident} generated for @precedence rule {} conflicts with a rule",
+                    self.name
+                );
+            }
+        }
+        let mut operator_names = HashSet::new();
+        for (operator, _) in self.operators() {
+            if !operator_names.insert(&operator.name) {
+                bail!(
+                    "Operator {} is defined twice in @precedence rule {}",
+                    operator.name,
+                    self.name
+                );
+            }
+            if !operator.body.get_fields(grammar)?.is_empty() {
+                bail!("Precedence operators cannot have fields");
+            }
+        }
+        Ok(())
+    }
+
+    pub fn generate_code(
+        &self,
+        grammar: &Grammar,
+        settings: &CodegenSettings,
+    ) -> Result<(TokenStream, TokenStream)> {
+        self.check(grammar)?;
+        let settings = CodegenSettings {
+            skip_whitespace: settings.skip_whitespace && self.skips_whitespace(),
+            ..settings.clone()
+        };
+        let name = &self.name;
+        let rule_mod = format_ident!("{}_impl", self.name);
+        let rule_type = generate_rule_type(&self.name, grammar, &settings);
+        let parser_name = format_ident!("parse_{}", self.name);
+        let parsers = self.generate_parsers(grammar, &settings)?;
+
+        // Same as the outer parts of normal rules, see `Rule::generate_code`
+        let parse_body = quote!(
+            global.tracer.print_trace_start(&state, #name);
+            let result = state.parse_rule(#name, global.record_context, |state| {
+                #rule_mod::parse(state, global, 0).map_err(ParseFailure::uncommit)
+            });
+            global.tracer.print_trace_result(&result);
+            result
+        );
+        let parse_function =
+            generate_rule_parse_function(parser_name, rule_type, parse_body, &settings);
+        Ok((
+            self.generate_types(grammar, &settings),
+            quote!(
+                mod #rule_mod {
+                    use super::*;
+                    #parsers
+                }
+                #parse_function
+            ),
+        ))
+    }
+
+    fn generate_types(&self, grammar: &Grammar, settings: &CodegenSettings) -> TokenStream {
+        let derives = generate_derives(settings);
+        let rule_ident = safe_ident(&self.name);
+        let lifetime_params =
+            generate_lifetime_params(type_borrows_input(&self.name, grammar, settings));
+        let rule_type = quote!(#rule_ident #lifetime_params);
+        let operand = &self.directive.operand;
+        let operand_ident = safe_ident(operand);
+        let operand_type = generate_parsed_type(operand, grammar, settings);
+        let has_positions = type_has_positions(&self.name, grammar);
+        let shift_positions = |fields: TokenStream| {
+            if has_positions {
+                fields
+            } else {
+                quote!()
+            }
+        };
+
+        let mut types = TokenStream::new();
+        let mut variants = TokenStream::new();
+        let mut shifted_variants = TokenStream::new();
+        if self.has_binary_operators() {
+            let binary_ident = self.binary_ident();
+            let binary_operator_ident = self.binary_operator_ident();
+            let operator_idents = self
+                .operators()
+                .filter(|(_, binding)| binding.is_binary())
+                .map(|(operator, _)| safe_ident(&operator.name));
+            let shift_positions_impl = generate_shift_positions_impl(
+                &binary_ident,
+                shift_positions(quote!(
+                    self.left.shift_positions(offset);
+                    self.right.shift_positions(offset);
+                )),
+                settings,
+            );
+            let operator_shift_positions_impl =
+                generate_shift_positions_impl(&binary_operator_ident, quote!(), settings);
+            types.extend(quote!(
+                #derives
+                pub struct #binary_ident #lifetime_params {
+                    pub left: Box<#rule_type>,
+                    pub operator: #binary_operator_ident,
+                    pub right: Box<#rule_type>,
+                }
+                #shift_positions_impl
+                #derives
+                pub enum #binary_operator_ident {
+                    #(#operator_idents,)*
+                }
+                #operator_shift_positions_impl
+            ));
+            variants.extend(quote!(Binary(#binary_ident #lifetime_params),));
+            shifted_variants.extend(quote!(Self::Binary(x) => x.shift_positions(offset),));
+        }
+        if self.has_unary_operators() {
+            let unary_ident = self.unary_ident();
+            let unary_operator_ident = self.unary_operator_ident();
+            let operator_idents = self
+                .operators()
+                .filter(|(_, binding)| !binding.is_binary())
+                .map(|(operator, _)| safe_ident(&operator.name));
+            let shift_positions_impl = generate_shift_positions_impl(
+                &unary_ident,
+                shift_positions(quote!(self.operand.shift_positions(offset);)),
+                settings,
+            );
+            let operator_shift_positions_impl =
+                generate_shift_positions_impl(&unary_operator_ident, quote!(), settings);
+            types.extend(quote!(
+                #derives
+                pub struct #unary_ident #lifetime_params {
+                    pub operator: #unary_operator_ident,
+                    pub operand: Box<#rule_type>,
+                }
+                #shift_positions_impl
+                #derives
+                pub enum #unary_operator_ident {
+                    #(#operator_idents,)*
+                }
+                #operator_shift_positions_impl
+            ));
+            variants.extend(quote!(Unary(#unary_ident #lifetime_params),));
+            shifted_variants.extend(quote!(Self::Unary(x) => x.shift_positions(offset),));
+        }
+        shifted_variants.extend(quote!(Self::#operand_ident(x) => x.shift_positions(offset),));
+        let shift_positions_impl = generate_shift_positions_impl(
+            &rule_ident,
+            shift_positions(quote!(
+                match self {
+                    #shifted_variants
+                }
+            )),
+            settings,
+        );
+        quote!(
+            #derives
+            pub enum #rule_type {
+                #variants
+                #operand_ident(#operand_type),
+            }
+            #shift_positions_impl
+            #types
+        )
+    }
+
+    /// The precedence climbing parser: an operand (possibly with prefix operators), followed by
+    /// any number of binary or postfix operators that bind at least as tight as
+    /// `min_binding_power`. The right operands of binary operators are parsed recursively, with
+    /// the right binding power of the operator.
+    fn generate_parsers(
+        &self,
+        grammar: &Grammar,
+        settings: &CodegenSettings,
+    ) -> Result<TokenStream> {
+        let user_defined_type = &settings.user_defined_type;
+        let input_type_param = settings.input_type_param();
+        let rule_ident = safe_ident(&self.name);
+        let rule_type = generate_rule_type(&self.name, grammar, settings);
+        let binary_ident = self.binary_ident();
+        let binary_operator_ident = self.binary_operator_ident();
+        let unary_ident = self.unary_ident();
+        let unary_operator_ident = self.unary_operator_ident();
+
+        let mut operator_mods = TokenStream::new();
+        let mut prefix_choices = TokenStream::new();
+        let mut infix_choices = TokenStream::new();
+        for (num, (operator, binding)) in self.operators().enumerate() {
+            let operator_mod = format_ident!("operator_{num}");
+            let operator_body = operator.body.generate_code(&[], grammar, settings)?;
+            operator_mods.extend(quote!(
+                mod #operator_mod {
+                    use super::*;
+                    #operator_body
+                }
+            ));
+            let operator_ident = safe_ident(&operator.name);
+            let result = match binding {
+                Binding::Binary { left, right } => quote!(
+                    InfixOperator::Binary(#binary_operator_ident::#operator_ident, #left, #right)
+                ),
+                Binding::Postfix { left } => {
+                    quote!(InfixOperator::Postfix(#unary_operator_ident::#operator_ident, #left))
+                }
+                Binding::Prefix { right } => {
+                    quote!((#unary_operator_ident::#operator_ident, #right))
+                }
+            };
+            let operator_call = generate_attempt(
+                quote!(#operator_mod::parse(state, global).map_inner(|_| #result)),
+                grammar,
+            );
+            let choice = quote!(.choice(|state| #operator_call));
+            match binding {
+                Binding::Prefix { .. } => prefix_choices.extend(choice),
+                _ => infix_choices.extend(choice),
+            }
+        }
+
+        let operand_call = generate_skip_ws(
+            settings,
+            &format!("parse_{}", self.directive.operand),
+            quote!(&mut *global),
+            CloneState::No,
+        );
+        let operand_ident = safe_ident(&self.directive.operand);
+        let operand_call = generate_attempt(
+            quote!(#operand_call.map_inner(#rule_ident::#operand_ident)),
+            grammar,
+        );
+        // The errors recovered from in a discarded operator or right operand are forgotten.
+        let (save_recovered_errors, discard_recovered_errors) = if grammar_recovers(grammar) {
+            (
+                quote!(let recovered_errors = global.recovered_errors.len();),
+                quote!(global.recovered_errors.truncate(recovered_errors);),
+            )
+        } else {
+            (quote!(), quote!())
+        };
+        let prefix_parser = if prefix_choices.is_empty() {
+            quote!()
+        } else {
+            quote!(
+                fn parse_prefix_operation<'a, TT: ParseTracer>(
+                    state: ParseState<'a #input_type_param>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, #user_defined_type>,
+                ) -> ParseResult<'a, #rule_type #input_type_param> {
+                    let ParseOk {
+                        result: (operator, right_binding_power),
+                        state,
+                    } = ChoiceHelper::new(state) #prefix_choices .end()?;
+                    let ParseOk { result: operand, state } = parse(state, global, right_binding_power)?;
+                    Ok(ParseOk {
+                        result: #rule_ident::Unary(#unary_ident {
+                            operator,
+                            operand: Box::new(operand),
+                        }),
+                        state,
+                    })
+                }
+            )
+        };
+        let prefix_choice = if prefix_choices.is_empty() {
+            quote!()
+        } else {
+            let prefix_call =
+                generate_attempt(quote!(parse_prefix_operation(state, global)), grammar);
+            quote!(.choice(|state| #prefix_call))
+        };
+
+        let (infix_parser, infix_loop) = if infix_choices.is_empty() {
+            (quote!(), quote!())
+        } else {
+            let binary_variant = if self.has_binary_operators() {
+                quote!(Binary(#binary_operator_ident, u32, u32),)
+            } else {
+                quote!()
+            };
+            let has_postfix_operators = self
+                .operators()
+                .any(|(_, binding)| matches!(binding, Binding::Postfix { .. }));
+            let postfix_variant = if has_postfix_operators {
+                quote!(Postfix(#unary_operator_ident, u32),)
+            } else {
+                quote!()
+            };
+            let binary_arm = if self.has_binary_operators() {
+                quote!(
+                    Ok(ParseOk {
+                        result: InfixOperator::Binary(operator, left_binding_power, right_binding_power),
+                        state: operator_state,
+                    }) if left_binding_power >= min_binding_power => {
+                        match parse(operator_state, global, right_binding_power) {
+                            Ok(ParseOk { result: right, state: new_state }) => {
+                                left = #rule_ident::Binary(#binary_ident {
+                                    left: Box::new(left),
+                                    operator,
+                                    right: Box::new(right),
+                                });
+                                state = new_state;
+                            }
+                            Err(err) if err.is_committed() => return Err(err.uncommit()),
+                            Err(err) => {
+                                #discard_recovered_errors
+                                state = state.record_error(err);
+                                break;
+                            }
+                        }
+                    }
+                )
+            } else {
+                quote!()
+            };
+            let postfix_arm = if has_postfix_operators {
+                quote!(
+                    Ok(ParseOk {
+                        result: InfixOperator::Postfix(operator, left_binding_power),
+                        state: new_state,
+                    }) if left_binding_power >= min_binding_power => {
+                        left = #rule_ident::Unary(#unary_ident {
+                            operator,
+                            operand: Box::new(left),
+                        });
+                        state = new_state;
+                    }
+                )
+            } else {
+                quote!()
+            };
+            (
+                quote!(
+                    enum InfixOperator {
+                        #binary_variant
+                        #postfix_variant
+                    }
+
+                    #[inline]
+                    fn parse_infix_operator<'a, TT: ParseTracer>(
+                        state: ParseState<'a #input_type_param>,
+                        global: &mut ParseGlobal<TT, ParseCache<'a>, #user_defined_type>,
+                    ) -> ParseResult<'a, InfixOperator #input_type_param> {
+                        ChoiceHelper::new(state) #infix_choices .end()
+                    }
+                ),
+                quote!(
+                    loop {
+                        #save_recovered_errors
+                        match parse_infix_operator(state.clone(), global) {
+                            #binary_arm
+                            #postfix_arm
+                            // Looser operators are handled by an outer call.
+                            Ok(_) => {
+                                #discard_recovered_errors
+                                break;
+                            }
+                            Err(err) if err.is_committed() => return Err(err.uncommit()),
+                            Err(err) => {
+                                #discard_recovered_errors
+                                state = state.record_error(err);
+                                break;
+                            }
+                        }
+                    }
+                ),
+            )
+        };
+
+        Ok(quote!(
+            #operator_mods
+            #prefix_parser
+            #infix_parser
+
+            pub fn parse<'a, TT: ParseTracer>(
+                state: ParseState<'a #input_type_param>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, #user_defined_type>,
+                min_binding_power: u32,
+            ) -> ParseResult<'a, #rule_type #input_type_param> {
+                let ParseOk { result: mut left, mut state } = ChoiceHelper::new(state)
+                    #prefix_choice
+                    .choice(|state| #operand_call)
+                    .end()?;
+                #infix_loop
+                Ok(ParseOk { result: left, state })
+            }
+        ))
+    }
+}
@@ -7,11 +7,14 @@ use proc_macro2::{Ident, TokenStream};
 use quote::{format_ident, quote};
 
 use super::common::{
-    generate_derives, generate_enum_type, generate_field_type, generate_rule_parse_function,
-    safe_ident, Arity, Codegen, CodegenRule, CodegenSettings, FieldDescriptor, PublicType,
-    RecordPosition,
+    generate_derives, generate_enum_type, generate_field_type, generate_lifetime_params,
+    generate_parsed_type, generate_rule_parse_function, generate_rule_type,
+    generate_shift_positions_impl, generate_skip_ws, grammar_recovers, safe_ident,
+    type_borrows_input, Arity, CloneState, Codegen, CodegenRule, CodegenSettings, FieldDescriptor,
+    PublicType, RecordPosition,
 };
-use crate::grammar::{DirectiveExpression, Grammar, Rule};
+use super::keyword::generate_not_keyword_check;
+use crate::grammar::{DirectiveExpression, Grammar, NameDirective, RecoverDirective, Rule};
 
 impl CodegenRule for Rule {
     fn generate_code(
@@ -31,29 +34,87 @@ impl CodegenRule for Rule {
 
         let name = &self.name;
         let rule_mod = self.rule_module_ident();
-        let rule_type = safe_ident(&self.name);
+        let rule_type = generate_rule_type(&self.name, grammar, &settings);
         let parser_name = format_ident!("parse_{name}");
         let choice_body = self.definition.generate_code(&fields, grammar, &settings)?;
 
         let (types, inner_decls, parse_body) = if flags.string {
             self.generate_string_rule(&settings)?
         } else if fields.len() == 1 && fields[0].name == "_override" {
-            self.generate_override_rule(&fields, &settings)?
+            self.generate_override_rule(&fields, grammar, &settings)?
         } else {
             self.generate_normal_rule(&fields, grammar, &settings, flags.position.into())?
         };
 
-        let rule_parser_call = self.generate_memoized_body(parse_body);
+        // The body may return early with '?', so it's wrapped in a closure to make sure the
+        // memoization, recovery and tracing code is always run.
+        // Cuts (`~`) do not have an effect outside the rule.
+        let input_type_param = settings.input_type_param();
         let parse_body = quote!(
-            global.tracer.print_trace_start(&state, #name);
-            let result = { #rule_parser_call };
-            global.tracer.print_trace_result(&result);
-            result
+            (|| -> ParseResult<'a, #rule_type #input_type_param> { #parse_body })()
+                .map_err(ParseFailure::uncommit)
         );
+        let rule_parser_call = self.generate_memoized_body(parse_body, grammar);
+        let not_keyword_check = if flags.not_keyword {
+            generate_not_keyword_check(grammar, &settings)?
+        } else {
+            quote!()
+        };
+        let naming = self.generate_naming()?;
+        let (recovery_start, recovery) = self.generate_recovery(&settings);
+        // The errors recorded before the rule are taken out, so that the errors of the rule
+        // can be modified separately, and the memoized results do not depend on the errors
+        // before them. Merging them back is expensive, so other rules only do this when the
+        // context is recorded.
+        let separate_errors = flags.memoize
+            || flags.left_recursive
+            || self.name_directives().next().is_some()
+            || flags.recover;
+        let parse_body = if separate_errors {
+            quote!(
+                global.tracer.print_trace_start(&state, #name);
+                let (state, outer_error) = state.take_farthest_error();
+                let rule_start_state = state.clone();
+                #recovery_start
+                let result = { #rule_parser_call };
+                #not_keyword_check
+                #naming
+                let result = if global.record_context {
+                    result.push_error_context(#name)
+                } else {
+                    result
+                };
+                #recovery
+                let result = rule_start_state.restore_farthest_error(result, outer_error);
+                global.tracer.print_trace_result(&result);
+                result
+            )
+        } else {
+            let rule_start_state = if flags.not_keyword {
+                quote!(let rule_start_state = state.clone();)
+            } else {
+                quote!()
+            };
+            quote!(
+                global.tracer.print_trace_start(&state, #name);
+                let result = state.parse_rule(#name, global.record_context, |state| {
+                    #rule_start_state
+                    let result = { #rule_parser_call };
+                    #not_keyword_check
+                    result
+                });
+                global.tracer.print_trace_result(&result);
+                result
+            )
+        };
 
-        let parse_function =
-            generate_rule_parse_function(parser_name, rule_type, parse_body, &settings);
-        let position_impls = self.generate_impl_position(&fields);
+        let parse_function = generate_rule_parse_function(
+            parser_name,
+            generate_parsed_type(&self.name, grammar, &settings),
+            parse_body,
+            &settings,
+        );
+        let position_impls = self.generate_impl_position(&fields, grammar, &settings);
 
         Ok((
             types,
@@ -78,6 +139,8 @@ pub struct RuleFlags {
     pub position: bool,
     pub memoize: bool,
     pub left_recursive: bool,
+    pub not_keyword: bool,
+    pub recover: bool,
 }
 
 impl Rule {
@@ -91,7 +154,10 @@ impl Rule {
                 DirectiveExpression::PositionDirective(_) => result.position = true,
                 DirectiveExpression::MemoizeDirective(_) => result.memoize = true,
                 DirectiveExpression::LeftrecDirective(_) => result.left_recursive = true,
+                DirectiveExpression::NotKeywordDirective(_) => result.not_keyword = true,
                 DirectiveExpression::CheckDirective(_) => (),
+                DirectiveExpression::RecoverDirective(_) => result.recover = true,
+                DirectiveExpression::NameDirective(_) => (),
             }
         }
         result
@@ -101,12 +167,24 @@ impl Rule {
         if flags.export && flags.string {
             bail!("@string rules cannot be @export-ed");
         }
+        if flags.export && flags.recover {
+            bail!("@recover rules cannot be @export-ed, the errors of exported rules are returned by parse_with_recovery");
+        }
         if self.name == "Whitespace" && !flags.no_skip_ws {
             bail!("The 'Whitespace' rule (and all called rules) must be @no_skip_ws to prevent recursion");
         }
         if flags.memoize && !settings.derives.contains(&"Clone".into()) {
             bail!("@memoize can only be used if 'Clone' is in the derives set");
         }
+        if self.recover_directives().count() > 1 {
+            bail!("Only one @recover directive can be used on a rule");
+        }
+        if self.name_directives().count() > 1 {
+            bail!("Only one @name directive can be used on a rule");
+        }
+        if flags.not_keyword && settings.skip_whitespace {
+            bail!("@not_keyword rules must be @no_skip_ws, so that the matched text can be compared to the keywords");
+        }
         Ok(())
     }
 
@@ -118,17 +196,30 @@ impl Rule {
         let rule_type_ident = safe_ident(&self.name);
         let check_calls = self.generate_check_calls()?;
         let flags = self.flags();
+        let string_type = settings.string_type();
+        let lifetime_params = generate_lifetime_params(settings.borrowed_strings);
         let type_decl = if flags.position {
             let derives = generate_derives(settings);
+            let shift_positions_impl = generate_shift_positions_impl(
+                &rule_type_ident,
+                quote!(self.position.shift_positions(offset);),
+                settings,
+            );
             quote!(
                 #derives
-                pub struct #rule_type_ident {
-                    pub string: String,
+                pub struct #rule_type_ident #lifetime_params {
+                    pub string: #string_type,
                     pub position: std::ops::Range<usize>
                 }
+                #shift_positions_impl
             )
         } else {
-            quote!(pub type #rule_type_ident = String;)
+            quote!(pub type #rule_type_ident #lifetime_params = #string_type;)
+        };
+        let to_owned = if settings.borrowed_strings {
+            quote!()
+        } else {
+            quote!(.to_owned())
         };
         let return_value = if flags.position {
             quote!(#rule_type_ident {
@@ -146,7 +237,7 @@ impl Rule {
                     #rule_mod::parse(state.clone(), global)?
                     .map_with_state(
                         |_, new_state| {
-                            let string = state.slice_until(new_state).to_string();
+                            let string = state.slice_until(new_state) #to_owned;
                             #return_value
                         }
                     );
@@ -158,6 +249,7 @@ impl Rule {
     fn generate_override_rule(
         &self,
         fields: &[FieldDescriptor],
+        grammar: &Grammar,
         settings: &CodegenSettings,
     ) -> Result<(TokenStream, TokenStream, TokenStream)> {
         let field = &fields[0];
@@ -169,27 +261,30 @@ impl Rule {
             if flags.position {
                 bail!("Simply overridden (containing '@:') rules cannot contain @position. Try the > operator instead.");
             }
-            self.generate_override_rule_simple(field, settings)
+            self.generate_override_rule_simple(field, grammar, settings)
         } else {
             if field.arity != Arity::One {
                 bail!("Enum '@:' fields have to be used exactly once in all choice branches, and must not be used in closures or optional parts.");
             }
-            self.generate_override_rule_enum(field, settings)
+            self.generate_override_rule_enum(field, grammar, settings)
         }
     }
 
     fn generate_override_rule_simple(
         &self,
         field: &FieldDescriptor,
+        grammar: &Grammar,
         settings: &CodegenSettings,
     ) -> Result<(TokenStream, TokenStream, TokenStream)> {
         let rule_mod = self.rule_module_ident();
         let rule_type = safe_ident(&self.name);
-        let override_type = generate_field_type(&self.name, field, settings);
+        let lifetime_params =
+            generate_lifetime_params(type_borrows_input(&self.name, grammar, settings));
+        let override_type = generate_field_type(&self.name, field, grammar, settings);
         let check_calls = self.generate_check_calls()?;
         Ok((
             quote!(
-                pub type #rule_type = #override_type;
+                pub type #rule_type #lifetime_params = #override_type;
             ),
             quote!(
                 use super::#rule_type as Parsed__override;
@@ -205,11 +300,12 @@ impl Rule {
     fn generate_override_rule_enum(
         &self,
         field: &FieldDescriptor,
+        grammar: &Grammar,
         settings: &CodegenSettings,
     ) -> Result<(TokenStream, TokenStream, TokenStream)> {
         let rule_mod = self.rule_module_ident();
         let rule_type = safe_ident(&self.name);
-        let enum_type = generate_enum_type(&self.name, field, settings);
+        let enum_type = generate_enum_type(&self.name, field, grammar, settings);
         let check_calls = self.generate_check_calls()?;
         Ok((
             quote!(
@@ -238,7 +334,7 @@ impl Rule {
         let parsed_enum_types: TokenStream = fields
             .iter()
             .filter(|f| f.type_names.len() > 1)
-            .map(|f| generate_enum_type(&format!("{}_{}", self.name, f.name), f, settings))
+            .map(|f| generate_enum_type(&format!("{}_{}", self.name, f.name), f, grammar, settings))
             .collect();
         let parsed_struct_type = self.definition.generate_struct_type(
             fields,
@@ -318,23 +414,104 @@ impl Rule {
 
         Ok(quote!(
             #(
-                if !#check_idents(&result.result) {
+                if let Err(message) = CheckResult::into_check_result(#check_idents(&result.result)) {
                     return Err(result.state.report_error(
-                        ParseErrorSpecifics::CheckFunctionFailed{function_name: #check_names}
+                        ParseErrorSpecifics::CheckFunctionFailed{function_name: #check_names, message}
                     ));
                 }
             )*
         ))
     }
 
-    fn generate_impl_position(&self, fields: &[FieldDescriptor]) -> TokenStream {
-        let rule_type = safe_ident(&self.name);
+    fn recover_directives(&self) -> impl Iterator<Item = &RecoverDirective> {
+        self.directives.iter().filter_map(|d| {
+            if let DirectiveExpression::RecoverDirective(r) = d {
+                Some(r)
+            } else {
+                None
+            }
+        })
+    }
+
+    /// The code recording the number of recovered errors at the start of the rule, and the code
+    /// recovering from the errors of the rule.
+    fn generate_recovery(&self, settings: &CodegenSettings) -> (TokenStream, TokenStream) {
+        if let Some(recover_directive) = self.recover_directives().next() {
+            let sync_call = generate_skip_ws(
+                settings,
+                &format!("parse_{}", recover_directive.sync),
+                quote!(global),
+                CloneState::No,
+            );
+            // Failures at the first token of the rule are normal during backtracking (e.g. at
+            // the end of a closure), so only the errors after it are recovered from.
+            let content_start = if settings.skip_whitespace {
+                quote!(parse_Whitespace(rule_start_state.clone(), &mut *global)
+                    .map_or(rule_start_state.position(), |ok| ok.state.position()))
+            } else {
+                quote!(rule_start_state.position())
+            };
+            (
+                quote!(let recovered_errors = global.recovered_errors.len();),
+                quote!(
+                    let result = match result {
+                        Err(err) if global.recover && err.position() > #content_start => recover_from_error(
+                            rule_start_state.clone(),
+                            recovered_errors,
+                            err,
+                            global,
+                            |state, global| #sync_call,
+                        ),
+                        result => result.map_inner(Ok),
+                    };
+                ),
+            )
+        } else {
+            (TokenStream::new(), TokenStream::new())
+        }
+    }
+
+    fn name_directives(&self) -> impl Iterator<Item = &NameDirective> {
+        self.directives.iter().filter_map(|d| {
+            if let DirectiveExpression::NameDirective(n) = d {
+                Some(n)
+            } else {
+                None
+            }
+        })
+    }
+
+    fn generate_naming(&self) -> Result<TokenStream> {
+        if let Some(name_directive) = self.name_directives().next() {
+            if name_directive.name.insensitive.is_some() {
+                bail!("The @name directive does not accept case insensitive strings");
+            }
+            let display_name = name_directive.name.value()?;
+            Ok(quote!(
+                let result = result.map_farthest_error(
+                    |err| err.name_at(rule_start_state.position(), #display_name)
+                );
+            ))
+        } else {
+            Ok(TokenStream::new())
+        }
+    }
+
+    fn generate_impl_position(
+        &self,
+        fields: &[FieldDescriptor],
+        grammar: &Grammar,
+        settings: &CodegenSettings,
+    ) -> TokenStream {
+        let rule_type = generate_rule_type(&self.name, grammar, settings);
+        let lifetime_params =
+            generate_lifetime_params(type_borrows_input(&self.name, grammar, settings));
         if self.flags().position {
             if fields.len() == 1 && fields[0].name == "_override" && fields[0].type_names.len() > 1
             {
                 let cases = fields[0].type_names.iter().map(safe_ident);
                 quote!(
-                    impl PegPosition for #rule_type {
+                    impl #lifetime_params PegPosition for #rule_type {
                         fn position(&self) -> &std::ops::Range<usize> {
                             match self{
                                 #(Self::#cases(x) => x.position(),)*
@@ -344,7 +521,7 @@ impl Rule {
                 )
             } else {
                 quote!(
-                    impl PegPosition for #rule_type {
+                    impl #lifetime_params PegPosition for #rule_type {
                         fn position(&self) -> &std::ops::Range<usize> {
                             &self.position
                         }
@@ -356,28 +533,60 @@ impl Rule {
         }
     }
 
-    fn generate_memoized_body(&self, parse_body: TokenStream) -> TokenStream {
+    fn generate_memoized_body(&self, parse_body: TokenStream, grammar: &Grammar) -> TokenStream {
         let flags = self.flags();
+        let name = &self.name;
         let cache_entry_ident = format_ident!("c_{}", self.name);
+        // The errors recovered from inside the rule are recorded again on cache hits, and the
+        // ones of discarded left recursive iterations are forgotten.
+        let (cached_result, save_recovered_errors, memoize_recovered_errors) =
+            if grammar_recovers(grammar) {
+                (
+                    quote!({
+                        let result = cached.clone();
+                        global.replay_recovered_errors(#name, cache_key);
+                        result
+                    }),
+                    quote!(let recovered_errors = global.recovered_errors.len();),
+                    quote!(global.memoize_recovered_errors(#name, cache_key, recovered_errors);),
+                )
+            } else {
+                (quote!(cached.clone()), quote!(), quote!())
+            };
+        let (save_iteration_errors, discard_iteration_errors) = if grammar_recovers(grammar) {
+            (
+                quote!(let iteration_errors = global.recovered_errors.len();),
+                quote!(global.recovered_errors.truncate(iteration_errors);),
+            )
+        } else {
+            (quote!(), quote!())
+        };
         if flags.left_recursive {
             quote!(
                 let cache_key = state.cache_key();
                 if let Some(cached) = global.cache.#cache_entry_ident.get(&cache_key) {
                     global.tracer.print_informative("Cache hit (left recursive)");
-                    cached.clone()
+                    #cached_result
                 } else {
+                    let state = state.reset_examined();
                     let mut best_result = Err(state.clone().report_error(ParseErrorSpecifics::LeftRecursionSentinel));
                     global.cache.#cache_entry_ident.insert(cache_key, best_result.clone());
+                    // The last, unsuccessful iteration may have looked further than the best one.
+                    let mut examined_until = state.examined_until();
+                    #save_recovered_errors
                     loop {
                         global.tracer.print_informative("Starting new left recursive loop");
                         let state = state.clone();
+                        #save_iteration_errors
                         let new_result = { #parse_body };
+                        examined_until = examined_until.max(new_result.examined_until());
                         match (new_result, &best_result) {
                             (Ok(nro), Ok(bro)) => {
                                 if nro.state.is_further_than(&bro.state) {
                                     best_result = Ok(nro);
                                     global.cache.#cache_entry_ident.insert(cache_key, best_result.clone());
                                 } else {
+                                    #discard_iteration_errors
                                     break;
                                 }
                             }
@@ -386,6 +595,7 @@ impl Rule {
                                 global.cache.#cache_entry_ident.insert(cache_key, best_result.clone());
                             }
                             (Err(nre), Ok(bro)) => {
+                                #discard_iteration_errors
                                 break;
                             }
                             (Err(nre), Err(bre)) => {
@@ -395,6 +605,9 @@ impl Rule {
                             }
                         }
                     }
+                    let best_result = best_result.merge_examined(examined_until);
+                    #memoize_recovered_errors
+                    global.cache.#cache_entry_ident.insert(cache_key, best_result.clone());
                     best_result
                 }
             )
@@ -403,9 +616,12 @@ impl Rule {
                 let cache_key = state.cache_key();
                 if let Some(cached) = global.cache.#cache_entry_ident.get(&cache_key) {
                     global.tracer.print_informative("Cache hit");
-                    cached.clone()
+                    #cached_result
                 } else {
+                    let state = state.reset_examined();
+                    #save_recovered_errors
                     let result = { #parse_body };
+                    #memoize_recovered_errors
                     global.cache.#cache_entry_ident.insert(cache_key, result.clone());
                     result
                 }
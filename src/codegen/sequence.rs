@@ -12,7 +12,7 @@ use super::common::{
     generate_inner_parse_function, safe_ident, Arity, CloneState, Codegen, CodegenSettings,
     FieldDescriptor,
 };
-use crate::grammar::{Grammar, Sequence};
+use crate::grammar::{DelimitedExpression, Grammar, Sequence};
 
 impl Codegen for Sequence {
     fn generate_code_spec(
@@ -24,6 +24,7 @@ impl Codegen for Sequence {
         if self.parts.is_empty() {
             return Ok(generate_inner_parse_function(
                 quote!(Ok(ParseOk { result: (), state })),
+                false,
                 settings,
             ));
         }
@@ -105,7 +106,12 @@ impl Sequence {
         let fields = self.get_filtered_rule_fields(rule_fields, grammar)?;
         let mut calls = TokenStream::new();
         let mut fields_seen = HashSet::<&str>::new();
+        let mut after_cut = false;
         for (num, part) in self.parts.iter().enumerate() {
+            if let DelimitedExpression::Cut(_) = part {
+                after_cut = true;
+                continue;
+            }
             let inner_fields = part.get_filtered_rule_fields(rule_fields, grammar)?;
             let part_mod = format_ident!("part_{num}");
             let parse_call = if let Some(inline_body) =
@@ -115,6 +121,11 @@ impl Sequence {
             } else {
                 quote!(#part_mod::parse(state, global))
             };
+            let parse_call = if after_cut {
+                quote!(#parse_call.map_err(ParseFailure::commit))
+            } else {
+                parse_call
+            };
             let call = if inner_fields.is_empty() {
                 quote!(
                     let ParseOk{state, ..} = #parse_call?;
@@ -178,6 +189,10 @@ impl Sequence {
             #calls
             Ok(ParseOk{result:#parse_result, state})
         );
-        Ok(generate_inner_parse_function(parse_body, settings))
+        Ok(generate_inner_parse_function(
+            parse_body,
+            self.parsed_type_borrows_input(rule_fields, grammar, settings)?,
+            settings,
+        ))
     }
 }
@@ -3,10 +3,14 @@
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
 use anyhow::{anyhow, bail, Result};
-use proc_macro2::TokenStream;
+use proc_macro2::{Literal, TokenStream};
 use quote::quote;
+use regex_syntax::hir::{ClassUnicode, ClassUnicodeRange};
 
-use super::common::{generate_skip_ws, CloneState, Codegen, CodegenSettings, FieldDescriptor};
+use super::common::{
+    generate_skip_ws, safe_ident, CloneState, Codegen, CodegenSettings, FieldDescriptor,
+};
+use super::keyword::generate_keyword_boundary_check;
 use crate::grammar::{
     CharacterRange, Grammar, HexaEscape, SimpleEscape, StringItem, StringLiteral, Utf8Escape,
 };
@@ -49,22 +53,10 @@ impl TryFrom<&Utf8Escape> for char {
     type Error = anyhow::Error;
 
     fn try_from(value: &Utf8Escape) -> Result<Self, Self::Error> {
-        let mut result: u32 = value.c1.to_digit(16).unwrap();
-        if let Some(v) = &value.c2 {
-            result = result * 16 + v.to_digit(16).unwrap();
-        };
-        if let Some(v) = &value.c3 {
-            result = result * 16 + v.to_digit(16).unwrap();
-        };
-        if let Some(v) = &value.c4 {
-            result = result * 16 + v.to_digit(16).unwrap();
-        };
-        if let Some(v) = &value.c5 {
-            result = result * 16 + v.to_digit(16).unwrap();
-        };
-        if let Some(v) = &value.c6 {
-            result = result * 16 + v.to_digit(16).unwrap();
-        };
+        let result = value
+            .digits
+            .iter()
+            .fold(0, |result, digit| result * 16 + digit.to_digit(16).unwrap());
         char::from_u32(result).ok_or_else(|| anyhow!("Invalid utf-8 codepoint {result:#x}"))
     }
 }
@@ -79,61 +71,164 @@ impl Codegen for CharacterRange {
     ) -> Result<Option<TokenStream>> {
         let from: char = (&self.from).try_into()?;
         let to: char = (&self.to).try_into()?;
-        let parse_call = generate_skip_ws(
-            settings,
-            "parse_character_range",
-            quote!(#from, #to),
-            clone_state,
-        );
+        let parse_call = if settings.byte_input {
+            let from = byte_literal(from)?;
+            let to = byte_literal(to)?;
+            generate_skip_ws(
+                settings,
+                "parse_byte_range",
+                quote!(#from, #to),
+                clone_state,
+            )
+        } else {
+            generate_skip_ws(
+                settings,
+                "parse_character_range",
+                quote!(#from, #to),
+                clone_state,
+            )
+        };
         Ok(Some(quote!(#parse_call .discard_result())))
     }
 
-    fn get_fields(&self, _grammar: &Grammar) -> Result<Vec<FieldDescriptor>> {
+    fn get_fields(&self, _grammar: &Grammar) -> Result<Vec<FieldDescriptor<'_>>> {
         Ok(Vec::new())
     }
 }
 
-impl Codegen for StringLiteral {
-    fn generate_inline_body(
-        &self,
-        _rule_fields: &[FieldDescriptor],
-        _grammar: &Grammar,
-        settings: &CodegenSettings,
-        clone_state: CloneState,
-    ) -> Result<Option<TokenStream>> {
-        let parser_name;
-        let additional_params;
-        let literal = &self
-            .body
+impl StringLiteral {
+    /// The literal string value, with all escapes resolved.
+    pub fn value(&self) -> Result<String> {
+        self.body
             .iter()
             .map(|item| -> Result<char> { item.try_into() })
-            .collect::<Result<String>>()?;
-        if self.insensitive.is_some() {
+            .collect()
+    }
+
+    /// The literal value as bytes, for byte input.
+    pub fn byte_value(&self) -> Result<Vec<u8>> {
+        self.value()?.chars().map(char_to_byte).collect()
+    }
+
+    /// The runtime parse function and its additional parameters for byte input.
+    fn byte_parser_and_params(&self) -> Result<(&'static str, TokenStream)> {
+        let mut literal = self.byte_value()?;
+        let insensitive = self.insensitive.is_some();
+        if insensitive {
             if !literal.is_ascii() {
-                bail!("Case insensitive matching only works for ascii strings. ({literal:?} was not ascii)");
+                bail!(
+                    "Case insensitive matching only works for ascii strings. (b\"{}\" was not ascii)",
+                    literal.escape_ascii()
+                );
+            }
+            literal.make_ascii_lowercase();
+        }
+        Ok(if literal.len() == 1 {
+            let byte_literal = Literal::u8_suffixed(literal[0]);
+            if insensitive {
+                ("parse_byte_literal_insensitive", quote!(#byte_literal))
+            } else {
+                ("parse_byte_literal", quote!(#byte_literal))
             }
+        } else {
+            let byte_string_literal = Literal::byte_string(&literal);
+            if insensitive {
+                (
+                    "parse_byte_string_literal_insensitive",
+                    quote!(#byte_string_literal),
+                )
+            } else {
+                ("parse_byte_string_literal", quote!(#byte_string_literal))
+            }
+        })
+    }
+
+    /// The runtime parse function and its additional parameters for string input.
+    fn str_parser_and_params(&self) -> Result<(&'static str, TokenStream)> {
+        let literal = &self.value()?;
+        Ok(if self.insensitive.is_some() && !literal.is_ascii() {
+            let variants = literal.chars().map(|c| {
+                let variants = case_fold_variants(c);
+                quote!(&[#(#variants),*])
+            });
+            let literal = literal.to_lowercase();
+            (
+                "parse_string_literal_case_folded",
+                quote!(#literal, &[#(#variants),*]),
+            )
+        } else if self.insensitive.is_some() {
             let literal = literal.to_ascii_lowercase();
             if literal.chars().count() == 1 {
                 let char_literal = literal.chars().next().unwrap();
-                parser_name = "parse_character_literal_insensitive";
-                additional_params = quote!(#char_literal);
+                ("parse_character_literal_insensitive", quote!(#char_literal))
             } else {
-                parser_name = "parse_string_literal_insensitive";
-                additional_params = quote!(#literal);
+                ("parse_string_literal_insensitive", quote!(#literal))
             }
         } else if literal.chars().count() == 1 {
             let char_literal = literal.chars().next().unwrap();
-            parser_name = "parse_character_literal";
-            additional_params = quote!(#char_literal);
+            ("parse_character_literal", quote!(#char_literal))
+        } else {
+            ("parse_string_literal", quote!(#literal))
+        })
+    }
+}
+
+/// All characters that are equal to `c` with Unicode simple case folding, including `c`.
+fn case_fold_variants(c: char) -> Vec<char> {
+    let mut class = ClassUnicode::new([ClassUnicodeRange::new(c, c)]);
+    class.case_fold_simple();
+    class
+        .iter()
+        .flat_map(|range| range.start()..=range.end())
+        .collect()
+}
+
+/// Characters are treated as single bytes in byte input mode, so only characters up to `'\xff'`
+/// can be used.
+fn char_to_byte(c: char) -> Result<u8> {
+    u8::try_from(c).map_err(|_| anyhow!("Character {c:?} does not fit in a byte (byte input mode)"))
+}
+
+pub fn byte_literal(c: char) -> Result<Literal> {
+    Ok(Literal::u8_suffixed(char_to_byte(c)?))
+}
+
+impl Codegen for StringLiteral {
+    fn generate_inline_body(
+        &self,
+        _rule_fields: &[FieldDescriptor],
+        grammar: &Grammar,
+        settings: &CodegenSettings,
+        clone_state: CloneState,
+    ) -> Result<Option<TokenStream>> {
+        let (parser_name, additional_params) = if settings.byte_input {
+            self.byte_parser_and_params()?
+        } else {
+            self.str_parser_and_params()?
+        };
+        let parse_call = if self.keyword.is_some() {
+            // The literal and the boundary check are fused into a single call, so that the
+            // keyword is reported as a whole in the error messages.
+            let parser_name = safe_ident(parser_name);
+            let keyword = self.keyword_value()?;
+            let boundary_check = generate_keyword_boundary_check(grammar)?;
+            generate_skip_ws(
+                settings,
+                "parse_keyword",
+                quote!(
+                    #keyword,
+                    |state| #parser_name(state, #additional_params),
+                    #boundary_check
+                ),
+                clone_state,
+            )
         } else {
-            parser_name = "parse_string_literal";
-            additional_params = quote!(#literal);
+            generate_skip_ws(settings, parser_name, additional_params, clone_state)
         };
-        let parse_call = generate_skip_ws(settings, parser_name, additional_params, clone_state);
         Ok(Some(quote!(#parse_call .discard_result())))
     }
 
-    fn get_fields(&self, _grammar: &Grammar) -> Result<Vec<FieldDescriptor>> {
+    fn get_fields(&self, _grammar: &Grammar) -> Result<Vec<FieldDescriptor<'_>>> {
         Ok(Vec::new())
     }
 }
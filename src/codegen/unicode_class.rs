@@ -0,0 +1,73 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use anyhow::{bail, Result};
+use proc_macro2::TokenStream;
+use quote::quote;
+use regex_syntax::hir::{Class, HirKind};
+
+use super::common::{generate_skip_ws, CloneState, Codegen, CodegenSettings, FieldDescriptor};
+use crate::grammar::{Grammar, UnicodeClass};
+
+impl UnicodeClass {
+    /// The class as written in the grammar, e.g. `\p{L}`
+    fn display_name(&self) -> String {
+        let letter = if self.negated.is_some() { 'P' } else { 'p' };
+        format!("\\{letter}{{{}}}", self.name)
+    }
+
+    /// The characters in the class, as a sorted list of non-overlapping inclusive ranges.
+    fn ranges(&self) -> Result<Vec<(char, char)>> {
+        let name = self.display_name();
+        let hir = match regex_syntax::parse(&name) {
+            Ok(hir) => hir,
+            Err(_) => bail!("Unknown Unicode property class {name}"),
+        };
+        match hir.kind() {
+            HirKind::Class(Class::Unicode(class)) => Ok(class
+                .ranges()
+                .iter()
+                .map(|range| (range.start(), range.end()))
+                .collect()),
+            _ => bail!("Unknown Unicode property class {name}"),
+        }
+    }
+
+    /// The parameters of `parse_character_class` for this class.
+    fn generate_parse_params(&self, settings: &CodegenSettings) -> Result<TokenStream> {
+        if settings.byte_input {
+            bail!("Unicode property classes cannot be used with byte input");
+        }
+        let (from, to): (Vec<char>, Vec<char>) = self.ranges()?.into_iter().unzip();
+        let name = self.display_name();
+        Ok(quote!(&[#((#from, #to)),*], #name))
+    }
+
+    pub fn generate_parse_call(&self, settings: &CodegenSettings) -> Result<TokenStream> {
+        let params = self.generate_parse_params(settings)?;
+        Ok(quote!(parse_character_class(state.clone(), #params)))
+    }
+}
+
+impl Codegen for UnicodeClass {
+    fn generate_inline_body(
+        &self,
+        _rule_fields: &[FieldDescriptor],
+        _grammar: &Grammar,
+        settings: &CodegenSettings,
+        clone_state: CloneState,
+    ) -> Result<Option<TokenStream>> {
+        let parse_call = generate_skip_ws(
+            settings,
+            "parse_character_class",
+            self.generate_parse_params(settings)?,
+            clone_state,
+        );
+        Ok(Some(quote!(#parse_call .discard_result())))
+    }
+
+    fn get_fields(&self, _grammar: &Grammar) -> Result<Vec<FieldDescriptor<'_>>> {
+        Ok(Vec::new())
+    }
+}
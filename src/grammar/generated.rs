@@ -1,25 +1,32 @@
 // This file was generated by Peginator v0.4.0
-// Hash of the grammar file: FC57BC845451F8899B2E3289C13E9C5DB38C429C738452897008A83565E9A871
+// Hash of the grammar file: E689D17D6B04D6FD4846A6A56DA4F76367CBFBA36AF5FEB08416D999AD80BA12
 // Any changes to it will be lost on regeneration
 
 #[derive(Debug, Clone)]
 pub struct Grammar {
     pub rules: Vec<Grammar_rules>,
+    pub directives: Vec<GrammarDirective>,
 }
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone)]
 pub enum Grammar_rules {
     CharRule(CharRule),
     ExternRule(ExternRule),
+    PrecedenceRule(PrecedenceRule),
     Rule(Rule),
 }
 #[derive(Debug, Clone)]
 pub struct Rule {
     pub directives: Vec<DirectiveExpression>,
     pub name: Identifier,
+    pub parameters: Option<RuleParameters>,
     pub definition: Choice,
 }
 #[derive(Debug, Clone)]
+pub struct RuleParameters {
+    pub names: Vec<Identifier>,
+}
+#[derive(Debug, Clone)]
 pub struct CharRule {
     pub directives: Vec<CheckDirective>,
     pub name: Identifier,
@@ -31,6 +38,7 @@ pub enum CharRulePart {
     CharRangePart(CharRangePart),
     CharacterRange(CharacterRange),
     Identifier(Identifier),
+    UnicodeClass(UnicodeClass),
 }
 #[derive(Debug, Clone)]
 pub struct ExternRule {
@@ -38,6 +46,45 @@ pub struct ExternRule {
     pub name: Identifier,
 }
 #[derive(Debug, Clone)]
+pub struct PrecedenceRule {
+    pub directives: Vec<PrecedenceRuleDirective>,
+    pub directive: PrecedenceDirective,
+    pub name: Identifier,
+    pub levels: Vec<PrecedenceLevel>,
+}
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone)]
+pub enum PrecedenceRuleDirective {
+    ExportDirective(ExportDirective),
+    NoSkipWsDirective(NoSkipWsDirective),
+}
+#[derive(Debug, Clone)]
+pub struct PrecedenceLevel {
+    pub kind: PrecedenceLevelKind,
+    pub operators: Vec<PrecedenceOperator>,
+}
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone)]
+pub enum PrecedenceLevelKind {
+    LeftAssociative(LeftAssociative),
+    PostfixOperators(PostfixOperators),
+    PrefixOperators(PrefixOperators),
+    RightAssociative(RightAssociative),
+}
+#[derive(Debug, Clone)]
+pub struct LeftAssociative;
+#[derive(Debug, Clone)]
+pub struct RightAssociative;
+#[derive(Debug, Clone)]
+pub struct PrefixOperators;
+#[derive(Debug, Clone)]
+pub struct PostfixOperators;
+#[derive(Debug, Clone)]
+pub struct PrecedenceOperator {
+    pub name: Identifier,
+    pub body: Sequence,
+}
+#[derive(Debug, Clone)]
 pub struct Choice {
     pub choices: Vec<Sequence>,
 }
@@ -56,11 +103,30 @@ pub struct Optional {
 #[derive(Debug, Clone)]
 pub struct Closure {
     pub body: Choice,
+    pub separator: Option<ClosureSeparator>,
     pub at_least_one: Option<AtLeastOneMarker>,
+    pub bounds: Option<RepetitionBounds>,
 }
 #[derive(Debug, Clone)]
 pub struct AtLeastOneMarker;
 #[derive(Debug, Clone)]
+pub struct RepetitionBounds {
+    pub min: RepetitionCount,
+    pub upper: Option<RepetitionUpperBound>,
+}
+#[derive(Debug, Clone)]
+pub struct RepetitionUpperBound {
+    pub max: Option<RepetitionCount>,
+}
+pub type RepetitionCount = String;
+#[derive(Debug, Clone)]
+pub struct ClosureSeparator {
+    pub trailing: Option<TrailingSeparatorMarker>,
+    pub body: Choice,
+}
+#[derive(Debug, Clone)]
+pub struct TrailingSeparatorMarker;
+#[derive(Debug, Clone)]
 pub struct NegativeLookahead {
     pub expr: Box<DelimitedExpression>,
 }
@@ -73,35 +139,59 @@ pub struct CharacterRange {
     pub from: CharRangePart,
     pub to: CharRangePart,
 }
+#[derive(Debug, Clone)]
+pub struct UnicodeClass {
+    pub negated: Option<NegatedClassMarker>,
+    pub name: UnicodePropertyName,
+}
+#[derive(Debug, Clone)]
+pub struct NegatedClassMarker;
+pub type UnicodePropertyName = String;
 pub type CharRangePart = StringItem;
 #[derive(Debug, Clone)]
 pub struct StringLiteral {
     pub insensitive: Option<CaseInsensitiveMarker>,
+    pub keyword: Option<KeywordMarker>,
     pub body: Vec<StringItem>,
 }
 #[derive(Debug, Clone)]
 pub struct CaseInsensitiveMarker;
 #[derive(Debug, Clone)]
+pub struct KeywordMarker;
+#[derive(Debug, Clone)]
 pub struct Field {
     pub name: Option<Identifier>,
     pub boxed: Option<BoxMarker>,
     pub typ: Identifier,
+    pub type_arguments: Option<TypeArguments>,
 }
 #[derive(Debug, Clone)]
 pub struct BoxMarker;
 #[derive(Debug, Clone)]
 pub struct OverrideField {
     pub typ: Identifier,
+    pub type_arguments: Option<TypeArguments>,
 }
 #[derive(Debug, Clone)]
 pub struct IncludeRule {
     pub rule: Identifier,
+    pub type_arguments: Option<TypeArguments>,
+}
+#[derive(Debug, Clone)]
+pub struct TypeArguments {
+    pub arguments: Vec<TypeReference>,
+}
+#[derive(Debug, Clone)]
+pub struct TypeReference {
+    pub name: Identifier,
+    pub type_arguments: Option<Box<TypeArguments>>,
 }
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone)]
 pub enum DelimitedExpression {
     CharacterRange(CharacterRange),
     Closure(Closure),
+    Cut(Cut),
     EndOfInput(EndOfInput),
     Field(Field),
     Group(Group),
@@ -111,6 +201,7 @@ pub enum DelimitedExpression {
     OverrideField(OverrideField),
     PositiveLookahead(PositiveLookahead),
     StringLiteral(StringLiteral),
+    UnicodeClass(UnicodeClass),
 }
 pub type Identifier = String;
 pub type IdentifierChar = char;
@@ -152,12 +243,7 @@ pub struct HexaEscape {
 pub type HexChar = char;
 #[derive(Debug, Clone)]
 pub struct Utf8Escape {
-    pub c1: HexChar,
-    pub c2: Option<HexChar>,
-    pub c3: Option<HexChar>,
-    pub c4: Option<HexChar>,
-    pub c5: Option<HexChar>,
-    pub c6: Option<HexChar>,
+    pub digits: Vec<HexChar>,
 }
 #[allow(non_camel_case_types)]
 #[derive(Debug, Clone)]
@@ -166,8 +252,11 @@ pub enum DirectiveExpression {
     ExportDirective(ExportDirective),
     LeftrecDirective(LeftrecDirective),
     MemoizeDirective(MemoizeDirective),
+    NameDirective(NameDirective),
     NoSkipWsDirective(NoSkipWsDirective),
+    NotKeywordDirective(NotKeywordDirective),
     PositionDirective(PositionDirective),
+    RecoverDirective(RecoverDirective),
     StringDirective(StringDirective),
 }
 #[derive(Debug, Clone)]
@@ -189,6 +278,20 @@ pub struct CheckDirective {
     pub function: NamespacedRustName,
 }
 #[derive(Debug, Clone)]
+pub struct RecoverDirective {
+    pub sync: Identifier,
+}
+#[derive(Debug, Clone)]
+pub struct NameDirective {
+    pub name: StringLiteral,
+}
+#[derive(Debug, Clone)]
+pub struct NotKeywordDirective;
+#[derive(Debug, Clone)]
+pub struct PrecedenceDirective {
+    pub operand: Identifier,
+}
+#[derive(Debug, Clone)]
 pub struct ExternDirective {
     pub function: ExternDirective_function,
     pub return_type: Option<NamespacedRustName>,
@@ -199,29 +302,102 @@ pub enum ExternDirective_function {
     NamespacedRustName(NamespacedRustName),
     UserDefinedMethod(UserDefinedMethod),
 }
+#[allow(non_camel_case_types)]
+#[derive(Debug, Clone)]
+pub enum GrammarDirective {
+    ExtendsDirective(ExtendsDirective),
+    ImportDirective(ImportDirective),
+    KeywordBoundaryDirective(KeywordBoundaryDirective),
+}
+#[derive(Debug, Clone)]
+pub struct KeywordBoundaryDirective {
+    pub rule: Identifier,
+}
+#[derive(Debug, Clone)]
+pub struct ImportDirective {
+    pub path: StringLiteral,
+}
+#[derive(Debug, Clone)]
+pub struct ExtendsDirective {
+    pub path: StringLiteral,
+}
 pub type UserDefinedMethod = RustNamePart;
 pub type NamespacedRustName = Vec<RustNamePart>;
 pub type RustNamePart = String;
 #[derive(Debug, Clone)]
 pub struct EndOfInput;
 #[derive(Debug, Clone)]
+pub struct Cut;
+#[derive(Debug, Clone)]
 pub struct Whitespace;
 #[derive(Debug, Clone)]
 pub struct Comment;
-impl peginator_generated::PegParserAdvanced<()> for Grammar {
-    fn parse_advanced<TT: peginator_generated::ParseTracer>(
-        s: &str,
+impl<'a> peginator_generated::PegParserAdvanced<'a, ()> for Grammar {
+    fn parse_advanced_at<TT: peginator_generated::ParseTracer>(
+        s: &'a str,
+        offset: usize,
         settings: &peginator_generated::ParseSettings,
         user_defined: (),
-    ) -> Result<Self, peginator_generated::ParseError> {
-        Ok(peginator_generated::parse_Grammar(
-            peginator_generated::ParseState::new(s, settings),
+    ) -> Result<(Self, usize), peginator_generated::ParseError> {
+        let result = peginator_generated::parse_Grammar(
+            peginator_generated::ParseState::new_at(s, offset, settings),
             &mut peginator_generated::ParseGlobal::<TT, peginator_generated::ParseCache, ()>::new(
                 Default::default(),
                 user_defined,
+                settings,
             ),
-        )?
-        .result)
+        )
+        .map_err(|err| err.with_found(s))?;
+        Ok((result.result, result.state.position()))
+    }
+    fn parse_advanced_item<TT: peginator_generated::ParseTracer>(
+        s: &'a str,
+        base: usize,
+        settings: &peginator_generated::ParseSettings,
+        user_defined: (),
+    ) -> Option<Result<peginator_generated::ParsedItem<Self>, peginator_generated::ParseError>>
+    {
+        let mut global =
+            peginator_generated::ParseGlobal::<TT, peginator_generated::ParseCache, ()>::new(
+                Default::default(),
+                user_defined,
+                settings,
+            );
+        let state = peginator_generated::skip_item_whitespace(
+            peginator_generated::ParseState::new_with_base(s, base, settings),
+            &mut global,
+        );
+        if state.is_empty() {
+            return None;
+        }
+        let start = state.position();
+        Some(
+            peginator_generated::parse_Grammar(state, &mut global)
+                .map(|result| peginator_generated::ParsedItem {
+                    result: result.result,
+                    range: start..result.state.position(),
+                    examined_until: result.state.examined_until(),
+                })
+                .map_err(|err| err.with_found_in(s, base)),
+        )
+    }
+    fn parse_advanced_with_recovery<TT: peginator_generated::ParseTracer>(
+        s: &'a str,
+        settings: &peginator_generated::ParseSettings,
+        user_defined: (),
+    ) -> (Option<Self>, Vec<peginator_generated::ParseError>) {
+        let mut global =
+            peginator_generated::ParseGlobal::<TT, peginator_generated::ParseCache, ()>::new(
+                Default::default(),
+                user_defined,
+                settings,
+            );
+        global.recover = true;
+        let result = peginator_generated::parse_Grammar(
+            peginator_generated::ParseState::new(s, settings),
+            &mut global,
+        );
+        global.into_recovery_result(result, s)
     }
 }
 #[allow(
@@ -229,72 +405,140 @@ impl peginator_generated::PegParserAdvanced<()> for Grammar {
     unused_variables,
     unused_imports,
     unused_mut,
-    dead_code
+    dead_code,
+    clippy::module_inception,
+    clippy::useless_conversion
 )]
 mod peginator_generated {
     use super::*;
     use crate::runtime::*;
     pub use crate::runtime::{
         IndentedTracer, ParseError, ParseGlobal, ParseSettings, ParseState, ParseTracer,
-        PegParserAdvanced, PegPosition,
+        ParsedItem, PegBorrowedParser, PegByteParser, PegParser, PegParserAdvanced, PegPosition,
     };
     #[derive(Default)]
     pub struct ParseCache<'a> {
         _please_dont_complain: std::marker::PhantomData<&'a ()>,
     }
+    pub fn skip_item_whitespace<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseState<'a> {
+        match parse_Whitespace(state.clone(), global) {
+            Ok(ParseOk { state, .. }) => state,
+            Err(_) => state,
+        }
+    }
     mod Grammar_impl {
         use super::*;
         mod part_0 {
             use super::*;
             mod closure {
                 use super::*;
+                mod part_0 {
+                    use super::*;
+                    #[inline(always)]
+                    pub fn parse<'a, TT: ParseTracer>(
+                        state: ParseState<'a>,
+                        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+                    ) -> ParseResult<'a, Parsed> {
+                        ChoiceHelper::new(state)
+                            .choice(|state| {
+                                parse_Whitespace(state, &mut *global)
+                                    .and_then(|ParseOk { state, .. }| {
+                                        parse_Rule(state, &mut *global)
+                                    })
+                                    .map_inner(|result| vec![Parsed_rules::Rule(result)])
+                                    .map_inner(|r| Parsed {
+                                        rules: r,
+                                        directives: Vec::new(),
+                                    })
+                            })
+                            .choice(|state| {
+                                parse_Whitespace(state, &mut *global)
+                                    .and_then(|ParseOk { state, .. }| {
+                                        parse_CharRule(state, &mut *global)
+                                    })
+                                    .map_inner(|result| vec![Parsed_rules::CharRule(result)])
+                                    .map_inner(|r| Parsed {
+                                        rules: r,
+                                        directives: Vec::new(),
+                                    })
+                            })
+                            .choice(|state| {
+                                parse_Whitespace(state, &mut *global)
+                                    .and_then(|ParseOk { state, .. }| {
+                                        parse_ExternRule(state, &mut *global)
+                                    })
+                                    .map_inner(|result| vec![Parsed_rules::ExternRule(result)])
+                                    .map_inner(|r| Parsed {
+                                        rules: r,
+                                        directives: Vec::new(),
+                                    })
+                            })
+                            .choice(|state| {
+                                parse_Whitespace(state, &mut *global)
+                                    .and_then(|ParseOk { state, .. }| {
+                                        parse_PrecedenceRule(state, &mut *global)
+                                    })
+                                    .map_inner(|result| vec![Parsed_rules::PrecedenceRule(result)])
+                                    .map_inner(|r| Parsed {
+                                        rules: r,
+                                        directives: Vec::new(),
+                                    })
+                            })
+                            .choice(|state| {
+                                parse_Whitespace(state, &mut *global)
+                                    .and_then(|ParseOk { state, .. }| {
+                                        parse_GrammarDirective(state, &mut *global)
+                                    })
+                                    .map_inner(|result| vec![result])
+                                    .map_inner(|r| Parsed {
+                                        rules: Vec::new(),
+                                        directives: r,
+                                    })
+                            })
+                            .end()
+                    }
+                    pub struct Parsed {
+                        pub rules: Vec<Parsed_rules>,
+                        pub directives: Vec<GrammarDirective>,
+                    }
+                }
                 #[inline(always)]
-                pub fn parse<'a, TT: ParseTracer, TUD>(
+                pub fn parse<'a, TT: ParseTracer>(
                     state: ParseState<'a>,
-                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                 ) -> ParseResult<'a, Parsed> {
                     let ParseOk {
-                        result: mut rules,
+                        result:
+                            part_0::Parsed {
+                                mut rules,
+                                mut directives,
+                            },
                         state,
-                    } = ChoiceHelper::new(state)
-                        .choice(|state| {
-                            parse_Whitespace(state, &mut *global)
-                                .and_then(|ParseOk { state, .. }| parse_Rule(state, &mut *global))
-                                .map_inner(|result| vec![Parsed_rules::Rule(result)])
-                        })
-                        .choice(|state| {
-                            parse_Whitespace(state, &mut *global)
-                                .and_then(|ParseOk { state, .. }| {
-                                    parse_CharRule(state, &mut *global)
-                                })
-                                .map_inner(|result| vec![Parsed_rules::CharRule(result)])
-                        })
-                        .choice(|state| {
-                            parse_Whitespace(state, &mut *global)
-                                .and_then(|ParseOk { state, .. }| {
-                                    parse_ExternRule(state, &mut *global)
-                                })
-                                .map_inner(|result| vec![Parsed_rules::ExternRule(result)])
-                        })
-                        .end()?;
+                    } = part_0::parse(state, global)?;
                     let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                         .and_then(|ParseOk { state, .. }| parse_character_literal(state, ';'))
                         .discard_result()?;
                     Ok(ParseOk {
-                        result: rules,
+                        result: Parsed { rules, directives },
                         state,
                     })
                 }
-                pub type Parsed = Vec<Parsed_rules>;
+                pub struct Parsed {
+                    pub rules: Vec<Parsed_rules>,
+                    pub directives: Vec<GrammarDirective>,
+                }
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
-                let mut iterations: usize = 0;
                 let mut state = state;
                 let mut rules: Vec<Parsed_rules> = Vec::new();
+                let mut directives: Vec<GrammarDirective> = Vec::new();
                 loop {
                     match closure::parse(state.clone(), global) {
                         Ok(ParseOk {
@@ -302,52 +546,72 @@ mod peginator_generated {
                             state: new_state,
                             ..
                         }) => {
-                            rules.extend(__result);
+                            rules.extend(__result.rules);
+                            directives.extend(__result.directives);
                             state = new_state;
                         }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
                         Err(err) => {
                             state = state.record_error(err);
                             break;
                         }
                     }
-                    iterations += 1;
                 }
                 Ok(ParseOk {
-                    result: rules,
+                    result: Parsed { rules, directives },
                     state,
                 })
             }
-            pub type Parsed = Vec<Parsed_rules>;
+            pub struct Parsed {
+                pub rules: Vec<Parsed_rules>,
+                pub directives: Vec<GrammarDirective>,
+            }
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk {
-                result: mut rules,
+                result:
+                    part_0::Parsed {
+                        mut rules,
+                        mut directives,
+                    },
                 state,
             } = part_0::parse(state, global)?;
             let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_end_of_input(state))?;
             Ok(ParseOk {
-                result: rules,
+                result: Parsed { rules, directives },
                 state,
             })
         }
-        pub type Parsed = Vec<Parsed_rules>;
+        pub struct Parsed {
+            pub rules: Vec<Parsed_rules>,
+            pub directives: Vec<GrammarDirective>,
+        }
         use super::Grammar_rules as Parsed_rules;
     }
     #[inline]
-    pub(super) fn parse_Grammar<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_Grammar<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, Grammar> {
         global.tracer.print_trace_start(&state, "Grammar");
-        let result = {
-            let result = Grammar_impl::parse(state, global)?.map(|r| super::Grammar { rules: r });
-            Ok(result)
-        };
+        let result = state.parse_rule("Grammar", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Grammar> {
+                    let result = Grammar_impl::parse(state, global)?.map(|r| super::Grammar {
+                        rules: r.rules,
+                        directives: r.directives,
+                    });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
@@ -359,11 +623,10 @@ mod peginator_generated {
                 use super::*;
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
-                let mut iterations: usize = 0;
                 let mut state = state;
                 let mut directives: Vec<DirectiveExpression> = Vec::new();
                 loop {
@@ -381,12 +644,12 @@ mod peginator_generated {
                             directives.extend(__result);
                             state = new_state;
                         }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
                         Err(err) => {
                             state = state.record_error(err);
                             break;
                         }
                     }
-                    iterations += 1;
                 }
                 Ok(ParseOk {
                     result: directives,
@@ -396,9 +659,9 @@ mod peginator_generated {
             pub type Parsed = Vec<DirectiveExpression>;
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk {
                 result: mut directives,
@@ -409,6 +672,22 @@ mod peginator_generated {
                 state,
             } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_Identifier(state, &mut *global))?;
+            let ParseOk {
+                result: parameters,
+                state,
+            } = parse_Whitespace(state.clone(), &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_RuleParameters(state, &mut *global))
+                .map_inner(Some)
+                .or_else(|err| {
+                    if err.is_committed() {
+                        Err(err.uncommit())
+                    } else {
+                        Ok(ParseOk {
+                            result: Default::default(),
+                            state: state.record_error(err),
+                        })
+                    }
+                })?;
             let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_character_literal(state, '='))
                 .discard_result()?;
@@ -421,6 +700,7 @@ mod peginator_generated {
                 result: Parsed {
                     directives,
                     name,
+                    parameters,
                     definition,
                 },
                 state,
@@ -429,23 +709,135 @@ mod peginator_generated {
         pub struct Parsed {
             pub directives: Vec<DirectiveExpression>,
             pub name: Identifier,
+            pub parameters: Option<RuleParameters>,
             pub definition: Choice,
         }
     }
     #[inline]
-    pub(super) fn parse_Rule<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_Rule<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, Rule> {
         global.tracer.print_trace_start(&state, "Rule");
-        let result = {
-            let result = Rule_impl::parse(state, global)?.map(|r| super::Rule {
-                directives: r.directives,
-                name: r.name,
-                definition: r.definition,
-            });
-            Ok(result)
-        };
+        let result = state.parse_rule("Rule", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Rule> {
+                    let result = Rule_impl::parse(state, global)?.map(|r| super::Rule {
+                        directives: r.directives,
+                        name: r.name,
+                        parameters: r.parameters,
+                        definition: r.definition,
+                    });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod RuleParameters_impl {
+        use super::*;
+        mod part_1 {
+            use super::*;
+            mod closure {
+                use super::*;
+            }
+            #[inline(always)]
+            pub fn parse<'a, TT: ParseTracer>(
+                state: ParseState<'a>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+            ) -> ParseResult<'a, Parsed> {
+                let mut iterations: usize = 0;
+                let mut state = state;
+                let mut names: Vec<Identifier> = Vec::new();
+                loop {
+                    let before_separator = state.clone();
+                    if iterations > 0 {
+                        match parse_Whitespace(state.clone(), &mut *global)
+                            .and_then(|ParseOk { state, .. }| parse_character_literal(state, ','))
+                            .discard_result()
+                        {
+                            Ok(ParseOk {
+                                state: new_state, ..
+                            }) => state = new_state,
+                            Err(err) if err.is_committed() => return Err(err.uncommit()),
+                            Err(err) => {
+                                state = state.record_error(err);
+                                break;
+                            }
+                        }
+                    }
+                    match parse_Whitespace(state.clone(), &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_Identifier(state, &mut *global))
+                        .map_inner(|result| vec![result])
+                    {
+                        Ok(ParseOk {
+                            result: __result,
+                            state: new_state,
+                            ..
+                        }) => {
+                            names.extend(__result);
+                            state = new_state;
+                        }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
+                        Err(err) => {
+                            state = state.rewind_to(&before_separator).record_error(err);
+                            break;
+                        }
+                    }
+                    iterations += 1;
+                }
+                if iterations == 0 {
+                    return Err(state.report_farthest_error());
+                }
+                Ok(ParseOk {
+                    result: names,
+                    state,
+                })
+            }
+            pub type Parsed = Vec<Identifier>;
+        }
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '<'))
+                .discard_result()?;
+            let ParseOk {
+                result: mut names,
+                state,
+            } = part_1::parse(state, global)?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '>'))
+                .discard_result()?;
+            Ok(ParseOk {
+                result: names,
+                state,
+            })
+        }
+        pub type Parsed = Vec<Identifier>;
+    }
+    #[inline]
+    pub(super) fn parse_RuleParameters<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, RuleParameters> {
+        global.tracer.print_trace_start(&state, "RuleParameters");
+        let result = state.parse_rule("RuleParameters", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, RuleParameters> {
+                    let result = RuleParameters_impl::parse(state, global)?
+                        .map(|r| super::RuleParameters { names: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
@@ -457,11 +849,10 @@ mod peginator_generated {
                 use super::*;
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
-                let mut iterations: usize = 0;
                 let mut state = state;
                 let mut directives: Vec<CheckDirective> = Vec::new();
                 loop {
@@ -477,12 +868,12 @@ mod peginator_generated {
                             directives.extend(__result);
                             state = new_state;
                         }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
                         Err(err) => {
                             state = state.record_error(err);
                             break;
                         }
                     }
-                    iterations += 1;
                 }
                 Ok(ParseOk {
                     result: directives,
@@ -497,11 +888,10 @@ mod peginator_generated {
                 use super::*;
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
-                let mut iterations: usize = 0;
                 let mut state = state;
                 let mut directives: Vec<CheckDirective> = Vec::new();
                 loop {
@@ -517,12 +907,12 @@ mod peginator_generated {
                             directives.extend(__result);
                             state = new_state;
                         }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
                         Err(err) => {
                             state = state.record_error(err);
                             break;
                         }
                     }
-                    iterations += 1;
                 }
                 Ok(ParseOk {
                     result: directives,
@@ -531,41 +921,40 @@ mod peginator_generated {
             }
             pub type Parsed = Vec<CheckDirective>;
         }
-        mod part_6 {
+        mod part_5 {
             use super::*;
             mod closure {
                 use super::*;
-                #[inline(always)]
-                pub fn parse<'a, TT: ParseTracer, TUD>(
-                    state: ParseState<'a>,
-                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-                ) -> ParseResult<'a, Parsed> {
-                    let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
-                        .and_then(|ParseOk { state, .. }| parse_character_literal(state, '|'))
-                        .discard_result()?;
-                    let ParseOk {
-                        result: mut choices,
-                        state,
-                    } = parse_Whitespace(state, &mut *global)
-                        .and_then(|ParseOk { state, .. }| parse_CharRulePart(state, &mut *global))
-                        .map_inner(|result| vec![result])?;
-                    Ok(ParseOk {
-                        result: choices,
-                        state,
-                    })
-                }
-                pub type Parsed = Vec<CharRulePart>;
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
                 let mut iterations: usize = 0;
                 let mut state = state;
                 let mut choices: Vec<CharRulePart> = Vec::new();
                 loop {
-                    match closure::parse(state.clone(), global) {
+                    let before_separator = state.clone();
+                    if iterations > 0 {
+                        match parse_Whitespace(state.clone(), &mut *global)
+                            .and_then(|ParseOk { state, .. }| parse_character_literal(state, '|'))
+                            .discard_result()
+                        {
+                            Ok(ParseOk {
+                                state: new_state, ..
+                            }) => state = new_state,
+                            Err(err) if err.is_committed() => return Err(err.uncommit()),
+                            Err(err) => {
+                                state = state.record_error(err);
+                                break;
+                            }
+                        }
+                    }
+                    match parse_Whitespace(state.clone(), &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_CharRulePart(state, &mut *global))
+                        .map_inner(|result| vec![result])
+                    {
                         Ok(ParseOk {
                             result: __result,
                             state: new_state,
@@ -574,13 +963,17 @@ mod peginator_generated {
                             choices.extend(__result);
                             state = new_state;
                         }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
                         Err(err) => {
-                            state = state.record_error(err);
+                            state = state.rewind_to(&before_separator).record_error(err);
                             break;
                         }
                     }
                     iterations += 1;
                 }
+                if iterations == 0 {
+                    return Err(state.report_farthest_error());
+                }
                 Ok(ParseOk {
                     result: choices,
                     state,
@@ -589,9 +982,9 @@ mod peginator_generated {
             pub type Parsed = Vec<CharRulePart>;
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk {
                 result: mut directives,
@@ -616,14 +1009,7 @@ mod peginator_generated {
             let ParseOk {
                 result: mut choices,
                 state,
-            } = parse_Whitespace(state, &mut *global)
-                .and_then(|ParseOk { state, .. }| parse_CharRulePart(state, &mut *global))
-                .map_inner(|result| vec![result])?;
-            let ParseOk {
-                result: extend_choices_with,
-                state,
-            } = part_6::parse(state, global)?;
-            choices.extend(extend_choices_with);
+            } = part_5::parse(state, global)?;
             Ok(ParseOk {
                 result: Parsed {
                     directives,
@@ -640,30 +1026,41 @@ mod peginator_generated {
         }
     }
     #[inline]
-    pub(super) fn parse_CharRule<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_CharRule<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, CharRule> {
         global.tracer.print_trace_start(&state, "CharRule");
-        let result = {
-            let result = CharRule_impl::parse(state, global)?.map(|r| super::CharRule {
-                directives: r.directives,
-                name: r.name,
-                choices: r.choices,
-            });
-            Ok(result)
-        };
+        let result = state.parse_rule("CharRule", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, CharRule> {
+                    let result = CharRule_impl::parse(state, global)?.map(|r| super::CharRule {
+                        directives: r.directives,
+                        name: r.name,
+                        choices: r.choices,
+                    });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod CharRulePart_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             ChoiceHelper::new(state)
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_UnicodeClass(state, global))
+                        .map_inner(Parsed__override::UnicodeClass)
+                })
                 .choice(|state| {
                     parse_Whitespace(state, &mut *global)
                         .and_then(|ParseOk { state, .. }| parse_CharacterRange(state, global))
@@ -685,24 +1082,30 @@ mod peginator_generated {
         use super::CharRulePart as Parsed__override;
     }
     #[inline]
-    pub(super) fn parse_CharRulePart<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_CharRulePart<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, CharRulePart> {
         global.tracer.print_trace_start(&state, "CharRulePart");
-        let result = {
-            let result = CharRulePart_impl::parse(state, global)?;
-            Ok(result)
-        };
+        let result = state.parse_rule("CharRulePart", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, CharRulePart> {
+                    let result = CharRulePart_impl::parse(state, global)?;
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod ExternRule_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk {
                 result: directive,
@@ -725,96 +1128,685 @@ mod peginator_generated {
         }
     }
     #[inline]
-    pub(super) fn parse_ExternRule<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_ExternRule<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, ExternRule> {
         global.tracer.print_trace_start(&state, "ExternRule");
-        let result = {
-            let result = ExternRule_impl::parse(state, global)?.map(|r| super::ExternRule {
-                directive: r.directive,
-                name: r.name,
-            });
-            Ok(result)
-        };
+        let result = state.parse_rule("ExternRule", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, ExternRule> {
+                    let result =
+                        ExternRule_impl::parse(state, global)?.map(|r| super::ExternRule {
+                            directive: r.directive,
+                            name: r.name,
+                        });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
-    mod Choice_impl {
+    mod PrecedenceRule_impl {
         use super::*;
-        mod part_1 {
+        mod part_0 {
             use super::*;
             mod closure {
                 use super::*;
-                #[inline(always)]
-                pub fn parse<'a, TT: ParseTracer, TUD>(
-                    state: ParseState<'a>,
-                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-                ) -> ParseResult<'a, Parsed> {
-                    let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+            }
+            #[inline(always)]
+            pub fn parse<'a, TT: ParseTracer>(
+                state: ParseState<'a>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+            ) -> ParseResult<'a, Parsed> {
+                let mut state = state;
+                let mut directives: Vec<PrecedenceRuleDirective> = Vec::new();
+                loop {
+                    match parse_Whitespace(state.clone(), &mut *global)
+                        .and_then(|ParseOk { state, .. }| {
+                            parse_PrecedenceRuleDirective(state, &mut *global)
+                        })
+                        .map_inner(|result| vec![result])
+                    {
+                        Ok(ParseOk {
+                            result: __result,
+                            state: new_state,
+                            ..
+                        }) => {
+                            directives.extend(__result);
+                            state = new_state;
+                        }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
+                        Err(err) => {
+                            state = state.record_error(err);
+                            break;
+                        }
+                    }
+                }
+                Ok(ParseOk {
+                    result: directives,
+                    state,
+                })
+            }
+            pub type Parsed = Vec<PrecedenceRuleDirective>;
+        }
+        mod part_2 {
+            use super::*;
+            mod closure {
+                use super::*;
+            }
+            #[inline(always)]
+            pub fn parse<'a, TT: ParseTracer>(
+                state: ParseState<'a>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+            ) -> ParseResult<'a, Parsed> {
+                let mut state = state;
+                let mut directives: Vec<PrecedenceRuleDirective> = Vec::new();
+                loop {
+                    match parse_Whitespace(state.clone(), &mut *global)
+                        .and_then(|ParseOk { state, .. }| {
+                            parse_PrecedenceRuleDirective(state, &mut *global)
+                        })
+                        .map_inner(|result| vec![result])
+                    {
+                        Ok(ParseOk {
+                            result: __result,
+                            state: new_state,
+                            ..
+                        }) => {
+                            directives.extend(__result);
+                            state = new_state;
+                        }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
+                        Err(err) => {
+                            state = state.record_error(err);
+                            break;
+                        }
+                    }
+                }
+                Ok(ParseOk {
+                    result: directives,
+                    state,
+                })
+            }
+            pub type Parsed = Vec<PrecedenceRuleDirective>;
+        }
+        mod part_5 {
+            use super::*;
+            mod closure {
+                use super::*;
+            }
+            #[inline(always)]
+            pub fn parse<'a, TT: ParseTracer>(
+                state: ParseState<'a>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+            ) -> ParseResult<'a, Parsed> {
+                let mut iterations: usize = 0;
+                let mut state = state;
+                let mut levels: Vec<PrecedenceLevel> = Vec::new();
+                loop {
+                    match parse_Whitespace(state.clone(), &mut *global)
+                        .and_then(|ParseOk { state, .. }| {
+                            parse_PrecedenceLevel(state, &mut *global)
+                        })
+                        .map_inner(|result| vec![result])
+                    {
+                        Ok(ParseOk {
+                            result: __result,
+                            state: new_state,
+                            ..
+                        }) => {
+                            levels.extend(__result);
+                            state = new_state;
+                        }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
+                        Err(err) => {
+                            state = state.record_error(err);
+                            break;
+                        }
+                    }
+                    iterations += 1;
+                }
+                if iterations == 0 {
+                    return Err(state.report_farthest_error());
+                }
+                Ok(ParseOk {
+                    result: levels,
+                    state,
+                })
+            }
+            pub type Parsed = Vec<PrecedenceLevel>;
+        }
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk {
+                result: mut directives,
+                state,
+            } = part_0::parse(state, global)?;
+            let ParseOk {
+                result: directive,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_PrecedenceDirective(state, &mut *global))?;
+            let ParseOk {
+                result: extend_directives_with,
+                state,
+            } = part_2::parse(state, global)?;
+            directives.extend(extend_directives_with);
+            let ParseOk {
+                result: name,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_Identifier(state, &mut *global))?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '='))
+                .discard_result()?;
+            let ParseOk {
+                result: mut levels,
+                state,
+            } = part_5::parse(state, global)?;
+            Ok(ParseOk {
+                result: Parsed {
+                    directives,
+                    directive,
+                    name,
+                    levels,
+                },
+                state,
+            })
+        }
+        pub struct Parsed {
+            pub directives: Vec<PrecedenceRuleDirective>,
+            pub directive: PrecedenceDirective,
+            pub name: Identifier,
+            pub levels: Vec<PrecedenceLevel>,
+        }
+    }
+    #[inline]
+    pub(super) fn parse_PrecedenceRule<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, PrecedenceRule> {
+        global.tracer.print_trace_start(&state, "PrecedenceRule");
+        let result = state.parse_rule("PrecedenceRule", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, PrecedenceRule> {
+                    let result =
+                        PrecedenceRule_impl::parse(state, global)?.map(|r| super::PrecedenceRule {
+                            directives: r.directives,
+                            directive: r.directive,
+                            name: r.name,
+                            levels: r.levels,
+                        });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod PrecedenceRuleDirective_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            ChoiceHelper::new(state)
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_ExportDirective(state, global))
+                        .map_inner(Parsed__override::ExportDirective)
+                })
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_NoSkipWsDirective(state, global))
+                        .map_inner(Parsed__override::NoSkipWsDirective)
+                })
+                .end()
+        }
+        pub type Parsed = Parsed__override;
+        use super::PrecedenceRuleDirective as Parsed__override;
+    }
+    #[inline]
+    pub(super) fn parse_PrecedenceRuleDirective<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, PrecedenceRuleDirective> {
+        global
+            .tracer
+            .print_trace_start(&state, "PrecedenceRuleDirective");
+        let result = state.parse_rule("PrecedenceRuleDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, PrecedenceRuleDirective> {
+                    let result = PrecedenceRuleDirective_impl::parse(state, global)?;
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod PrecedenceLevel_impl {
+        use super::*;
+        mod part_2 {
+            use super::*;
+            mod closure {
+                use super::*;
+            }
+            #[inline(always)]
+            pub fn parse<'a, TT: ParseTracer>(
+                state: ParseState<'a>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+            ) -> ParseResult<'a, Parsed> {
+                let mut iterations: usize = 0;
+                let mut state = state;
+                let mut operators: Vec<PrecedenceOperator> = Vec::new();
+                loop {
+                    let before_separator = state.clone();
+                    if iterations > 0 {
+                        match parse_Whitespace(state.clone(), &mut *global)
+                            .and_then(|ParseOk { state, .. }| parse_character_literal(state, '|'))
+                            .discard_result()
+                        {
+                            Ok(ParseOk {
+                                state: new_state, ..
+                            }) => state = new_state,
+                            Err(err) if err.is_committed() => return Err(err.uncommit()),
+                            Err(err) => {
+                                state = state.record_error(err);
+                                break;
+                            }
+                        }
+                    }
+                    match parse_Whitespace(state.clone(), &mut *global)
+                        .and_then(|ParseOk { state, .. }| {
+                            parse_PrecedenceOperator(state, &mut *global)
+                        })
+                        .map_inner(|result| vec![result])
+                    {
+                        Ok(ParseOk {
+                            result: __result,
+                            state: new_state,
+                            ..
+                        }) => {
+                            operators.extend(__result);
+                            state = new_state;
+                        }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
+                        Err(err) => {
+                            state = state.rewind_to(&before_separator).record_error(err);
+                            break;
+                        }
+                    }
+                    iterations += 1;
+                }
+                if iterations == 0 {
+                    return Err(state.report_farthest_error());
+                }
+                Ok(ParseOk {
+                    result: operators,
+                    state,
+                })
+            }
+            pub type Parsed = Vec<PrecedenceOperator>;
+        }
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk {
+                result: kind,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_PrecedenceLevelKind(state, &mut *global))?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '('))
+                .discard_result()?;
+            let ParseOk {
+                result: mut operators,
+                state,
+            } = part_2::parse(state, global)?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, ')'))
+                .discard_result()?;
+            Ok(ParseOk {
+                result: Parsed { kind, operators },
+                state,
+            })
+        }
+        pub struct Parsed {
+            pub kind: PrecedenceLevelKind,
+            pub operators: Vec<PrecedenceOperator>,
+        }
+    }
+    #[inline]
+    pub(super) fn parse_PrecedenceLevel<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, PrecedenceLevel> {
+        global.tracer.print_trace_start(&state, "PrecedenceLevel");
+        let result = state.parse_rule("PrecedenceLevel", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, PrecedenceLevel> {
+                    let result = PrecedenceLevel_impl::parse(state, global)?.map(|r| {
+                        super::PrecedenceLevel {
+                            kind: r.kind,
+                            operators: r.operators,
+                        }
+                    });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod PrecedenceLevelKind_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            ChoiceHelper::new(state)
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_LeftAssociative(state, global))
+                        .map_inner(Parsed__override::LeftAssociative)
+                })
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_RightAssociative(state, global))
+                        .map_inner(Parsed__override::RightAssociative)
+                })
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_PrefixOperators(state, global))
+                        .map_inner(Parsed__override::PrefixOperators)
+                })
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_PostfixOperators(state, global))
+                        .map_inner(Parsed__override::PostfixOperators)
+                })
+                .end()
+        }
+        pub type Parsed = Parsed__override;
+        use super::PrecedenceLevelKind as Parsed__override;
+    }
+    #[inline]
+    pub(super) fn parse_PrecedenceLevelKind<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, PrecedenceLevelKind> {
+        global
+            .tracer
+            .print_trace_start(&state, "PrecedenceLevelKind");
+        let result = state.parse_rule("PrecedenceLevelKind", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, PrecedenceLevelKind> {
+                    let result = PrecedenceLevelKind_impl::parse(state, global)?;
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod LeftAssociative_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_string_literal(state, "left"))
+                .discard_result()
+        }
+        pub type Parsed = ();
+    }
+    #[inline]
+    pub(super) fn parse_LeftAssociative<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, LeftAssociative> {
+        global.tracer.print_trace_start(&state, "LeftAssociative");
+        let result = state.parse_rule("LeftAssociative", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, LeftAssociative> {
+                    let result = LeftAssociative_impl::parse(state, global)?
+                        .map(|r| super::LeftAssociative {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod RightAssociative_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_string_literal(state, "right"))
+                .discard_result()
+        }
+        pub type Parsed = ();
+    }
+    #[inline]
+    pub(super) fn parse_RightAssociative<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, RightAssociative> {
+        global.tracer.print_trace_start(&state, "RightAssociative");
+        let result = state.parse_rule("RightAssociative", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, RightAssociative> {
+                    let result = RightAssociative_impl::parse(state, global)?
+                        .map(|r| super::RightAssociative {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod PrefixOperators_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_string_literal(state, "prefix"))
+                .discard_result()
+        }
+        pub type Parsed = ();
+    }
+    #[inline]
+    pub(super) fn parse_PrefixOperators<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, PrefixOperators> {
+        global.tracer.print_trace_start(&state, "PrefixOperators");
+        let result = state.parse_rule("PrefixOperators", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, PrefixOperators> {
+                    let result = PrefixOperators_impl::parse(state, global)?
+                        .map(|r| super::PrefixOperators {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod PostfixOperators_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_string_literal(state, "postfix"))
+                .discard_result()
+        }
+        pub type Parsed = ();
+    }
+    #[inline]
+    pub(super) fn parse_PostfixOperators<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, PostfixOperators> {
+        global.tracer.print_trace_start(&state, "PostfixOperators");
+        let result = state.parse_rule("PostfixOperators", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, PostfixOperators> {
+                    let result = PostfixOperators_impl::parse(state, global)?
+                        .map(|r| super::PostfixOperators {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod PrecedenceOperator_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk {
+                result: name,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_Identifier(state, &mut *global))?;
+            let ParseOk {
+                result: body,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_Sequence(state, &mut *global))?;
+            Ok(ParseOk {
+                result: Parsed { name, body },
+                state,
+            })
+        }
+        pub struct Parsed {
+            pub name: Identifier,
+            pub body: Sequence,
+        }
+    }
+    #[inline]
+    pub(super) fn parse_PrecedenceOperator<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, PrecedenceOperator> {
+        global
+            .tracer
+            .print_trace_start(&state, "PrecedenceOperator");
+        let result = state.parse_rule("PrecedenceOperator", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, PrecedenceOperator> {
+                    let result = PrecedenceOperator_impl::parse(state, global)?.map(|r| {
+                        super::PrecedenceOperator {
+                            name: r.name,
+                            body: r.body,
+                        }
+                    });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod Choice_impl {
+        use super::*;
+        mod closure {
+            use super::*;
+        }
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let mut iterations: usize = 0;
+            let mut state = state;
+            let mut choices: Vec<Sequence> = Vec::new();
+            loop {
+                let before_separator = state.clone();
+                if iterations > 0 {
+                    match parse_Whitespace(state.clone(), &mut *global)
                         .and_then(|ParseOk { state, .. }| parse_character_literal(state, '|'))
-                        .discard_result()?;
-                    let ParseOk {
-                        result: mut choices,
-                        state,
-                    } = parse_Whitespace(state, &mut *global)
-                        .and_then(|ParseOk { state, .. }| parse_Sequence(state, &mut *global))
-                        .map_inner(|result| vec![result])?;
-                    Ok(ParseOk {
-                        result: choices,
-                        state,
-                    })
-                }
-                pub type Parsed = Vec<Sequence>;
-            }
-            #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
-                state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-            ) -> ParseResult<'a, Parsed> {
-                let mut iterations: usize = 0;
-                let mut state = state;
-                let mut choices: Vec<Sequence> = Vec::new();
-                loop {
-                    match closure::parse(state.clone(), global) {
+                        .discard_result()
+                    {
                         Ok(ParseOk {
-                            result: __result,
-                            state: new_state,
-                            ..
-                        }) => {
-                            choices.extend(__result);
-                            state = new_state;
-                        }
+                            state: new_state, ..
+                        }) => state = new_state,
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
                         Err(err) => {
                             state = state.record_error(err);
                             break;
                         }
                     }
-                    iterations += 1;
                 }
-                Ok(ParseOk {
-                    result: choices,
-                    state,
-                })
+                match parse_Whitespace(state.clone(), &mut *global)
+                    .and_then(|ParseOk { state, .. }| parse_Sequence(state, &mut *global))
+                    .map_inner(|result| vec![result])
+                {
+                    Ok(ParseOk {
+                        result: __result,
+                        state: new_state,
+                        ..
+                    }) => {
+                        choices.extend(__result);
+                        state = new_state;
+                    }
+                    Err(err) if err.is_committed() => return Err(err.uncommit()),
+                    Err(err) => {
+                        state = state.rewind_to(&before_separator).record_error(err);
+                        break;
+                    }
+                }
+                iterations += 1;
+            }
+            if iterations == 0 {
+                return Err(state.report_farthest_error());
             }
-            pub type Parsed = Vec<Sequence>;
-        }
-        #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
-            state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-        ) -> ParseResult<'a, Parsed> {
-            let ParseOk {
-                result: mut choices,
-                state,
-            } = parse_Whitespace(state, &mut *global)
-                .and_then(|ParseOk { state, .. }| parse_Sequence(state, &mut *global))
-                .map_inner(|result| vec![result])?;
-            let ParseOk {
-                result: extend_choices_with,
-                state,
-            } = part_1::parse(state, global)?;
-            choices.extend(extend_choices_with);
             Ok(ParseOk {
                 result: choices,
                 state,
@@ -823,15 +1815,22 @@ mod peginator_generated {
         pub type Parsed = Vec<Sequence>;
     }
     #[inline]
-    pub(super) fn parse_Choice<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_Choice<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, Choice> {
         global.tracer.print_trace_start(&state, "Choice");
-        let result = {
-            let result = Choice_impl::parse(state, global)?.map(|r| super::Choice { choices: r });
-            Ok(result)
-        };
+        let result = state.parse_rule("Choice", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Choice> {
+                    let result =
+                        Choice_impl::parse(state, global)?.map(|r| super::Choice { choices: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
@@ -841,11 +1840,10 @@ mod peginator_generated {
             use super::*;
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
-            let mut iterations: usize = 0;
             let mut state = state;
             let mut parts: Vec<DelimitedExpression> = Vec::new();
             loop {
@@ -863,12 +1861,12 @@ mod peginator_generated {
                         parts.extend(__result);
                         state = new_state;
                     }
+                    Err(err) if err.is_committed() => return Err(err.uncommit()),
                     Err(err) => {
                         state = state.record_error(err);
                         break;
                     }
                 }
-                iterations += 1;
             }
             Ok(ParseOk {
                 result: parts,
@@ -878,24 +1876,31 @@ mod peginator_generated {
         pub type Parsed = Vec<DelimitedExpression>;
     }
     #[inline]
-    pub(super) fn parse_Sequence<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_Sequence<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, Sequence> {
         global.tracer.print_trace_start(&state, "Sequence");
-        let result = {
-            let result = Sequence_impl::parse(state, global)?.map(|r| super::Sequence { parts: r });
-            Ok(result)
-        };
+        let result = state.parse_rule("Sequence", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Sequence> {
+                    let result =
+                        Sequence_impl::parse(state, global)?.map(|r| super::Sequence { parts: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod Group_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_character_literal(state, '('))
@@ -908,153 +1913,540 @@ mod peginator_generated {
             let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_character_literal(state, ')'))
                 .discard_result()?;
-            Ok(ParseOk {
-                result: body,
-                state,
-            })
+            Ok(ParseOk {
+                result: body,
+                state,
+            })
+        }
+        pub type Parsed = Choice;
+    }
+    #[inline]
+    pub(super) fn parse_Group<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, Group> {
+        global.tracer.print_trace_start(&state, "Group");
+        let result = state.parse_rule("Group", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Group> {
+                    let result =
+                        Group_impl::parse(state, global)?.map(|r| super::Group { body: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod Optional_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '['))
+                .discard_result()?;
+            let ParseOk {
+                result: body,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_Choice(state, &mut *global))?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, ']'))
+                .discard_result()?;
+            Ok(ParseOk {
+                result: body,
+                state,
+            })
+        }
+        pub type Parsed = Choice;
+    }
+    #[inline]
+    pub(super) fn parse_Optional<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, Optional> {
+        global.tracer.print_trace_start(&state, "Optional");
+        let result = state.parse_rule("Optional", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Optional> {
+                    let result =
+                        Optional_impl::parse(state, global)?.map(|r| super::Optional { body: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod Closure_impl {
+        use super::*;
+        mod part_4 {
+            use super::*;
+            mod optional {
+                use super::*;
+                #[inline(always)]
+                pub fn parse<'a, TT: ParseTracer>(
+                    state: ParseState<'a>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+                ) -> ParseResult<'a, Parsed> {
+                    ChoiceHelper::new(state)
+                        .choice(|state| {
+                            parse_Whitespace(state, &mut *global)
+                                .and_then(|ParseOk { state, .. }| {
+                                    parse_AtLeastOneMarker(state, &mut *global)
+                                })
+                                .map_inner(Some)
+                                .map_inner(|r| Parsed {
+                                    at_least_one: r,
+                                    bounds: None,
+                                })
+                        })
+                        .choice(|state| {
+                            parse_Whitespace(state, &mut *global)
+                                .and_then(|ParseOk { state, .. }| {
+                                    parse_RepetitionBounds(state, &mut *global)
+                                })
+                                .map_inner(Some)
+                                .map_inner(|r| Parsed {
+                                    at_least_one: None,
+                                    bounds: r,
+                                })
+                        })
+                        .end()
+                }
+                pub struct Parsed {
+                    pub at_least_one: Option<AtLeastOneMarker>,
+                    pub bounds: Option<RepetitionBounds>,
+                }
+            }
+            #[inline(always)]
+            pub fn parse<'a, TT: ParseTracer>(
+                state: ParseState<'a>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+            ) -> ParseResult<'a, Parsed> {
+                optional::parse(state.clone(), global)
+                    .map_inner(|result| Parsed {
+                        at_least_one: result.at_least_one,
+                        bounds: result.bounds,
+                    })
+                    .or_else(|err| {
+                        if err.is_committed() {
+                            Err(err.uncommit())
+                        } else {
+                            Ok(ParseOk {
+                                result: Parsed {
+                                    at_least_one: Default::default(),
+                                    bounds: Default::default(),
+                                },
+                                state: state.record_error(err),
+                            })
+                        }
+                    })
+            }
+            pub struct Parsed {
+                pub at_least_one: Option<AtLeastOneMarker>,
+                pub bounds: Option<RepetitionBounds>,
+            }
+        }
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '{'))
+                .discard_result()?;
+            let ParseOk {
+                result: body,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_Choice(state, &mut *global))?;
+            let ParseOk {
+                result: separator,
+                state,
+            } = parse_Whitespace(state.clone(), &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_ClosureSeparator(state, &mut *global))
+                .map_inner(Some)
+                .or_else(|err| {
+                    if err.is_committed() {
+                        Err(err.uncommit())
+                    } else {
+                        Ok(ParseOk {
+                            result: Default::default(),
+                            state: state.record_error(err),
+                        })
+                    }
+                })?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '}'))
+                .discard_result()?;
+            let ParseOk {
+                result:
+                    part_4::Parsed {
+                        at_least_one,
+                        bounds,
+                    },
+                state,
+            } = part_4::parse(state, global)?;
+            Ok(ParseOk {
+                result: Parsed {
+                    body,
+                    separator,
+                    at_least_one,
+                    bounds,
+                },
+                state,
+            })
+        }
+        pub struct Parsed {
+            pub body: Choice,
+            pub separator: Option<ClosureSeparator>,
+            pub at_least_one: Option<AtLeastOneMarker>,
+            pub bounds: Option<RepetitionBounds>,
+        }
+    }
+    #[inline]
+    pub(super) fn parse_Closure<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, Closure> {
+        global.tracer.print_trace_start(&state, "Closure");
+        let result = state.parse_rule("Closure", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Closure> {
+                    let result = Closure_impl::parse(state, global)?.map(|r| super::Closure {
+                        body: r.body,
+                        separator: r.separator,
+                        at_least_one: r.at_least_one,
+                        bounds: r.bounds,
+                    });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod AtLeastOneMarker_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '+'))
+                .discard_result()
+        }
+        pub type Parsed = ();
+    }
+    #[inline]
+    pub(super) fn parse_AtLeastOneMarker<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, AtLeastOneMarker> {
+        global.tracer.print_trace_start(&state, "AtLeastOneMarker");
+        let result = state.parse_rule("AtLeastOneMarker", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, AtLeastOneMarker> {
+                    let result = AtLeastOneMarker_impl::parse(state, global)?
+                        .map(|r| super::AtLeastOneMarker {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod RepetitionBounds_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '{'))
+                .discard_result()?;
+            let ParseOk { result: min, state } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_RepetitionCount(state, &mut *global))?;
+            let ParseOk {
+                result: upper,
+                state,
+            } = parse_Whitespace(state.clone(), &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_RepetitionUpperBound(state, &mut *global))
+                .map_inner(Some)
+                .or_else(|err| {
+                    if err.is_committed() {
+                        Err(err.uncommit())
+                    } else {
+                        Ok(ParseOk {
+                            result: Default::default(),
+                            state: state.record_error(err),
+                        })
+                    }
+                })?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '}'))
+                .discard_result()?;
+            Ok(ParseOk {
+                result: Parsed { min, upper },
+                state,
+            })
+        }
+        pub struct Parsed {
+            pub min: RepetitionCount,
+            pub upper: Option<RepetitionUpperBound>,
+        }
+    }
+    #[inline]
+    pub(super) fn parse_RepetitionBounds<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, RepetitionBounds> {
+        global.tracer.print_trace_start(&state, "RepetitionBounds");
+        let result = state.parse_rule("RepetitionBounds", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, RepetitionBounds> {
+                    let result = RepetitionBounds_impl::parse(state, global)?.map(|r| {
+                        super::RepetitionBounds {
+                            min: r.min,
+                            upper: r.upper,
+                        }
+                    });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod RepetitionUpperBound_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, ','))
+                .discard_result()?;
+            let ParseOk { result: max, state } = parse_Whitespace(state.clone(), &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_RepetitionCount(state, &mut *global))
+                .map_inner(Some)
+                .or_else(|err| {
+                    if err.is_committed() {
+                        Err(err.uncommit())
+                    } else {
+                        Ok(ParseOk {
+                            result: Default::default(),
+                            state: state.record_error(err),
+                        })
+                    }
+                })?;
+            Ok(ParseOk { result: max, state })
         }
-        pub type Parsed = Choice;
+        pub type Parsed = Option<RepetitionCount>;
     }
     #[inline]
-    pub(super) fn parse_Group<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_RepetitionUpperBound<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-    ) -> ParseResult<'a, Group> {
-        global.tracer.print_trace_start(&state, "Group");
-        let result = {
-            let result = Group_impl::parse(state, global)?.map(|r| super::Group { body: r });
-            Ok(result)
-        };
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, RepetitionUpperBound> {
+        global
+            .tracer
+            .print_trace_start(&state, "RepetitionUpperBound");
+        let result = state.parse_rule("RepetitionUpperBound", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, RepetitionUpperBound> {
+                    let result = RepetitionUpperBound_impl::parse(state, global)?
+                        .map(|r| super::RepetitionUpperBound { max: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
-    mod Optional_impl {
+    mod RepetitionCount_impl {
         use super::*;
+        mod closure {
+            use super::*;
+        }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
-            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
-                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '['))
-                .discard_result()?;
-            let ParseOk {
-                result: body,
-                state,
-            } = parse_Whitespace(state, &mut *global)
-                .and_then(|ParseOk { state, .. }| parse_Choice(state, &mut *global))?;
-            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
-                .and_then(|ParseOk { state, .. }| parse_character_literal(state, ']'))
-                .discard_result()?;
-            Ok(ParseOk {
-                result: body,
-                state,
-            })
+            let mut iterations: usize = 0;
+            let mut state = state;
+            loop {
+                match parse_character_range(state.clone(), '0', '9').discard_result() {
+                    Ok(ParseOk {
+                        result: __result,
+                        state: new_state,
+                        ..
+                    }) => {
+                        state = new_state;
+                    }
+                    Err(err) if err.is_committed() => return Err(err.uncommit()),
+                    Err(err) => {
+                        state = state.record_error(err);
+                        break;
+                    }
+                }
+                iterations += 1;
+            }
+            if iterations == 0 {
+                return Err(state.report_farthest_error());
+            }
+            Ok(ParseOk { result: (), state })
         }
-        pub type Parsed = Choice;
+        pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_Optional<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_RepetitionCount<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-    ) -> ParseResult<'a, Optional> {
-        global.tracer.print_trace_start(&state, "Optional");
-        let result = {
-            let result = Optional_impl::parse(state, global)?.map(|r| super::Optional { body: r });
-            Ok(result)
-        };
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, RepetitionCount> {
+        global.tracer.print_trace_start(&state, "RepetitionCount");
+        let result = state.parse_rule("RepetitionCount", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, RepetitionCount> {
+                    let result = RepetitionCount_impl::parse(state.clone(), global)?
+                        .map_with_state(|_, new_state| {
+                            let string = state.slice_until(new_state).to_owned();
+                            string
+                        });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
-    mod Closure_impl {
+    mod ClosureSeparator_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
-            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
-                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '{'))
-                .discard_result()?;
+            let ParseOk {
+                result: trailing,
+                state,
+            } = ChoiceHelper::new(state)
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| {
+                            parse_TrailingSeparatorMarker(state, &mut *global)
+                        })
+                        .map_inner(Some)
+                })
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_character_literal(state, '%'))
+                        .discard_result()
+                        .map_inner(|_| None)
+                })
+                .end()?;
             let ParseOk {
                 result: body,
                 state,
             } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_Choice(state, &mut *global))?;
-            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
-                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '}'))
-                .discard_result()?;
-            let ParseOk {
-                result: at_least_one,
-                state,
-            } = parse_Whitespace(state.clone(), &mut *global)
-                .and_then(|ParseOk { state, .. }| parse_AtLeastOneMarker(state, &mut *global))
-                .map_inner(Some)
-                .or_else(|err| {
-                    Ok(ParseOk {
-                        result: Default::default(),
-                        state: state.record_error(err),
-                    })
-                })?;
             Ok(ParseOk {
-                result: Parsed { body, at_least_one },
+                result: Parsed { trailing, body },
                 state,
             })
         }
         pub struct Parsed {
+            pub trailing: Option<TrailingSeparatorMarker>,
             pub body: Choice,
-            pub at_least_one: Option<AtLeastOneMarker>,
         }
     }
     #[inline]
-    pub(super) fn parse_Closure<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_ClosureSeparator<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-    ) -> ParseResult<'a, Closure> {
-        global.tracer.print_trace_start(&state, "Closure");
-        let result = {
-            let result = Closure_impl::parse(state, global)?.map(|r| super::Closure {
-                body: r.body,
-                at_least_one: r.at_least_one,
-            });
-            Ok(result)
-        };
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, ClosureSeparator> {
+        global.tracer.print_trace_start(&state, "ClosureSeparator");
+        let result = state.parse_rule("ClosureSeparator", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, ClosureSeparator> {
+                    let result = ClosureSeparator_impl::parse(state, global)?.map(|r| {
+                        super::ClosureSeparator {
+                            trailing: r.trailing,
+                            body: r.body,
+                        }
+                    });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
-    mod AtLeastOneMarker_impl {
+    mod TrailingSeparatorMarker_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_Whitespace(state, &mut *global)
-                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '+'))
+                .and_then(|ParseOk { state, .. }| parse_string_literal(state, "%%"))
                 .discard_result()
         }
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_AtLeastOneMarker<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_TrailingSeparatorMarker<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-    ) -> ParseResult<'a, AtLeastOneMarker> {
-        global.tracer.print_trace_start(&state, "AtLeastOneMarker");
-        let result = {
-            let result =
-                AtLeastOneMarker_impl::parse(state, global)?.map(|r| super::AtLeastOneMarker {});
-            Ok(result)
-        };
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, TrailingSeparatorMarker> {
+        global
+            .tracer
+            .print_trace_start(&state, "TrailingSeparatorMarker");
+        let result = state.parse_rule("TrailingSeparatorMarker", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, TrailingSeparatorMarker> {
+                    let result = TrailingSeparatorMarker_impl::parse(state, global)?
+                        .map(|r| super::TrailingSeparatorMarker {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod NegativeLookahead_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_character_literal(state, '!'))
@@ -1073,25 +2465,31 @@ mod peginator_generated {
         pub type Parsed = Box<DelimitedExpression>;
     }
     #[inline]
-    pub(super) fn parse_NegativeLookahead<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_NegativeLookahead<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, NegativeLookahead> {
         global.tracer.print_trace_start(&state, "NegativeLookahead");
-        let result = {
-            let result = NegativeLookahead_impl::parse(state, global)?
-                .map(|r| super::NegativeLookahead { expr: r });
-            Ok(result)
-        };
+        let result = state.parse_rule("NegativeLookahead", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, NegativeLookahead> {
+                    let result = NegativeLookahead_impl::parse(state, global)?
+                        .map(|r| super::NegativeLookahead { expr: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod PositiveLookahead_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_character_literal(state, '&'))
@@ -1110,25 +2508,31 @@ mod peginator_generated {
         pub type Parsed = Box<DelimitedExpression>;
     }
     #[inline]
-    pub(super) fn parse_PositiveLookahead<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_PositiveLookahead<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, PositiveLookahead> {
         global.tracer.print_trace_start(&state, "PositiveLookahead");
-        let result = {
-            let result = PositiveLookahead_impl::parse(state, global)?
-                .map(|r| super::PositiveLookahead { expr: r });
-            Ok(result)
-        };
+        let result = state.parse_rule("PositiveLookahead", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, PositiveLookahead> {
+                    let result = PositiveLookahead_impl::parse(state, global)?
+                        .map(|r| super::PositiveLookahead { expr: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod CharacterRange_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk {
                 result: from,
@@ -1151,28 +2555,230 @@ mod peginator_generated {
         }
     }
     #[inline]
-    pub(super) fn parse_CharacterRange<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_CharacterRange<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, CharacterRange> {
         global.tracer.print_trace_start(&state, "CharacterRange");
-        let result = {
-            let result =
-                CharacterRange_impl::parse(state, global)?.map(|r| super::CharacterRange {
-                    from: r.from,
-                    to: r.to,
-                });
-            Ok(result)
-        };
+        let result = state.parse_rule("CharacterRange", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, CharacterRange> {
+                    let result =
+                        CharacterRange_impl::parse(state, global)?.map(|r| super::CharacterRange {
+                            from: r.from,
+                            to: r.to,
+                        });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod UnicodeClass_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_character_literal(state, '\\').discard_result()?;
+            let ParseOk {
+                result: negated,
+                state,
+            } = ChoiceHelper::new(state)
+                .choice(|state| {
+                    parse_character_literal(state, 'p')
+                        .discard_result()
+                        .map_inner(|_| None)
+                })
+                .choice(|state| parse_NegatedClassMarker(state, &mut *global).map_inner(Some))
+                .end()?;
+            let ParseOk { state, .. } = parse_character_literal(state, '{').discard_result()?;
+            let ParseOk {
+                result: name,
+                state,
+            } = parse_UnicodePropertyName(state, &mut *global)?;
+            let ParseOk { state, .. } = parse_character_literal(state, '}').discard_result()?;
+            Ok(ParseOk {
+                result: Parsed { negated, name },
+                state,
+            })
+        }
+        pub struct Parsed {
+            pub negated: Option<NegatedClassMarker>,
+            pub name: UnicodePropertyName,
+        }
+    }
+    #[inline]
+    pub(super) fn parse_UnicodeClass<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, UnicodeClass> {
+        global.tracer.print_trace_start(&state, "UnicodeClass");
+        let result = state.parse_rule("UnicodeClass", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, UnicodeClass> {
+                    let result =
+                        UnicodeClass_impl::parse(state, global)?.map(|r| super::UnicodeClass {
+                            negated: r.negated,
+                            name: r.name,
+                        });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod NegatedClassMarker_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, 'P'))
+                .discard_result()
+        }
+        pub type Parsed = ();
+    }
+    #[inline]
+    pub(super) fn parse_NegatedClassMarker<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, NegatedClassMarker> {
+        global
+            .tracer
+            .print_trace_start(&state, "NegatedClassMarker");
+        let result = state.parse_rule("NegatedClassMarker", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, NegatedClassMarker> {
+                    let result = NegatedClassMarker_impl::parse(state, global)?
+                        .map(|r| super::NegatedClassMarker {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod UnicodePropertyName_impl {
+        use super::*;
+        mod closure {
+            use super::*;
+            mod part_0 {
+                use super::*;
+                mod negative_lookahead {
+                    use super::*;
+                    #[inline(always)]
+                    pub fn parse<'a, TT: ParseTracer>(
+                        state: ParseState<'a>,
+                        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+                    ) -> ParseResult<'a, Parsed> {
+                        parse_character_literal(state, '}').discard_result()
+                    }
+                    pub type Parsed = ();
+                }
+                #[inline(always)]
+                pub fn parse<'a, TT: ParseTracer>(
+                    state: ParseState<'a>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+                ) -> ParseResult<'a, Parsed> {
+                    match negative_lookahead::parse(state.clone(), global) {
+                        Ok(ParseOk {
+                            state: inner_state, ..
+                        }) => Err(state
+                            .merge_examined(inner_state.examined_until())
+                            .report_error(ParseErrorSpecifics::NegativeLookaheadFailed)),
+                        Err(err) => Ok(ParseOk {
+                            result: (),
+                            state: state.merge_examined(err.examined_until()),
+                        }),
+                    }
+                }
+                pub type Parsed = ();
+            }
+            #[inline(always)]
+            pub fn parse<'a, TT: ParseTracer>(
+                state: ParseState<'a>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+            ) -> ParseResult<'a, Parsed> {
+                let ParseOk { state, .. } = part_0::parse(state, global)?;
+                let ParseOk { state, .. } = parse_char(state, &mut *global).discard_result()?;
+                Ok(ParseOk { result: (), state })
+            }
+            pub type Parsed = ();
+        }
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let mut iterations: usize = 0;
+            let mut state = state;
+            loop {
+                match closure::parse(state.clone(), global) {
+                    Ok(ParseOk {
+                        result: __result,
+                        state: new_state,
+                        ..
+                    }) => {
+                        state = new_state;
+                    }
+                    Err(err) if err.is_committed() => return Err(err.uncommit()),
+                    Err(err) => {
+                        state = state.record_error(err);
+                        break;
+                    }
+                }
+                iterations += 1;
+            }
+            if iterations == 0 {
+                return Err(state.report_farthest_error());
+            }
+            Ok(ParseOk { result: (), state })
+        }
+        pub type Parsed = ();
+    }
+    #[inline]
+    pub(super) fn parse_UnicodePropertyName<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, UnicodePropertyName> {
+        global
+            .tracer
+            .print_trace_start(&state, "UnicodePropertyName");
+        let result = state.parse_rule("UnicodePropertyName", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, UnicodePropertyName> {
+                    let result = UnicodePropertyName_impl::parse(state.clone(), global)?
+                        .map_with_state(|_, new_state| {
+                            let string = state.slice_until(new_state).to_owned();
+                            string
+                        });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod CharRangePart_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk { state, .. } = parse_character_literal(state, '\'').discard_result()?;
             let ParseOk {
@@ -1189,21 +2795,27 @@ mod peginator_generated {
         use super::CharRangePart as Parsed__override;
     }
     #[inline]
-    pub(super) fn parse_CharRangePart<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_CharRangePart<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, CharRangePart> {
         global.tracer.print_trace_start(&state, "CharRangePart");
-        let result = {
-            let result = CharRangePart_impl::parse(state, global)?;
-            Ok(result)
-        };
+        let result = state.parse_rule("CharRangePart", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, CharRangePart> {
+                    let result = CharRangePart_impl::parse(state, global)?;
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod StringLiteral_impl {
         use super::*;
-        mod part_1 {
+        mod part_2 {
             use super::*;
             mod choice_0 {
                 use super::*;
@@ -1216,32 +2828,39 @@ mod peginator_generated {
                             mod negative_lookahead {
                                 use super::*;
                                 #[inline(always)]
-                                pub fn parse<'a, TT: ParseTracer, TUD>(
+                                pub fn parse<'a, TT: ParseTracer>(
                                     state: ParseState<'a>,
-                                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                                 ) -> ParseResult<'a, Parsed> {
                                     parse_character_literal(state, '"').discard_result()
                                 }
                                 pub type Parsed = ();
                             }
                             #[inline(always)]
-                            pub fn parse<'a, TT: ParseTracer, TUD>(
+                            pub fn parse<'a, TT: ParseTracer>(
                                 state: ParseState<'a>,
-                                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                             ) -> ParseResult<'a, Parsed> {
                                 match negative_lookahead::parse(state.clone(), global) {
-                                    Ok(_) => Err(state.report_error(
-                                        ParseErrorSpecifics::NegativeLookaheadFailed,
-                                    )),
-                                    Err(_) => Ok(ParseOk { result: (), state }),
+                                    Ok(ParseOk {
+                                        state: inner_state, ..
+                                    }) => Err(state
+                                        .merge_examined(inner_state.examined_until())
+                                        .report_error(
+                                            ParseErrorSpecifics::NegativeLookaheadFailed,
+                                        )),
+                                    Err(err) => Ok(ParseOk {
+                                        result: (),
+                                        state: state.merge_examined(err.examined_until()),
+                                    }),
                                 }
                             }
                             pub type Parsed = ();
                         }
                         #[inline(always)]
-                        pub fn parse<'a, TT: ParseTracer, TUD>(
+                        pub fn parse<'a, TT: ParseTracer>(
                             state: ParseState<'a>,
-                            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                         ) -> ParseResult<'a, Parsed> {
                             let ParseOk { state, .. } = part_0::parse(state, global)?;
                             let ParseOk {
@@ -1257,11 +2876,10 @@ mod peginator_generated {
                         pub type Parsed = Vec<StringItem>;
                     }
                     #[inline(always)]
-                    pub fn parse<'a, TT: ParseTracer, TUD>(
+                    pub fn parse<'a, TT: ParseTracer>(
                         state: ParseState<'a>,
-                        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                     ) -> ParseResult<'a, Parsed> {
-                        let mut iterations: usize = 0;
                         let mut state = state;
                         let mut body: Vec<StringItem> = Vec::new();
                         loop {
@@ -1274,12 +2892,12 @@ mod peginator_generated {
                                     body.extend(__result);
                                     state = new_state;
                                 }
+                                Err(err) if err.is_committed() => return Err(err.uncommit()),
                                 Err(err) => {
                                     state = state.record_error(err);
                                     break;
                                 }
                             }
-                            iterations += 1;
                         }
                         Ok(ParseOk {
                             result: body,
@@ -1289,9 +2907,9 @@ mod peginator_generated {
                     pub type Parsed = Vec<StringItem>;
                 }
                 #[inline(always)]
-                pub fn parse<'a, TT: ParseTracer, TUD>(
+                pub fn parse<'a, TT: ParseTracer>(
                     state: ParseState<'a>,
-                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                 ) -> ParseResult<'a, Parsed> {
                     let ParseOk { state, .. } =
                         parse_character_literal(state, '"').discard_result()?;
@@ -1319,32 +2937,39 @@ mod peginator_generated {
                             mod negative_lookahead {
                                 use super::*;
                                 #[inline(always)]
-                                pub fn parse<'a, TT: ParseTracer, TUD>(
+                                pub fn parse<'a, TT: ParseTracer>(
                                     state: ParseState<'a>,
-                                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                                 ) -> ParseResult<'a, Parsed> {
                                     parse_character_literal(state, '\'').discard_result()
                                 }
                                 pub type Parsed = ();
                             }
                             #[inline(always)]
-                            pub fn parse<'a, TT: ParseTracer, TUD>(
+                            pub fn parse<'a, TT: ParseTracer>(
                                 state: ParseState<'a>,
-                                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                             ) -> ParseResult<'a, Parsed> {
                                 match negative_lookahead::parse(state.clone(), global) {
-                                    Ok(_) => Err(state.report_error(
-                                        ParseErrorSpecifics::NegativeLookaheadFailed,
-                                    )),
-                                    Err(_) => Ok(ParseOk { result: (), state }),
+                                    Ok(ParseOk {
+                                        state: inner_state, ..
+                                    }) => Err(state
+                                        .merge_examined(inner_state.examined_until())
+                                        .report_error(
+                                            ParseErrorSpecifics::NegativeLookaheadFailed,
+                                        )),
+                                    Err(err) => Ok(ParseOk {
+                                        result: (),
+                                        state: state.merge_examined(err.examined_until()),
+                                    }),
                                 }
                             }
                             pub type Parsed = ();
                         }
                         #[inline(always)]
-                        pub fn parse<'a, TT: ParseTracer, TUD>(
+                        pub fn parse<'a, TT: ParseTracer>(
                             state: ParseState<'a>,
-                            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                         ) -> ParseResult<'a, Parsed> {
                             let ParseOk { state, .. } = part_0::parse(state, global)?;
                             let ParseOk {
@@ -1360,11 +2985,10 @@ mod peginator_generated {
                         pub type Parsed = Vec<StringItem>;
                     }
                     #[inline(always)]
-                    pub fn parse<'a, TT: ParseTracer, TUD>(
+                    pub fn parse<'a, TT: ParseTracer>(
                         state: ParseState<'a>,
-                        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                     ) -> ParseResult<'a, Parsed> {
-                        let mut iterations: usize = 0;
                         let mut state = state;
                         let mut body: Vec<StringItem> = Vec::new();
                         loop {
@@ -1377,12 +3001,12 @@ mod peginator_generated {
                                     body.extend(__result);
                                     state = new_state;
                                 }
+                                Err(err) if err.is_committed() => return Err(err.uncommit()),
                                 Err(err) => {
                                     state = state.record_error(err);
                                     break;
                                 }
                             }
-                            iterations += 1;
                         }
                         Ok(ParseOk {
                             result: body,
@@ -1392,9 +3016,9 @@ mod peginator_generated {
                     pub type Parsed = Vec<StringItem>;
                 }
                 #[inline(always)]
-                pub fn parse<'a, TT: ParseTracer, TUD>(
+                pub fn parse<'a, TT: ParseTracer>(
                     state: ParseState<'a>,
-                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                 ) -> ParseResult<'a, Parsed> {
                     let ParseOk { state, .. } =
                         parse_character_literal(state, '\'').discard_result()?;
@@ -1412,9 +3036,9 @@ mod peginator_generated {
                 pub type Parsed = Vec<StringItem>;
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
                 ChoiceHelper::new(state)
                     .choice(|state| choice_0::parse(state, global))
@@ -1424,9 +3048,9 @@ mod peginator_generated {
             pub type Parsed = Vec<StringItem>;
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk {
                 result: insensitive,
@@ -1434,47 +3058,79 @@ mod peginator_generated {
             } = parse_CaseInsensitiveMarker(state.clone(), &mut *global)
                 .map_inner(Some)
                 .or_else(|err| {
-                    Ok(ParseOk {
-                        result: Default::default(),
-                        state: state.record_error(err),
-                    })
+                    if err.is_committed() {
+                        Err(err.uncommit())
+                    } else {
+                        Ok(ParseOk {
+                            result: Default::default(),
+                            state: state.record_error(err),
+                        })
+                    }
+                })?;
+            let ParseOk {
+                result: keyword,
+                state,
+            } = parse_KeywordMarker(state.clone(), &mut *global)
+                .map_inner(Some)
+                .or_else(|err| {
+                    if err.is_committed() {
+                        Err(err.uncommit())
+                    } else {
+                        Ok(ParseOk {
+                            result: Default::default(),
+                            state: state.record_error(err),
+                        })
+                    }
                 })?;
             let ParseOk {
                 result: mut body,
                 state,
-            } = part_1::parse(state, global)?;
+            } = part_2::parse(state, global)?;
             Ok(ParseOk {
-                result: Parsed { insensitive, body },
+                result: Parsed {
+                    insensitive,
+                    keyword,
+                    body,
+                },
                 state,
             })
         }
         pub struct Parsed {
             pub insensitive: Option<CaseInsensitiveMarker>,
+            pub keyword: Option<KeywordMarker>,
             pub body: Vec<StringItem>,
         }
     }
     #[inline]
-    pub(super) fn parse_StringLiteral<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_StringLiteral<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, StringLiteral> {
         global.tracer.print_trace_start(&state, "StringLiteral");
-        let result = {
-            let result = StringLiteral_impl::parse(state, global)?.map(|r| super::StringLiteral {
-                insensitive: r.insensitive,
-                body: r.body,
-            });
-            Ok(result)
-        };
+        let result = state.parse_rule("StringLiteral", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, StringLiteral> {
+                    let result =
+                        StringLiteral_impl::parse(state, global)?.map(|r| super::StringLiteral {
+                            insensitive: r.insensitive,
+                            keyword: r.keyword,
+                            body: r.body,
+                        });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod CaseInsensitiveMarker_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_character_literal(state, 'i'))
@@ -1483,18 +3139,57 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_CaseInsensitiveMarker<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_CaseInsensitiveMarker<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, CaseInsensitiveMarker> {
         global
             .tracer
             .print_trace_start(&state, "CaseInsensitiveMarker");
-        let result = {
-            let result = CaseInsensitiveMarker_impl::parse(state, global)?
-                .map(|r| super::CaseInsensitiveMarker {});
-            Ok(result)
-        };
+        let result = state.parse_rule("CaseInsensitiveMarker", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, CaseInsensitiveMarker> {
+                    let result = CaseInsensitiveMarker_impl::parse(state, global)?
+                        .map(|r| super::CaseInsensitiveMarker {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod KeywordMarker_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, 'k'))
+                .discard_result()
+        }
+        pub type Parsed = ();
+    }
+    #[inline]
+    pub(super) fn parse_KeywordMarker<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, KeywordMarker> {
+        global.tracer.print_trace_start(&state, "KeywordMarker");
+        let result = state.parse_rule("KeywordMarker", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, KeywordMarker> {
+                    let result =
+                        KeywordMarker_impl::parse(state, global)?.map(|r| super::KeywordMarker {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
@@ -1505,9 +3200,9 @@ mod peginator_generated {
             mod optional {
                 use super::*;
                 #[inline(always)]
-                pub fn parse<'a, TT: ParseTracer, TUD>(
+                pub fn parse<'a, TT: ParseTracer>(
                     state: ParseState<'a>,
-                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                 ) -> ParseResult<'a, Parsed> {
                     let ParseOk {
                         result: name,
@@ -1525,10 +3220,14 @@ mod peginator_generated {
                         .and_then(|ParseOk { state, .. }| parse_BoxMarker(state, &mut *global))
                         .map_inner(Some)
                         .or_else(|err| {
-                            Ok(ParseOk {
-                                result: Default::default(),
-                                state: state.record_error(err),
-                            })
+                            if err.is_committed() {
+                                Err(err.uncommit())
+                            } else {
+                                Ok(ParseOk {
+                                    result: Default::default(),
+                                    state: state.record_error(err),
+                                })
+                            }
                         })?;
                     Ok(ParseOk {
                         result: Parsed { name, boxed },
@@ -1541,9 +3240,9 @@ mod peginator_generated {
                 }
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
                 optional::parse(state.clone(), global)
                     .map_inner(|result| Parsed {
@@ -1551,13 +3250,17 @@ mod peginator_generated {
                         boxed: result.boxed,
                     })
                     .or_else(|err| {
-                        Ok(ParseOk {
-                            result: Parsed {
-                                name: Default::default(),
-                                boxed: Default::default(),
-                            },
-                            state: state.record_error(err),
-                        })
+                        if err.is_committed() {
+                            Err(err.uncommit())
+                        } else {
+                            Ok(ParseOk {
+                                result: Parsed {
+                                    name: Default::default(),
+                                    boxed: Default::default(),
+                                },
+                                state: state.record_error(err),
+                            })
+                        }
                     })
             }
             pub struct Parsed {
@@ -1566,9 +3269,9 @@ mod peginator_generated {
             }
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk {
                 result: part_0::Parsed { name, boxed },
@@ -1576,8 +3279,29 @@ mod peginator_generated {
             } = part_0::parse(state, global)?;
             let ParseOk { result: typ, state } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_Identifier(state, &mut *global))?;
+            let ParseOk {
+                result: type_arguments,
+                state,
+            } = parse_Whitespace(state.clone(), &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_TypeArguments(state, &mut *global))
+                .map_inner(Some)
+                .or_else(|err| {
+                    if err.is_committed() {
+                        Err(err.uncommit())
+                    } else {
+                        Ok(ParseOk {
+                            result: Default::default(),
+                            state: state.record_error(err),
+                        })
+                    }
+                })?;
             Ok(ParseOk {
-                result: Parsed { name, boxed, typ },
+                result: Parsed {
+                    name,
+                    boxed,
+                    typ,
+                    type_arguments,
+                },
                 state,
             })
         }
@@ -1585,31 +3309,39 @@ mod peginator_generated {
             pub name: Option<Identifier>,
             pub boxed: Option<BoxMarker>,
             pub typ: Identifier,
+            pub type_arguments: Option<TypeArguments>,
         }
     }
     #[inline]
-    pub(super) fn parse_Field<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_Field<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, Field> {
         global.tracer.print_trace_start(&state, "Field");
-        let result = {
-            let result = Field_impl::parse(state, global)?.map(|r| super::Field {
-                name: r.name,
-                boxed: r.boxed,
-                typ: r.typ,
-            });
-            Ok(result)
-        };
+        let result = state.parse_rule("Field", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Field> {
+                    let result = Field_impl::parse(state, global)?.map(|r| super::Field {
+                        name: r.name,
+                        boxed: r.boxed,
+                        typ: r.typ,
+                        type_arguments: r.type_arguments,
+                    });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod BoxMarker_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_character_literal(state, '*'))
@@ -1618,24 +3350,30 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_BoxMarker<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_BoxMarker<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, BoxMarker> {
         global.tracer.print_trace_start(&state, "BoxMarker");
-        let result = {
-            let result = BoxMarker_impl::parse(state, global)?.map(|r| super::BoxMarker {});
-            Ok(result)
-        };
+        let result = state.parse_rule("BoxMarker", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, BoxMarker> {
+                    let result = BoxMarker_impl::parse(state, global)?.map(|r| super::BoxMarker {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod OverrideField_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_character_literal(state, '@'))
@@ -1645,30 +3383,64 @@ mod peginator_generated {
                 .discard_result()?;
             let ParseOk { result: typ, state } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_Identifier(state, &mut *global))?;
-            Ok(ParseOk { result: typ, state })
+            let ParseOk {
+                result: type_arguments,
+                state,
+            } = parse_Whitespace(state.clone(), &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_TypeArguments(state, &mut *global))
+                .map_inner(Some)
+                .or_else(|err| {
+                    if err.is_committed() {
+                        Err(err.uncommit())
+                    } else {
+                        Ok(ParseOk {
+                            result: Default::default(),
+                            state: state.record_error(err),
+                        })
+                    }
+                })?;
+            Ok(ParseOk {
+                result: Parsed {
+                    typ,
+                    type_arguments,
+                },
+                state,
+            })
+        }
+        pub struct Parsed {
+            pub typ: Identifier,
+            pub type_arguments: Option<TypeArguments>,
         }
-        pub type Parsed = Identifier;
     }
     #[inline]
-    pub(super) fn parse_OverrideField<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_OverrideField<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, OverrideField> {
         global.tracer.print_trace_start(&state, "OverrideField");
-        let result = {
-            let result =
-                OverrideField_impl::parse(state, global)?.map(|r| super::OverrideField { typ: r });
-            Ok(result)
-        };
+        let result = state.parse_rule("OverrideField", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, OverrideField> {
+                    let result =
+                        OverrideField_impl::parse(state, global)?.map(|r| super::OverrideField {
+                            typ: r.typ,
+                            type_arguments: r.type_arguments,
+                        });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod IncludeRule_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_character_literal(state, '>'))
@@ -1678,33 +3450,232 @@ mod peginator_generated {
                 state,
             } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_Identifier(state, &mut *global))?;
+            let ParseOk {
+                result: type_arguments,
+                state,
+            } = parse_Whitespace(state.clone(), &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_TypeArguments(state, &mut *global))
+                .map_inner(Some)
+                .or_else(|err| {
+                    if err.is_committed() {
+                        Err(err.uncommit())
+                    } else {
+                        Ok(ParseOk {
+                            result: Default::default(),
+                            state: state.record_error(err),
+                        })
+                    }
+                })?;
             Ok(ParseOk {
-                result: rule,
+                result: Parsed {
+                    rule,
+                    type_arguments,
+                },
                 state,
             })
         }
-        pub type Parsed = Identifier;
+        pub struct Parsed {
+            pub rule: Identifier,
+            pub type_arguments: Option<TypeArguments>,
+        }
     }
     #[inline]
-    pub(super) fn parse_IncludeRule<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_IncludeRule<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, IncludeRule> {
         global.tracer.print_trace_start(&state, "IncludeRule");
-        let result = {
-            let result =
-                IncludeRule_impl::parse(state, global)?.map(|r| super::IncludeRule { rule: r });
-            Ok(result)
-        };
+        let result = state.parse_rule("IncludeRule", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, IncludeRule> {
+                    let result =
+                        IncludeRule_impl::parse(state, global)?.map(|r| super::IncludeRule {
+                            rule: r.rule,
+                            type_arguments: r.type_arguments,
+                        });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod TypeArguments_impl {
+        use super::*;
+        mod part_1 {
+            use super::*;
+            mod closure {
+                use super::*;
+            }
+            #[inline(always)]
+            pub fn parse<'a, TT: ParseTracer>(
+                state: ParseState<'a>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+            ) -> ParseResult<'a, Parsed> {
+                let mut iterations: usize = 0;
+                let mut state = state;
+                let mut arguments: Vec<TypeReference> = Vec::new();
+                loop {
+                    let before_separator = state.clone();
+                    if iterations > 0 {
+                        match parse_Whitespace(state.clone(), &mut *global)
+                            .and_then(|ParseOk { state, .. }| parse_character_literal(state, ','))
+                            .discard_result()
+                        {
+                            Ok(ParseOk {
+                                state: new_state, ..
+                            }) => state = new_state,
+                            Err(err) if err.is_committed() => return Err(err.uncommit()),
+                            Err(err) => {
+                                state = state.record_error(err);
+                                break;
+                            }
+                        }
+                    }
+                    match parse_Whitespace(state.clone(), &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_TypeReference(state, &mut *global))
+                        .map_inner(|result| vec![result])
+                    {
+                        Ok(ParseOk {
+                            result: __result,
+                            state: new_state,
+                            ..
+                        }) => {
+                            arguments.extend(__result);
+                            state = new_state;
+                        }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
+                        Err(err) => {
+                            state = state.rewind_to(&before_separator).record_error(err);
+                            break;
+                        }
+                    }
+                    iterations += 1;
+                }
+                if iterations == 0 {
+                    return Err(state.report_farthest_error());
+                }
+                Ok(ParseOk {
+                    result: arguments,
+                    state,
+                })
+            }
+            pub type Parsed = Vec<TypeReference>;
+        }
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '<'))
+                .discard_result()?;
+            let ParseOk {
+                result: mut arguments,
+                state,
+            } = part_1::parse(state, global)?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '>'))
+                .discard_result()?;
+            Ok(ParseOk {
+                result: arguments,
+                state,
+            })
+        }
+        pub type Parsed = Vec<TypeReference>;
+    }
+    #[inline]
+    pub(super) fn parse_TypeArguments<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, TypeArguments> {
+        global.tracer.print_trace_start(&state, "TypeArguments");
+        let result = state.parse_rule("TypeArguments", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, TypeArguments> {
+                    let result = TypeArguments_impl::parse(state, global)?
+                        .map(|r| super::TypeArguments { arguments: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod TypeReference_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk {
+                result: name,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_Identifier(state, &mut *global))?;
+            let ParseOk {
+                result: type_arguments,
+                state,
+            } = parse_Whitespace(state.clone(), &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_TypeArguments(state, &mut *global))
+                .map_inner(|result| Some(Box::new(result)))
+                .or_else(|err| {
+                    if err.is_committed() {
+                        Err(err.uncommit())
+                    } else {
+                        Ok(ParseOk {
+                            result: Default::default(),
+                            state: state.record_error(err),
+                        })
+                    }
+                })?;
+            Ok(ParseOk {
+                result: Parsed {
+                    name,
+                    type_arguments,
+                },
+                state,
+            })
+        }
+        pub struct Parsed {
+            pub name: Identifier,
+            pub type_arguments: Option<Box<TypeArguments>>,
+        }
+    }
+    #[inline]
+    pub(super) fn parse_TypeReference<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, TypeReference> {
+        global.tracer.print_trace_start(&state, "TypeReference");
+        let result = state.parse_rule("TypeReference", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, TypeReference> {
+                    let result =
+                        TypeReference_impl::parse(state, global)?.map(|r| super::TypeReference {
+                            name: r.name,
+                            type_arguments: r.type_arguments,
+                        });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod DelimitedExpression_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             ChoiceHelper::new(state)
                 .choice(|state| {
@@ -1732,6 +3703,11 @@ mod peginator_generated {
                         .and_then(|ParseOk { state, .. }| parse_PositiveLookahead(state, global))
                         .map_inner(Parsed__override::PositiveLookahead)
                 })
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_UnicodeClass(state, global))
+                        .map_inner(Parsed__override::UnicodeClass)
+                })
                 .choice(|state| {
                     parse_Whitespace(state, &mut *global)
                         .and_then(|ParseOk { state, .. }| parse_CharacterRange(state, global))
@@ -1747,6 +3723,11 @@ mod peginator_generated {
                         .and_then(|ParseOk { state, .. }| parse_EndOfInput(state, global))
                         .map_inner(Parsed__override::EndOfInput)
                 })
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_Cut(state, global))
+                        .map_inner(Parsed__override::Cut)
+                })
                 .choice(|state| {
                     parse_Whitespace(state, &mut *global)
                         .and_then(|ParseOk { state, .. }| parse_OverrideField(state, global))
@@ -1768,17 +3749,23 @@ mod peginator_generated {
         use super::DelimitedExpression as Parsed__override;
     }
     #[inline]
-    pub(super) fn parse_DelimitedExpression<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_DelimitedExpression<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, DelimitedExpression> {
         global
             .tracer
             .print_trace_start(&state, "DelimitedExpression");
-        let result = {
-            let result = DelimitedExpression_impl::parse(state, global)?;
-            Ok(result)
-        };
+        let result = state.parse_rule("DelimitedExpression", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, DelimitedExpression> {
+                    let result = DelimitedExpression_impl::parse(state, global)?;
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
@@ -1788,9 +3775,9 @@ mod peginator_generated {
             use super::*;
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let mut iterations: usize = 0;
             let mut state = state;
@@ -1803,6 +3790,7 @@ mod peginator_generated {
                     }) => {
                         state = new_state;
                     }
+                    Err(err) if err.is_committed() => return Err(err.uncommit()),
                     Err(err) => {
                         state = state.record_error(err);
                         break;
@@ -1818,26 +3806,33 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_Identifier<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_Identifier<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, Identifier> {
         global.tracer.print_trace_start(&state, "Identifier");
-        let result = {
-            let result =
-                Identifier_impl::parse(state.clone(), global)?.map_with_state(|_, new_state| {
-                    let string = state.slice_until(new_state).to_string();
-                    string
-                });
-            Ok(result)
-        };
+        let result = state.parse_rule("Identifier", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Identifier> {
+                    let result = Identifier_impl::parse(state.clone(), global)?.map_with_state(
+                        |_, new_state| {
+                            let string = state.slice_until(new_state).to_owned();
+                            string
+                        },
+                    );
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     #[inline]
-    pub(super) fn parse_IdentifierChar<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_IdentifierChar<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, IdentifierChar> {
         if let Ok(result) = parse_character_range(state.clone(), 'a', 'z') {
             return Ok(result);
@@ -1862,9 +3857,9 @@ mod peginator_generated {
         mod choice_0 {
             use super::*;
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
                 let ParseOk { state, .. } =
                     parse_character_literal(state, '\\').discard_result()?;
@@ -1896,32 +3891,37 @@ mod peginator_generated {
                 mod negative_lookahead {
                     use super::*;
                     #[inline(always)]
-                    pub fn parse<'a, TT: ParseTracer, TUD>(
+                    pub fn parse<'a, TT: ParseTracer>(
                         state: ParseState<'a>,
-                        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                     ) -> ParseResult<'a, Parsed> {
                         parse_character_literal(state, '\\').discard_result()
                     }
                     pub type Parsed = ();
                 }
                 #[inline(always)]
-                pub fn parse<'a, TT: ParseTracer, TUD>(
+                pub fn parse<'a, TT: ParseTracer>(
                     state: ParseState<'a>,
-                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                 ) -> ParseResult<'a, Parsed> {
                     match negative_lookahead::parse(state.clone(), global) {
-                        Ok(_) => {
-                            Err(state.report_error(ParseErrorSpecifics::NegativeLookaheadFailed))
-                        }
-                        Err(_) => Ok(ParseOk { result: (), state }),
+                        Ok(ParseOk {
+                            state: inner_state, ..
+                        }) => Err(state
+                            .merge_examined(inner_state.examined_until())
+                            .report_error(ParseErrorSpecifics::NegativeLookaheadFailed)),
+                        Err(err) => Ok(ParseOk {
+                            result: (),
+                            state: state.merge_examined(err.examined_until()),
+                        }),
                     }
                 }
                 pub type Parsed = ();
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
                 let ParseOk { state, .. } = part_0::parse(state, global)?;
                 let ParseOk {
@@ -1936,9 +3936,9 @@ mod peginator_generated {
             pub type Parsed = Parsed__override;
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             ChoiceHelper::new(state)
                 .choice(|state| choice_0::parse(state, global))
@@ -1949,24 +3949,30 @@ mod peginator_generated {
         use super::StringItem as Parsed__override;
     }
     #[inline]
-    pub(super) fn parse_StringItem<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_StringItem<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, StringItem> {
         global.tracer.print_trace_start(&state, "StringItem");
-        let result = {
-            let result = StringItem_impl::parse(state, global)?;
-            Ok(result)
-        };
+        let result = state.parse_rule("StringItem", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, StringItem> {
+                    let result = StringItem_impl::parse(state, global)?;
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod SimpleEscape_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             ChoiceHelper::new(state)
                 .choice(|state| {
@@ -1999,182 +4005,228 @@ mod peginator_generated {
         use super::SimpleEscape as Parsed__override;
     }
     #[inline]
-    pub(super) fn parse_SimpleEscape<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_SimpleEscape<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, SimpleEscape> {
         global.tracer.print_trace_start(&state, "SimpleEscape");
-        let result = {
-            let result = SimpleEscape_impl::parse(state, global)?;
-            Ok(result)
-        };
+        let result = state.parse_rule("SimpleEscape", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, SimpleEscape> {
+                    let result = SimpleEscape_impl::parse(state, global)?;
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod SimpleEscapeNewline_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_character_literal(state, 'n').discard_result()
         }
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_SimpleEscapeNewline<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_SimpleEscapeNewline<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, SimpleEscapeNewline> {
         global
             .tracer
             .print_trace_start(&state, "SimpleEscapeNewline");
-        let result = {
-            let result = SimpleEscapeNewline_impl::parse(state, global)?
-                .map(|r| super::SimpleEscapeNewline {});
-            Ok(result)
-        };
+        let result = state.parse_rule("SimpleEscapeNewline", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, SimpleEscapeNewline> {
+                    let result = SimpleEscapeNewline_impl::parse(state, global)?
+                        .map(|r| super::SimpleEscapeNewline {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod SimpleEscapeCarriageReturn_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_character_literal(state, 'r').discard_result()
         }
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_SimpleEscapeCarriageReturn<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_SimpleEscapeCarriageReturn<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, SimpleEscapeCarriageReturn> {
         global
             .tracer
             .print_trace_start(&state, "SimpleEscapeCarriageReturn");
-        let result = {
-            let result = SimpleEscapeCarriageReturn_impl::parse(state, global)?
-                .map(|r| super::SimpleEscapeCarriageReturn {});
-            Ok(result)
-        };
+        let result = state.parse_rule(
+            "SimpleEscapeCarriageReturn",
+            global.record_context,
+            |state| {
+                let result = {
+                    (|| -> ParseResult<'a, SimpleEscapeCarriageReturn> {
+                        let result = SimpleEscapeCarriageReturn_impl::parse(state, global)?
+                            .map(|r| super::SimpleEscapeCarriageReturn {});
+                        Ok(result)
+                    })()
+                    .map_err(ParseFailure::uncommit)
+                };
+                result
+            },
+        );
         global.tracer.print_trace_result(&result);
         result
     }
     mod SimpleEscapeTab_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_character_literal(state, 't').discard_result()
         }
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_SimpleEscapeTab<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_SimpleEscapeTab<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, SimpleEscapeTab> {
         global.tracer.print_trace_start(&state, "SimpleEscapeTab");
-        let result = {
-            let result =
-                SimpleEscapeTab_impl::parse(state, global)?.map(|r| super::SimpleEscapeTab {});
-            Ok(result)
-        };
+        let result = state.parse_rule("SimpleEscapeTab", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, SimpleEscapeTab> {
+                    let result = SimpleEscapeTab_impl::parse(state, global)?
+                        .map(|r| super::SimpleEscapeTab {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod SimpleEscapeBackslash_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_character_literal(state, '\\').discard_result()
         }
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_SimpleEscapeBackslash<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_SimpleEscapeBackslash<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, SimpleEscapeBackslash> {
         global
             .tracer
             .print_trace_start(&state, "SimpleEscapeBackslash");
-        let result = {
-            let result = SimpleEscapeBackslash_impl::parse(state, global)?
-                .map(|r| super::SimpleEscapeBackslash {});
-            Ok(result)
-        };
+        let result = state.parse_rule("SimpleEscapeBackslash", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, SimpleEscapeBackslash> {
+                    let result = SimpleEscapeBackslash_impl::parse(state, global)?
+                        .map(|r| super::SimpleEscapeBackslash {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod SimpleEscapeQuote_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_character_literal(state, '\'').discard_result()
         }
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_SimpleEscapeQuote<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_SimpleEscapeQuote<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, SimpleEscapeQuote> {
         global.tracer.print_trace_start(&state, "SimpleEscapeQuote");
-        let result = {
-            let result =
-                SimpleEscapeQuote_impl::parse(state, global)?.map(|r| super::SimpleEscapeQuote {});
-            Ok(result)
-        };
+        let result = state.parse_rule("SimpleEscapeQuote", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, SimpleEscapeQuote> {
+                    let result = SimpleEscapeQuote_impl::parse(state, global)?
+                        .map(|r| super::SimpleEscapeQuote {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod SimpleEscapeDQuote_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_character_literal(state, '"').discard_result()
         }
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_SimpleEscapeDQuote<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_SimpleEscapeDQuote<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, SimpleEscapeDQuote> {
         global
             .tracer
             .print_trace_start(&state, "SimpleEscapeDQuote");
-        let result = {
-            let result = SimpleEscapeDQuote_impl::parse(state, global)?
-                .map(|r| super::SimpleEscapeDQuote {});
-            Ok(result)
-        };
+        let result = state.parse_rule("SimpleEscapeDQuote", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, SimpleEscapeDQuote> {
+                    let result = SimpleEscapeDQuote_impl::parse(state, global)?
+                        .map(|r| super::SimpleEscapeDQuote {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod HexaEscape_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk { state, .. } = parse_character_literal(state, 'x').discard_result()?;
             let ParseOk { result: c1, state } = parse_HexChar(state, &mut *global)?;
@@ -2190,23 +4242,29 @@ mod peginator_generated {
         }
     }
     #[inline]
-    pub(super) fn parse_HexaEscape<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_HexaEscape<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, HexaEscape> {
         global.tracer.print_trace_start(&state, "HexaEscape");
-        let result = {
-            let result = HexaEscape_impl::parse(state, global)?
-                .map(|r| super::HexaEscape { c1: r.c1, c2: r.c2 });
-            Ok(result)
-        };
+        let result = state.parse_rule("HexaEscape", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, HexaEscape> {
+                    let result = HexaEscape_impl::parse(state, global)?
+                        .map(|r| super::HexaEscape { c1: r.c1, c2: r.c2 });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     #[inline]
-    pub(super) fn parse_HexChar<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_HexChar<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, HexChar> {
         if let Ok(result) = parse_character_range(state.clone(), '0', '9') {
             return Ok(result);
@@ -2223,415 +4281,257 @@ mod peginator_generated {
         use super::*;
         mod choice_0 {
             use super::*;
-            mod part_3 {
+            mod part_2 {
                 use super::*;
-                mod optional {
+                mod closure {
                     use super::*;
-                    mod part_1 {
-                        use super::*;
-                        mod optional {
-                            use super::*;
-                            mod part_1 {
-                                use super::*;
-                                mod optional {
-                                    use super::*;
-                                    mod part_1 {
-                                        use super::*;
-                                        mod optional {
-                                            use super::*;
-                                            #[inline(always)]
-                                            pub fn parse<'a, TT: ParseTracer, TUD>(
-                                                state: ParseState<'a>,
-                                                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-                                            ) -> ParseResult<'a, Parsed>
-                                            {
-                                                let ParseOk { result: c5, state } =
-                                                    parse_HexChar(state, &mut *global)
-                                                        .map_inner(Some)?;
-                                                let ParseOk { result: c6, state } =
-                                                    parse_HexChar(state.clone(), &mut *global)
-                                                        .map_inner(Some)
-                                                        .or_else(|err| {
-                                                            Ok(ParseOk {
-                                                                result: Default::default(),
-                                                                state: state.record_error(err),
-                                                            })
-                                                        })?;
-                                                Ok(ParseOk {
-                                                    result: Parsed { c5, c6 },
-                                                    state,
-                                                })
-                                            }
-                                            pub struct Parsed {
-                                                pub c5: Option<HexChar>,
-                                                pub c6: Option<HexChar>,
-                                            }
-                                        }
-                                        #[inline(always)]
-                                        pub fn parse<'a, TT: ParseTracer, TUD>(
-                                            state: ParseState<'a>,
-                                            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-                                        ) -> ParseResult<'a, Parsed>
-                                        {
-                                            optional::parse(state.clone(), global)
-                                                .map_inner(|result| Parsed {
-                                                    c5: result.c5,
-                                                    c6: result.c6,
-                                                })
-                                                .or_else(|err| {
-                                                    Ok(ParseOk {
-                                                        result: Parsed {
-                                                            c5: Default::default(),
-                                                            c6: Default::default(),
-                                                        },
-                                                        state: state.record_error(err),
-                                                    })
-                                                })
-                                        }
-                                        pub struct Parsed {
-                                            pub c5: Option<HexChar>,
-                                            pub c6: Option<HexChar>,
-                                        }
-                                    }
-                                    #[inline(always)]
-                                    pub fn parse<'a, TT: ParseTracer, TUD>(
-                                        state: ParseState<'a>,
-                                        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-                                    ) -> ParseResult<'a, Parsed>
-                                    {
-                                        let ParseOk { result: c4, state } =
-                                            parse_HexChar(state, &mut *global).map_inner(Some)?;
-                                        let ParseOk {
-                                            result: part_1::Parsed { c5, c6 },
-                                            state,
-                                        } = part_1::parse(state, global)?;
-                                        Ok(ParseOk {
-                                            result: Parsed { c4, c5, c6 },
-                                            state,
-                                        })
-                                    }
-                                    pub struct Parsed {
-                                        pub c4: Option<HexChar>,
-                                        pub c5: Option<HexChar>,
-                                        pub c6: Option<HexChar>,
-                                    }
-                                }
-                                #[inline(always)]
-                                pub fn parse<'a, TT: ParseTracer, TUD>(
-                                    state: ParseState<'a>,
-                                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-                                ) -> ParseResult<'a, Parsed> {
-                                    optional::parse(state.clone(), global)
-                                        .map_inner(|result| Parsed {
-                                            c4: result.c4,
-                                            c5: result.c5,
-                                            c6: result.c6,
-                                        })
-                                        .or_else(|err| {
-                                            Ok(ParseOk {
-                                                result: Parsed {
-                                                    c4: Default::default(),
-                                                    c5: Default::default(),
-                                                    c6: Default::default(),
-                                                },
-                                                state: state.record_error(err),
-                                            })
-                                        })
-                                }
-                                pub struct Parsed {
-                                    pub c4: Option<HexChar>,
-                                    pub c5: Option<HexChar>,
-                                    pub c6: Option<HexChar>,
-                                }
-                            }
-                            #[inline(always)]
-                            pub fn parse<'a, TT: ParseTracer, TUD>(
-                                state: ParseState<'a>,
-                                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-                            ) -> ParseResult<'a, Parsed> {
-                                let ParseOk { result: c3, state } =
-                                    parse_HexChar(state, &mut *global).map_inner(Some)?;
-                                let ParseOk {
-                                    result: part_1::Parsed { c4, c5, c6 },
-                                    state,
-                                } = part_1::parse(state, global)?;
-                                Ok(ParseOk {
-                                    result: Parsed { c3, c4, c5, c6 },
-                                    state,
-                                })
-                            }
-                            pub struct Parsed {
-                                pub c3: Option<HexChar>,
-                                pub c4: Option<HexChar>,
-                                pub c5: Option<HexChar>,
-                                pub c6: Option<HexChar>,
-                            }
-                        }
-                        #[inline(always)]
-                        pub fn parse<'a, TT: ParseTracer, TUD>(
-                            state: ParseState<'a>,
-                            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-                        ) -> ParseResult<'a, Parsed> {
-                            optional::parse(state.clone(), global)
-                                .map_inner(|result| Parsed {
-                                    c3: result.c3,
-                                    c4: result.c4,
-                                    c5: result.c5,
-                                    c6: result.c6,
-                                })
-                                .or_else(|err| {
-                                    Ok(ParseOk {
-                                        result: Parsed {
-                                            c3: Default::default(),
-                                            c4: Default::default(),
-                                            c5: Default::default(),
-                                            c6: Default::default(),
-                                        },
-                                        state: state.record_error(err),
-                                    })
-                                })
-                        }
-                        pub struct Parsed {
-                            pub c3: Option<HexChar>,
-                            pub c4: Option<HexChar>,
-                            pub c5: Option<HexChar>,
-                            pub c6: Option<HexChar>,
-                        }
-                    }
-                    #[inline(always)]
-                    pub fn parse<'a, TT: ParseTracer, TUD>(
-                        state: ParseState<'a>,
-                        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
-                    ) -> ParseResult<'a, Parsed> {
-                        let ParseOk { result: c2, state } =
-                            parse_HexChar(state, &mut *global).map_inner(Some)?;
-                        let ParseOk {
-                            result: part_1::Parsed { c3, c4, c5, c6 },
-                            state,
-                        } = part_1::parse(state, global)?;
-                        Ok(ParseOk {
-                            result: Parsed { c2, c3, c4, c5, c6 },
-                            state,
-                        })
-                    }
-                    pub struct Parsed {
-                        pub c2: Option<HexChar>,
-                        pub c3: Option<HexChar>,
-                        pub c4: Option<HexChar>,
-                        pub c5: Option<HexChar>,
-                        pub c6: Option<HexChar>,
-                    }
                 }
                 #[inline(always)]
-                pub fn parse<'a, TT: ParseTracer, TUD>(
+                pub fn parse<'a, TT: ParseTracer>(
                     state: ParseState<'a>,
-                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                 ) -> ParseResult<'a, Parsed> {
-                    optional::parse(state.clone(), global)
-                        .map_inner(|result| Parsed {
-                            c2: result.c2,
-                            c3: result.c3,
-                            c4: result.c4,
-                            c5: result.c5,
-                            c6: result.c6,
-                        })
-                        .or_else(|err| {
+                    let mut iterations: usize = 0;
+                    let mut state = state;
+                    let mut digits: Vec<HexChar> = Vec::new();
+                    loop {
+                        if iterations == 6usize {
+                            break;
+                        }
+                        match parse_HexChar(state.clone(), &mut *global)
+                            .map_inner(|result| vec![result])
+                        {
                             Ok(ParseOk {
-                                result: Parsed {
-                                    c2: Default::default(),
-                                    c3: Default::default(),
-                                    c4: Default::default(),
-                                    c5: Default::default(),
-                                    c6: Default::default(),
-                                },
-                                state: state.record_error(err),
-                            })
-                        })
-                }
-                pub struct Parsed {
-                    pub c2: Option<HexChar>,
-                    pub c3: Option<HexChar>,
-                    pub c4: Option<HexChar>,
-                    pub c5: Option<HexChar>,
-                    pub c6: Option<HexChar>,
+                                result: __result,
+                                state: new_state,
+                                ..
+                            }) => {
+                                digits.extend(__result);
+                                state = new_state;
+                            }
+                            Err(err) if err.is_committed() => return Err(err.uncommit()),
+                            Err(err) => {
+                                state = state.record_error(err);
+                                break;
+                            }
+                        }
+                        iterations += 1;
+                    }
+                    if iterations < 1usize {
+                        return Err(
+                            state.report_error(ParseErrorSpecifics::ExpectedRepetitions {
+                                min: 1usize,
+                                matched: iterations,
+                            }),
+                        );
+                    }
+                    Ok(ParseOk {
+                        result: digits,
+                        state,
+                    })
                 }
+                pub type Parsed = Vec<HexChar>;
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
                 let ParseOk { state, .. } = parse_character_literal(state, 'u').discard_result()?;
                 let ParseOk { state, .. } = parse_character_literal(state, '{').discard_result()?;
-                let ParseOk { result: c1, state } = parse_HexChar(state, &mut *global)?;
                 let ParseOk {
-                    result: part_3::Parsed { c2, c3, c4, c5, c6 },
+                    result: mut digits,
                     state,
-                } = part_3::parse(state, global)?;
+                } = part_2::parse(state, global)?;
                 let ParseOk { state, .. } = parse_character_literal(state, '}').discard_result()?;
                 Ok(ParseOk {
-                    result: Parsed {
-                        c1,
-                        c2,
-                        c3,
-                        c4,
-                        c5,
-                        c6,
-                    },
+                    result: digits,
                     state,
                 })
             }
-            pub struct Parsed {
-                pub c1: HexChar,
-                pub c2: Option<HexChar>,
-                pub c3: Option<HexChar>,
-                pub c4: Option<HexChar>,
-                pub c5: Option<HexChar>,
-                pub c6: Option<HexChar>,
-            }
+            pub type Parsed = Vec<HexChar>;
         }
         mod choice_1 {
             use super::*;
+            mod part_1 {
+                use super::*;
+                mod closure {
+                    use super::*;
+                }
+                #[inline(always)]
+                pub fn parse<'a, TT: ParseTracer>(
+                    state: ParseState<'a>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+                ) -> ParseResult<'a, Parsed> {
+                    let mut iterations: usize = 0;
+                    let mut state = state;
+                    let mut digits: Vec<HexChar> = Vec::new();
+                    loop {
+                        if iterations == 4usize {
+                            break;
+                        }
+                        match parse_HexChar(state.clone(), &mut *global)
+                            .map_inner(|result| vec![result])
+                        {
+                            Ok(ParseOk {
+                                result: __result,
+                                state: new_state,
+                                ..
+                            }) => {
+                                digits.extend(__result);
+                                state = new_state;
+                            }
+                            Err(err) if err.is_committed() => return Err(err.uncommit()),
+                            Err(err) => {
+                                state = state.record_error(err);
+                                break;
+                            }
+                        }
+                        iterations += 1;
+                    }
+                    if iterations < 4usize {
+                        return Err(
+                            state.report_error(ParseErrorSpecifics::ExpectedRepetitions {
+                                min: 4usize,
+                                matched: iterations,
+                            }),
+                        );
+                    }
+                    Ok(ParseOk {
+                        result: digits,
+                        state,
+                    })
+                }
+                pub type Parsed = Vec<HexChar>;
+            }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
                 let ParseOk { state, .. } = parse_character_literal(state, 'u').discard_result()?;
-                let ParseOk { result: c1, state } = parse_HexChar(state, &mut *global)?;
-                let ParseOk { result: c2, state } =
-                    parse_HexChar(state, &mut *global).map_inner(Some)?;
-                let ParseOk { result: c3, state } =
-                    parse_HexChar(state, &mut *global).map_inner(Some)?;
-                let ParseOk { result: c4, state } =
-                    parse_HexChar(state, &mut *global).map_inner(Some)?;
+                let ParseOk {
+                    result: mut digits,
+                    state,
+                } = part_1::parse(state, global)?;
                 Ok(ParseOk {
-                    result: Parsed { c1, c2, c3, c4 },
+                    result: digits,
                     state,
                 })
             }
-            pub struct Parsed {
-                pub c1: HexChar,
-                pub c2: Option<HexChar>,
-                pub c3: Option<HexChar>,
-                pub c4: Option<HexChar>,
+            pub type Parsed = Vec<HexChar>;
+        }
+        mod choice_2 {
+            use super::*;
+            mod part_3 {
+                use super::*;
+                mod closure {
+                    use super::*;
+                }
+                #[inline(always)]
+                pub fn parse<'a, TT: ParseTracer>(
+                    state: ParseState<'a>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+                ) -> ParseResult<'a, Parsed> {
+                    let mut iterations: usize = 0;
+                    let mut state = state;
+                    let mut digits: Vec<HexChar> = Vec::new();
+                    loop {
+                        if iterations == 6usize {
+                            break;
+                        }
+                        match parse_HexChar(state.clone(), &mut *global)
+                            .map_inner(|result| vec![result])
+                        {
+                            Ok(ParseOk {
+                                result: __result,
+                                state: new_state,
+                                ..
+                            }) => {
+                                digits.extend(__result);
+                                state = new_state;
+                            }
+                            Err(err) if err.is_committed() => return Err(err.uncommit()),
+                            Err(err) => {
+                                state = state.record_error(err);
+                                break;
+                            }
+                        }
+                        iterations += 1;
+                    }
+                    if iterations < 6usize {
+                        return Err(
+                            state.report_error(ParseErrorSpecifics::ExpectedRepetitions {
+                                min: 6usize,
+                                matched: iterations,
+                            }),
+                        );
+                    }
+                    Ok(ParseOk {
+                        result: digits,
+                        state,
+                    })
+                }
+                pub type Parsed = Vec<HexChar>;
             }
-        }
-        mod choice_2 {
-            use super::*;
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
                 let ParseOk { state, .. } = parse_character_literal(state, 'U').discard_result()?;
                 let ParseOk { state, .. } = parse_character_literal(state, '0').discard_result()?;
                 let ParseOk { state, .. } = parse_character_literal(state, '0').discard_result()?;
-                let ParseOk { result: c1, state } = parse_HexChar(state, &mut *global)?;
-                let ParseOk { result: c2, state } =
-                    parse_HexChar(state, &mut *global).map_inner(Some)?;
-                let ParseOk { result: c3, state } =
-                    parse_HexChar(state, &mut *global).map_inner(Some)?;
-                let ParseOk { result: c4, state } =
-                    parse_HexChar(state, &mut *global).map_inner(Some)?;
-                let ParseOk { result: c5, state } =
-                    parse_HexChar(state, &mut *global).map_inner(Some)?;
-                let ParseOk { result: c6, state } =
-                    parse_HexChar(state, &mut *global).map_inner(Some)?;
+                let ParseOk {
+                    result: mut digits,
+                    state,
+                } = part_3::parse(state, global)?;
                 Ok(ParseOk {
-                    result: Parsed {
-                        c1,
-                        c2,
-                        c3,
-                        c4,
-                        c5,
-                        c6,
-                    },
+                    result: digits,
                     state,
                 })
             }
-            pub struct Parsed {
-                pub c1: HexChar,
-                pub c2: Option<HexChar>,
-                pub c3: Option<HexChar>,
-                pub c4: Option<HexChar>,
-                pub c5: Option<HexChar>,
-                pub c6: Option<HexChar>,
-            }
+            pub type Parsed = Vec<HexChar>;
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             ChoiceHelper::new(state)
-                .choice(|state| {
-                    choice_0::parse(state, global).map_inner(|r| Parsed {
-                        c1: r.c1,
-                        c2: r.c2,
-                        c3: r.c3,
-                        c4: r.c4,
-                        c5: r.c5,
-                        c6: r.c6,
-                    })
-                })
-                .choice(|state| {
-                    choice_1::parse(state, global).map_inner(|r| Parsed {
-                        c1: r.c1,
-                        c2: r.c2,
-                        c3: r.c3,
-                        c4: r.c4,
-                        c5: None,
-                        c6: None,
-                    })
-                })
-                .choice(|state| {
-                    choice_2::parse(state, global).map_inner(|r| Parsed {
-                        c1: r.c1,
-                        c2: r.c2,
-                        c3: r.c3,
-                        c4: r.c4,
-                        c5: r.c5,
-                        c6: r.c6,
-                    })
-                })
+                .choice(|state| choice_0::parse(state, global))
+                .choice(|state| choice_1::parse(state, global))
+                .choice(|state| choice_2::parse(state, global))
                 .end()
         }
-        pub struct Parsed {
-            pub c1: HexChar,
-            pub c2: Option<HexChar>,
-            pub c3: Option<HexChar>,
-            pub c4: Option<HexChar>,
-            pub c5: Option<HexChar>,
-            pub c6: Option<HexChar>,
-        }
+        pub type Parsed = Vec<HexChar>;
     }
     #[inline]
-    pub(super) fn parse_Utf8Escape<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_Utf8Escape<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, Utf8Escape> {
         global.tracer.print_trace_start(&state, "Utf8Escape");
-        let result = {
-            let result = Utf8Escape_impl::parse(state, global)?.map(|r| super::Utf8Escape {
-                c1: r.c1,
-                c2: r.c2,
-                c3: r.c3,
-                c4: r.c4,
-                c5: r.c5,
-                c6: r.c6,
-            });
-            Ok(result)
-        };
+        let result = state.parse_rule("Utf8Escape", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Utf8Escape> {
+                    let result = Utf8Escape_impl::parse(state, global)?
+                        .map(|r| super::Utf8Escape { digits: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod DirectiveExpression_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             ChoiceHelper::new(state)
                 .choice(|state| {
@@ -2669,32 +4569,53 @@ mod peginator_generated {
                         .and_then(|ParseOk { state, .. }| parse_CheckDirective(state, global))
                         .map_inner(Parsed__override::CheckDirective)
                 })
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_RecoverDirective(state, global))
+                        .map_inner(Parsed__override::RecoverDirective)
+                })
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_NameDirective(state, global))
+                        .map_inner(Parsed__override::NameDirective)
+                })
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_NotKeywordDirective(state, global))
+                        .map_inner(Parsed__override::NotKeywordDirective)
+                })
                 .end()
         }
         pub type Parsed = Parsed__override;
         use super::DirectiveExpression as Parsed__override;
     }
     #[inline]
-    pub(super) fn parse_DirectiveExpression<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_DirectiveExpression<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, DirectiveExpression> {
         global
             .tracer
             .print_trace_start(&state, "DirectiveExpression");
-        let result = {
-            let result = DirectiveExpression_impl::parse(state, global)?;
-            Ok(result)
-        };
+        let result = state.parse_rule("DirectiveExpression", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, DirectiveExpression> {
+                    let result = DirectiveExpression_impl::parse(state, global)?;
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod StringDirective_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@string"))
@@ -2703,25 +4624,31 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_StringDirective<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_StringDirective<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, StringDirective> {
         global.tracer.print_trace_start(&state, "StringDirective");
-        let result = {
-            let result =
-                StringDirective_impl::parse(state, global)?.map(|r| super::StringDirective {});
-            Ok(result)
-        };
+        let result = state.parse_rule("StringDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, StringDirective> {
+                    let result = StringDirective_impl::parse(state, global)?
+                        .map(|r| super::StringDirective {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod CharDirective_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@char"))
@@ -2730,24 +4657,31 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_CharDirective<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_CharDirective<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, CharDirective> {
         global.tracer.print_trace_start(&state, "CharDirective");
-        let result = {
-            let result = CharDirective_impl::parse(state, global)?.map(|r| super::CharDirective {});
-            Ok(result)
-        };
+        let result = state.parse_rule("CharDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, CharDirective> {
+                    let result =
+                        CharDirective_impl::parse(state, global)?.map(|r| super::CharDirective {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod NoSkipWsDirective_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@no_skip_ws"))
@@ -2756,25 +4690,31 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_NoSkipWsDirective<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_NoSkipWsDirective<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, NoSkipWsDirective> {
         global.tracer.print_trace_start(&state, "NoSkipWsDirective");
-        let result = {
-            let result =
-                NoSkipWsDirective_impl::parse(state, global)?.map(|r| super::NoSkipWsDirective {});
-            Ok(result)
-        };
+        let result = state.parse_rule("NoSkipWsDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, NoSkipWsDirective> {
+                    let result = NoSkipWsDirective_impl::parse(state, global)?
+                        .map(|r| super::NoSkipWsDirective {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod ExportDirective_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@export"))
@@ -2783,25 +4723,31 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_ExportDirective<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_ExportDirective<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, ExportDirective> {
         global.tracer.print_trace_start(&state, "ExportDirective");
-        let result = {
-            let result =
-                ExportDirective_impl::parse(state, global)?.map(|r| super::ExportDirective {});
-            Ok(result)
-        };
+        let result = state.parse_rule("ExportDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, ExportDirective> {
+                    let result = ExportDirective_impl::parse(state, global)?
+                        .map(|r| super::ExportDirective {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod PositionDirective_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@position"))
@@ -2810,25 +4756,31 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_PositionDirective<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_PositionDirective<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, PositionDirective> {
         global.tracer.print_trace_start(&state, "PositionDirective");
-        let result = {
-            let result =
-                PositionDirective_impl::parse(state, global)?.map(|r| super::PositionDirective {});
-            Ok(result)
-        };
+        let result = state.parse_rule("PositionDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, PositionDirective> {
+                    let result = PositionDirective_impl::parse(state, global)?
+                        .map(|r| super::PositionDirective {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod MemoizeDirective_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@memoize"))
@@ -2837,25 +4789,31 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_MemoizeDirective<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_MemoizeDirective<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, MemoizeDirective> {
         global.tracer.print_trace_start(&state, "MemoizeDirective");
-        let result = {
-            let result =
-                MemoizeDirective_impl::parse(state, global)?.map(|r| super::MemoizeDirective {});
-            Ok(result)
-        };
+        let result = state.parse_rule("MemoizeDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, MemoizeDirective> {
+                    let result = MemoizeDirective_impl::parse(state, global)?
+                        .map(|r| super::MemoizeDirective {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod LeftrecDirective_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@leftrec"))
@@ -2864,25 +4822,31 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_LeftrecDirective<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_LeftrecDirective<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, LeftrecDirective> {
         global.tracer.print_trace_start(&state, "LeftrecDirective");
-        let result = {
-            let result =
-                LeftrecDirective_impl::parse(state, global)?.map(|r| super::LeftrecDirective {});
-            Ok(result)
-        };
+        let result = state.parse_rule("LeftrecDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, LeftrecDirective> {
+                    let result = LeftrecDirective_impl::parse(state, global)?
+                        .map(|r| super::LeftrecDirective {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod CheckDirective_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@check"))
@@ -2906,16 +4870,203 @@ mod peginator_generated {
         pub type Parsed = NamespacedRustName;
     }
     #[inline]
-    pub(super) fn parse_CheckDirective<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_CheckDirective<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, CheckDirective> {
         global.tracer.print_trace_start(&state, "CheckDirective");
-        let result = {
-            let result = CheckDirective_impl::parse(state, global)?
-                .map(|r| super::CheckDirective { function: r });
-            Ok(result)
-        };
+        let result = state.parse_rule("CheckDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, CheckDirective> {
+                    let result = CheckDirective_impl::parse(state, global)?
+                        .map(|r| super::CheckDirective { function: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod RecoverDirective_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@recover"))
+                .discard_result()?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '('))
+                .discard_result()?;
+            let ParseOk {
+                result: sync,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_Identifier(state, &mut *global))?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, ')'))
+                .discard_result()?;
+            Ok(ParseOk {
+                result: sync,
+                state,
+            })
+        }
+        pub type Parsed = Identifier;
+    }
+    #[inline]
+    pub(super) fn parse_RecoverDirective<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, RecoverDirective> {
+        global.tracer.print_trace_start(&state, "RecoverDirective");
+        let result = state.parse_rule("RecoverDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, RecoverDirective> {
+                    let result = RecoverDirective_impl::parse(state, global)?
+                        .map(|r| super::RecoverDirective { sync: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod NameDirective_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@name"))
+                .discard_result()?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '('))
+                .discard_result()?;
+            let ParseOk {
+                result: name,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_StringLiteral(state, &mut *global))?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, ')'))
+                .discard_result()?;
+            Ok(ParseOk {
+                result: name,
+                state,
+            })
+        }
+        pub type Parsed = StringLiteral;
+    }
+    #[inline]
+    pub(super) fn parse_NameDirective<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, NameDirective> {
+        global.tracer.print_trace_start(&state, "NameDirective");
+        let result = state.parse_rule("NameDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, NameDirective> {
+                    let result = NameDirective_impl::parse(state, global)?
+                        .map(|r| super::NameDirective { name: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod NotKeywordDirective_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@not_keyword"))
+                .discard_result()
+        }
+        pub type Parsed = ();
+    }
+    #[inline]
+    pub(super) fn parse_NotKeywordDirective<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, NotKeywordDirective> {
+        global
+            .tracer
+            .print_trace_start(&state, "NotKeywordDirective");
+        let result = state.parse_rule("NotKeywordDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, NotKeywordDirective> {
+                    let result = NotKeywordDirective_impl::parse(state, global)?
+                        .map(|r| super::NotKeywordDirective {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod PrecedenceDirective_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@precedence"))
+                .discard_result()?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '('))
+                .discard_result()?;
+            let ParseOk {
+                result: operand,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_Identifier(state, &mut *global))?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, ')'))
+                .discard_result()?;
+            Ok(ParseOk {
+                result: operand,
+                state,
+            })
+        }
+        pub type Parsed = Identifier;
+    }
+    #[inline]
+    pub(super) fn parse_PrecedenceDirective<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, PrecedenceDirective> {
+        global
+            .tracer
+            .print_trace_start(&state, "PrecedenceDirective");
+        let result = state.parse_rule("PrecedenceDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, PrecedenceDirective> {
+                    let result = PrecedenceDirective_impl::parse(state, global)?
+                        .map(|r| super::PrecedenceDirective { operand: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
@@ -2926,9 +5077,9 @@ mod peginator_generated {
             mod optional {
                 use super::*;
                 #[inline(always)]
-                pub fn parse<'a, TT: ParseTracer, TUD>(
+                pub fn parse<'a, TT: ParseTracer>(
                     state: ParseState<'a>,
-                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                 ) -> ParseResult<'a, Parsed> {
                     let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                         .and_then(|ParseOk { state, .. }| parse_string_literal(state, "->"))
@@ -2949,23 +5100,27 @@ mod peginator_generated {
                 pub type Parsed = Option<NamespacedRustName>;
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
                 optional::parse(state.clone(), global).or_else(|err| {
-                    Ok(ParseOk {
-                        result: Default::default(),
-                        state: state.record_error(err),
-                    })
+                    if err.is_committed() {
+                        Err(err.uncommit())
+                    } else {
+                        Ok(ParseOk {
+                            result: Default::default(),
+                            state: state.record_error(err),
+                        })
+                    }
                 })
             }
             pub type Parsed = Option<NamespacedRustName>;
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@extern"))
@@ -3014,28 +5169,218 @@ mod peginator_generated {
         use super::ExternDirective_function as Parsed_function;
     }
     #[inline]
-    pub(super) fn parse_ExternDirective<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_ExternDirective<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, ExternDirective> {
         global.tracer.print_trace_start(&state, "ExternDirective");
-        let result = {
-            let result =
-                ExternDirective_impl::parse(state, global)?.map(|r| super::ExternDirective {
-                    function: r.function,
-                    return_type: r.return_type,
-                });
-            Ok(result)
-        };
+        let result = state.parse_rule("ExternDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, ExternDirective> {
+                    let result = ExternDirective_impl::parse(state, global)?.map(|r| {
+                        super::ExternDirective {
+                            function: r.function,
+                            return_type: r.return_type,
+                        }
+                    });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod GrammarDirective_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            ChoiceHelper::new(state)
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| {
+                            parse_KeywordBoundaryDirective(state, global)
+                        })
+                        .map_inner(Parsed__override::KeywordBoundaryDirective)
+                })
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_ImportDirective(state, global))
+                        .map_inner(Parsed__override::ImportDirective)
+                })
+                .choice(|state| {
+                    parse_Whitespace(state, &mut *global)
+                        .and_then(|ParseOk { state, .. }| parse_ExtendsDirective(state, global))
+                        .map_inner(Parsed__override::ExtendsDirective)
+                })
+                .end()
+        }
+        pub type Parsed = Parsed__override;
+        use super::GrammarDirective as Parsed__override;
+    }
+    #[inline]
+    pub(super) fn parse_GrammarDirective<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, GrammarDirective> {
+        global.tracer.print_trace_start(&state, "GrammarDirective");
+        let result = state.parse_rule("GrammarDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, GrammarDirective> {
+                    let result = GrammarDirective_impl::parse(state, global)?;
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod KeywordBoundaryDirective_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@keyword_boundary"))
+                .discard_result()?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '('))
+                .discard_result()?;
+            let ParseOk {
+                result: rule,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_Identifier(state, &mut *global))?;
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, ')'))
+                .discard_result()?;
+            Ok(ParseOk {
+                result: rule,
+                state,
+            })
+        }
+        pub type Parsed = Identifier;
+    }
+    #[inline]
+    pub(super) fn parse_KeywordBoundaryDirective<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, KeywordBoundaryDirective> {
+        global
+            .tracer
+            .print_trace_start(&state, "KeywordBoundaryDirective");
+        let result = state.parse_rule("KeywordBoundaryDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, KeywordBoundaryDirective> {
+                    let result = KeywordBoundaryDirective_impl::parse(state, global)?
+                        .map(|r| super::KeywordBoundaryDirective { rule: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod ImportDirective_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@import"))
+                .discard_result()?;
+            let ParseOk {
+                result: path,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_StringLiteral(state, &mut *global))?;
+            Ok(ParseOk {
+                result: path,
+                state,
+            })
+        }
+        pub type Parsed = StringLiteral;
+    }
+    #[inline]
+    pub(super) fn parse_ImportDirective<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, ImportDirective> {
+        global.tracer.print_trace_start(&state, "ImportDirective");
+        let result = state.parse_rule("ImportDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, ImportDirective> {
+                    let result = ImportDirective_impl::parse(state, global)?
+                        .map(|r| super::ImportDirective { path: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod ExtendsDirective_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_string_literal(state, "@extends"))
+                .discard_result()?;
+            let ParseOk {
+                result: path,
+                state,
+            } = parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_StringLiteral(state, &mut *global))?;
+            Ok(ParseOk {
+                result: path,
+                state,
+            })
+        }
+        pub type Parsed = StringLiteral;
+    }
+    #[inline]
+    pub(super) fn parse_ExtendsDirective<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, ExtendsDirective> {
+        global.tracer.print_trace_start(&state, "ExtendsDirective");
+        let result = state.parse_rule("ExtendsDirective", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, ExtendsDirective> {
+                    let result = ExtendsDirective_impl::parse(state, global)?
+                        .map(|r| super::ExtendsDirective { path: r });
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod UserDefinedMethod_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_string_literal(state, "user_defined"))
@@ -3057,15 +5402,21 @@ mod peginator_generated {
         use super::UserDefinedMethod as Parsed__override;
     }
     #[inline]
-    pub(super) fn parse_UserDefinedMethod<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_UserDefinedMethod<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, UserDefinedMethod> {
         global.tracer.print_trace_start(&state, "UserDefinedMethod");
-        let result = {
-            let result = UserDefinedMethod_impl::parse(state, global)?;
-            Ok(result)
-        };
+        let result = state.parse_rule("UserDefinedMethod", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, UserDefinedMethod> {
+                    let result = UserDefinedMethod_impl::parse(state, global)?;
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
@@ -3076,9 +5427,9 @@ mod peginator_generated {
             mod closure {
                 use super::*;
                 #[inline(always)]
-                pub fn parse<'a, TT: ParseTracer, TUD>(
+                pub fn parse<'a, TT: ParseTracer>(
                     state: ParseState<'a>,
-                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                 ) -> ParseResult<'a, Parsed> {
                     let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
                         .and_then(|ParseOk { state, .. }| parse_string_literal(state, "::"))
@@ -3097,11 +5448,10 @@ mod peginator_generated {
                 pub type Parsed = Vec<RustNamePart>;
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
-                let mut iterations: usize = 0;
                 let mut state = state;
                 let mut _override: Vec<RustNamePart> = Vec::new();
                 loop {
@@ -3114,12 +5464,12 @@ mod peginator_generated {
                             _override.extend(__result);
                             state = new_state;
                         }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
                         Err(err) => {
                             state = state.record_error(err);
                             break;
                         }
                     }
-                    iterations += 1;
                 }
                 Ok(ParseOk {
                     result: _override,
@@ -3129,9 +5479,9 @@ mod peginator_generated {
             pub type Parsed = Vec<RustNamePart>;
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk {
                 result: mut _override,
@@ -3153,17 +5503,23 @@ mod peginator_generated {
         use super::NamespacedRustName as Parsed__override;
     }
     #[inline]
-    pub(super) fn parse_NamespacedRustName<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_NamespacedRustName<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, NamespacedRustName> {
         global
             .tracer
             .print_trace_start(&state, "NamespacedRustName");
-        let result = {
-            let result = NamespacedRustName_impl::parse(state, global)?;
-            Ok(result)
-        };
+        let result = state.parse_rule("NamespacedRustName", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, NamespacedRustName> {
+                    let result = NamespacedRustName_impl::parse(state, global)?;
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
@@ -3176,9 +5532,9 @@ mod peginator_generated {
                 mod negative_lookahead {
                     use super::*;
                     #[inline(always)]
-                    pub fn parse<'a, TT: ParseTracer, TUD>(
+                    pub fn parse<'a, TT: ParseTracer>(
                         state: ParseState<'a>,
-                        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                     ) -> ParseResult<'a, Parsed> {
                         ChoiceHelper::new(state)
                             .choice(|state| {
@@ -3207,23 +5563,28 @@ mod peginator_generated {
                     pub type Parsed = ();
                 }
                 #[inline(always)]
-                pub fn parse<'a, TT: ParseTracer, TUD>(
+                pub fn parse<'a, TT: ParseTracer>(
                     state: ParseState<'a>,
-                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                 ) -> ParseResult<'a, Parsed> {
                     match negative_lookahead::parse(state.clone(), global) {
-                        Ok(_) => {
-                            Err(state.report_error(ParseErrorSpecifics::NegativeLookaheadFailed))
-                        }
-                        Err(_) => Ok(ParseOk { result: (), state }),
+                        Ok(ParseOk {
+                            state: inner_state, ..
+                        }) => Err(state
+                            .merge_examined(inner_state.examined_until())
+                            .report_error(ParseErrorSpecifics::NegativeLookaheadFailed)),
+                        Err(err) => Ok(ParseOk {
+                            result: (),
+                            state: state.merge_examined(err.examined_until()),
+                        }),
                     }
                 }
                 pub type Parsed = ();
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
                 let ParseOk { state, .. } = part_0::parse(state, global)?;
                 let ParseOk { state, .. } = parse_Whitespace(state, &mut *global)
@@ -3234,9 +5595,9 @@ mod peginator_generated {
             pub type Parsed = ();
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let mut iterations: usize = 0;
             let mut state = state;
@@ -3249,6 +5610,7 @@ mod peginator_generated {
                     }) => {
                         state = new_state;
                     }
+                    Err(err) if err.is_committed() => return Err(err.uncommit()),
                     Err(err) => {
                         state = state.record_error(err);
                         break;
@@ -3264,28 +5626,35 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_RustNamePart<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_RustNamePart<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, RustNamePart> {
         global.tracer.print_trace_start(&state, "RustNamePart");
-        let result = {
-            let result =
-                RustNamePart_impl::parse(state.clone(), global)?.map_with_state(|_, new_state| {
-                    let string = state.slice_until(new_state).to_string();
-                    string
-                });
-            Ok(result)
-        };
+        let result = state.parse_rule("RustNamePart", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, RustNamePart> {
+                    let result = RustNamePart_impl::parse(state.clone(), global)?.map_with_state(
+                        |_, new_state| {
+                            let string = state.slice_until(new_state).to_owned();
+                            string
+                        },
+                    );
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
     mod EndOfInput_impl {
         use super::*;
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             parse_Whitespace(state, &mut *global)
                 .and_then(|ParseOk { state, .. }| parse_character_literal(state, '$'))
@@ -3294,15 +5663,54 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_EndOfInput<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_EndOfInput<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, EndOfInput> {
         global.tracer.print_trace_start(&state, "EndOfInput");
-        let result = {
-            let result = EndOfInput_impl::parse(state, global)?.map(|r| super::EndOfInput {});
-            Ok(result)
-        };
+        let result = state.parse_rule("EndOfInput", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, EndOfInput> {
+                    let result =
+                        EndOfInput_impl::parse(state, global)?.map(|r| super::EndOfInput {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
+        global.tracer.print_trace_result(&result);
+        result
+    }
+    mod Cut_impl {
+        use super::*;
+        #[inline(always)]
+        pub fn parse<'a, TT: ParseTracer>(
+            state: ParseState<'a>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+        ) -> ParseResult<'a, Parsed> {
+            parse_Whitespace(state, &mut *global)
+                .and_then(|ParseOk { state, .. }| parse_character_literal(state, '~'))
+                .discard_result()
+        }
+        pub type Parsed = ();
+    }
+    #[inline]
+    pub(super) fn parse_Cut<'a, TT: ParseTracer>(
+        state: ParseState<'a>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
+    ) -> ParseResult<'a, Cut> {
+        global.tracer.print_trace_start(&state, "Cut");
+        let result = state.parse_rule("Cut", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Cut> {
+                    let result = Cut_impl::parse(state, global)?.map(|r| super::Cut {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
@@ -3312,11 +5720,10 @@ mod peginator_generated {
             use super::*;
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
-            let mut iterations: usize = 0;
             let mut state = state;
             loop {
                 match ChoiceHelper::new(state.clone())
@@ -3335,27 +5742,34 @@ mod peginator_generated {
                     }) => {
                         state = new_state;
                     }
+                    Err(err) if err.is_committed() => return Err(err.uncommit()),
                     Err(err) => {
                         state = state.record_error(err);
                         break;
                     }
                 }
-                iterations += 1;
             }
             Ok(ParseOk { result: (), state })
         }
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_Whitespace<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_Whitespace<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, Whitespace> {
         global.tracer.print_trace_start(&state, "Whitespace");
-        let result = {
-            let result = Whitespace_impl::parse(state, global)?.map(|r| super::Whitespace {});
-            Ok(result)
-        };
+        let result = state.parse_rule("Whitespace", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Whitespace> {
+                    let result =
+                        Whitespace_impl::parse(state, global)?.map(|r| super::Whitespace {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
@@ -3370,33 +5784,37 @@ mod peginator_generated {
                     mod negative_lookahead {
                         use super::*;
                         #[inline(always)]
-                        pub fn parse<'a, TT: ParseTracer, TUD>(
+                        pub fn parse<'a, TT: ParseTracer>(
                             state: ParseState<'a>,
-                            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                         ) -> ParseResult<'a, Parsed> {
                             parse_character_literal(state, '\n').discard_result()
                         }
                         pub type Parsed = ();
                     }
                     #[inline(always)]
-                    pub fn parse<'a, TT: ParseTracer, TUD>(
+                    pub fn parse<'a, TT: ParseTracer>(
                         state: ParseState<'a>,
-                        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                     ) -> ParseResult<'a, Parsed> {
                         match negative_lookahead::parse(state.clone(), global) {
-                            Ok(_) => {
-                                Err(state
-                                    .report_error(ParseErrorSpecifics::NegativeLookaheadFailed))
-                            }
-                            Err(_) => Ok(ParseOk { result: (), state }),
+                            Ok(ParseOk {
+                                state: inner_state, ..
+                            }) => Err(state
+                                .merge_examined(inner_state.examined_until())
+                                .report_error(ParseErrorSpecifics::NegativeLookaheadFailed)),
+                            Err(err) => Ok(ParseOk {
+                                result: (),
+                                state: state.merge_examined(err.examined_until()),
+                            }),
                         }
                     }
                     pub type Parsed = ();
                 }
                 #[inline(always)]
-                pub fn parse<'a, TT: ParseTracer, TUD>(
+                pub fn parse<'a, TT: ParseTracer>(
                     state: ParseState<'a>,
-                    global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                    global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
                 ) -> ParseResult<'a, Parsed> {
                     let ParseOk { state, .. } = part_0::parse(state, global)?;
                     let ParseOk { state, .. } = parse_char(state, &mut *global).discard_result()?;
@@ -3405,11 +5823,10 @@ mod peginator_generated {
                 pub type Parsed = ();
             }
             #[inline(always)]
-            pub fn parse<'a, TT: ParseTracer, TUD>(
+            pub fn parse<'a, TT: ParseTracer>(
                 state: ParseState<'a>,
-                global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+                global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
             ) -> ParseResult<'a, Parsed> {
-                let mut iterations: usize = 0;
                 let mut state = state;
                 loop {
                     match closure::parse(state.clone(), global) {
@@ -3420,21 +5837,21 @@ mod peginator_generated {
                         }) => {
                             state = new_state;
                         }
+                        Err(err) if err.is_committed() => return Err(err.uncommit()),
                         Err(err) => {
                             state = state.record_error(err);
                             break;
                         }
                     }
-                    iterations += 1;
                 }
                 Ok(ParseOk { result: (), state })
             }
             pub type Parsed = ();
         }
         #[inline(always)]
-        pub fn parse<'a, TT: ParseTracer, TUD>(
+        pub fn parse<'a, TT: ParseTracer>(
             state: ParseState<'a>,
-            global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+            global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
         ) -> ParseResult<'a, Parsed> {
             let ParseOk { state, .. } = parse_character_literal(state, '#').discard_result()?;
             let ParseOk { state, .. } = part_1::parse(state, global)?;
@@ -3444,15 +5861,21 @@ mod peginator_generated {
         pub type Parsed = ();
     }
     #[inline]
-    pub(super) fn parse_Comment<'a, TT: ParseTracer, TUD>(
+    pub(super) fn parse_Comment<'a, TT: ParseTracer>(
         state: ParseState<'a>,
-        global: &mut ParseGlobal<TT, ParseCache<'a>, TUD>,
+        global: &mut ParseGlobal<TT, ParseCache<'a>, ()>,
     ) -> ParseResult<'a, Comment> {
         global.tracer.print_trace_start(&state, "Comment");
-        let result = {
-            let result = Comment_impl::parse(state, global)?.map(|r| super::Comment {});
-            Ok(result)
-        };
+        let result = state.parse_rule("Comment", global.record_context, |state| {
+            let result = {
+                (|| -> ParseResult<'a, Comment> {
+                    let result = Comment_impl::parse(state, global)?.map(|r| super::Comment {});
+                    Ok(result)
+                })()
+                .map_err(ParseFailure::uncommit)
+            };
+            result
+        });
         global.tracer.print_trace_result(&result);
         result
     }
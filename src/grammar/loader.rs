@@ -0,0 +1,220 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use std::{
+    collections::{HashMap, HashSet},
+    fs,
+    mem::discriminant,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Result};
+
+use super::{Grammar, GrammarDirective, Grammar_rules, StringLiteral};
+use crate::{ParseError, PegParser, PrettyParseError};
+
+/// A grammar file, merged with all the grammar files it imports with `@import`, directly or
+/// indirectly, and with the grammar it extends with `@extends`.
+///
+/// Import paths are relative to the importing file. Every file is only loaded once, so shared
+/// files can be imported from multiple places. A rule can only be defined once in all the
+/// loaded files, except that the rules of an extending grammar (and its imports) replace the
+/// rules of the same name in the base grammar.
+#[derive(Debug, Clone)]
+pub struct LoadedGrammar {
+    /// All the rules and directives of the loaded files, without the `@import` and `@extends`
+    /// directives.
+    pub grammar: Grammar,
+    /// The contents of the loaded files, starting with the main file, followed by the files of
+    /// the base grammar.
+    pub sources: Vec<String>,
+}
+
+impl LoadedGrammar {
+    /// Load a grammar file, its imports and its base grammar.
+    ///
+    /// Parse errors are returned as [`PrettyParseError`]s, showing the file they happened in.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self> {
+        Self::load_with(path.as_ref(), Grammar::parse)
+    }
+
+    /// Load a grammar file, its imports and its base grammar, parsing the files with `parse`,
+    /// e.g. `Grammar::parse_with_trace`.
+    pub fn load_with(
+        path: &Path,
+        parse: impl Fn(&str) -> Result<Grammar, ParseError>,
+    ) -> Result<Self> {
+        Self::load_extending(path, &parse, &mut Vec::new())
+    }
+
+    /// `extending` is the canonical paths of the grammars (transitively) extending this one.
+    fn load_extending(
+        path: &Path,
+        parse: &impl Fn(&str) -> Result<Grammar, ParseError>,
+        extending: &mut Vec<PathBuf>,
+    ) -> Result<Self> {
+        let mut loader = Loader {
+            parse,
+            loaded_files: HashSet::new(),
+            rule_files: HashMap::new(),
+            base: None,
+            result: LoadedGrammar {
+                grammar: Grammar {
+                    rules: Vec::new(),
+                    directives: Vec::new(),
+                },
+                sources: Vec::new(),
+            },
+        };
+        loader.load_file(path, None)?;
+        let Some(base_path) = loader.base else {
+            return Ok(loader.result);
+        };
+        extending.push(path.canonicalize()?);
+        let canonical_base_path = base_path.canonicalize().map_err(|err| {
+            anyhow!(
+                "Could not read {} (extended by {}): {err}",
+                base_path.display(),
+                path.display()
+            )
+        })?;
+        if extending.contains(&canonical_base_path) {
+            bail!(
+                "Circular @extends: {} extends {}, which extends it",
+                path.display(),
+                base_path.display()
+            );
+        }
+        let base = Self::load_extending(&base_path, parse, extending)?;
+        Ok(loader.result.overriding(base))
+    }
+
+    /// Merge the rules and directives of `self` into `base`, replacing the base rules of the
+    /// same name in place, and the base directives of the same kind.
+    fn overriding(self, base: LoadedGrammar) -> LoadedGrammar {
+        let mut rules = base.grammar.rules;
+        for rule in self.grammar.rules {
+            match rules.iter_mut().find(|r| rule_name(r) == rule_name(&rule)) {
+                Some(base_rule) => *base_rule = rule,
+                None => rules.push(rule),
+            }
+        }
+        let mut directives: Vec<_> = base
+            .grammar
+            .directives
+            .into_iter()
+            .filter(|d| {
+                !self
+                    .grammar
+                    .directives
+                    .iter()
+                    .any(|own| discriminant(own) == discriminant(d))
+            })
+            .collect();
+        directives.extend(self.grammar.directives);
+        let mut sources = self.sources;
+        sources.extend(base.sources);
+        LoadedGrammar {
+            grammar: Grammar { rules, directives },
+            sources,
+        }
+    }
+}
+
+struct Loader<F> {
+    parse: F,
+    /// The canonical paths of the files already loaded
+    loaded_files: HashSet<PathBuf>,
+    /// Rule name -> the canonical path of the file it was defined in
+    rule_files: HashMap<String, PathBuf>,
+    /// The path of the grammar in the `@extends` directive of the main file
+    base: Option<PathBuf>,
+    result: LoadedGrammar,
+}
+
+impl<F: Fn(&str) -> Result<Grammar, ParseError>> Loader<F> {
+    fn load_file(&mut self, path: &Path, imported_from: Option<&Path>) -> Result<()> {
+        let read_error = |err: std::io::Error| match imported_from {
+            Some(importer) => anyhow!(
+                "Could not read {} (imported from {}): {err}",
+                path.display(),
+                importer.display()
+            ),
+            None => anyhow!("Could not read {}: {err}", path.display()),
+        };
+        let canonical_path = path.canonicalize().map_err(read_error)?;
+        if !self.loaded_files.insert(canonical_path.clone()) {
+            return Ok(());
+        }
+        let source = fs::read_to_string(path).map_err(read_error)?;
+        let grammar = (self.parse)(&source)
+            .map_err(|err| PrettyParseError::from_parse_error(&err, &source, path.to_str()))?;
+        self.result.sources.push(source);
+
+        for rule in grammar.rules {
+            let name = rule_name(&rule);
+            if let Some(other_file) = self.rule_files.get(name) {
+                if *other_file == canonical_path {
+                    bail!("Rule {name} is defined twice in {}", path.display());
+                }
+                bail!(
+                    "Rule {name} is defined in both {} and {}",
+                    other_file.display(),
+                    path.display()
+                );
+            }
+            self.rule_files
+                .insert(name.to_string(), canonical_path.clone());
+            self.result.grammar.rules.push(rule);
+        }
+        for directive in grammar.directives {
+            match directive {
+                GrammarDirective::ImportDirective(import) => {
+                    let import_path = relative_path(path, &import.path, "@import")?;
+                    self.load_file(&import_path, Some(path))?;
+                }
+                GrammarDirective::ExtendsDirective(extends) => {
+                    if imported_from.is_some() {
+                        bail!(
+                            "@extends cannot be used in imported files ({})",
+                            path.display()
+                        );
+                    }
+                    if self.base.is_some() {
+                        bail!(
+                            "Only one @extends directive can be used in a grammar ({})",
+                            path.display()
+                        );
+                    }
+                    self.base = Some(relative_path(path, &extends.path, "@extends")?);
+                }
+                directive => self.result.grammar.directives.push(directive),
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `literal` as a path relative to the directory of the grammar file at `path`.
+fn relative_path(path: &Path, literal: &StringLiteral, directive: &str) -> Result<PathBuf> {
+    if literal.insensitive.is_some() || literal.keyword.is_some() {
+        bail!(
+            "The {directive} directive does not accept case insensitive or keyword strings ({})",
+            path.display()
+        );
+    }
+    Ok(path
+        .parent()
+        .unwrap_or_else(|| Path::new(""))
+        .join(literal.value()?))
+}
+
+fn rule_name(rule: &Grammar_rules) -> &str {
+    match rule {
+        Grammar_rules::Rule(rule) => &rule.name,
+        Grammar_rules::CharRule(rule) => &rule.name,
+        Grammar_rules::ExternRule(rule) => &rule.name,
+        Grammar_rules::PrecedenceRule(rule) => &rule.name,
+    }
+}
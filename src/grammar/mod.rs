@@ -3,5 +3,7 @@
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
 mod generated;
+mod loader;
 
 pub use generated::*;
+pub use loader::LoadedGrammar;
@@ -176,5 +176,8 @@ pub mod codegen;
 pub mod runtime;
 
 pub use runtime::{
-    ParseError, ParseErrorSpecifics, ParseSettings, PegParser, PegPosition, PrettyParseError,
+    ColumnEncoding, Diagnostic, DiagnosticLine, IncrementalParse, LineColumn, LineIndex,
+    ParseError, ParseErrorFound, ParseErrorSpecifics, ParseIter, ParseSettings, PegBorrowedParser,
+    PegByteParser, PegIncrementalParser, PegParser, PegPosition, PrettyParseError,
+    PrettyParseErrorSettings, ShiftPositions, StreamParseError, StreamParseIter, TextEdit,
 };
@@ -2,7 +2,9 @@
 // This file is part of peginator
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
-use super::{ParseErrorSpecifics, ParseOk, ParseResult, ParseState};
+use std::cmp::Ordering;
+
+use super::{ParseErrorSpecifics, ParseInput, ParseOk, ParseResult, ParseState};
 
 /// Hand-written 'rule parser' for parsing a single cahracter.
 ///
@@ -28,7 +30,10 @@ pub fn parse_char<_GT>(state: ParseState, _global: _GT) -> ParseResult<char> {
 /// Should always look just like all the other generated parse functions.
 #[inline]
 #[allow(non_snake_case)]
-pub fn parse_Whitespace<_GT>(state: ParseState, _global: _GT) -> ParseResult<()> {
+pub fn parse_Whitespace<_GT, I: ?Sized + ParseInput>(
+    state: ParseState<I>,
+    _global: _GT,
+) -> ParseResult<(), I> {
     let mut state = state;
     while !state.is_empty() {
         if state.s().as_bytes()[0].is_ascii_whitespace() {
@@ -42,7 +47,10 @@ pub fn parse_Whitespace<_GT>(state: ParseState, _global: _GT) -> ParseResult<()>
             break;
         }
     }
-    Ok(ParseOk { result: (), state })
+    Ok(ParseOk {
+        result: (),
+        state: state.examine(1),
+    })
 }
 
 #[inline(always)]
@@ -139,6 +147,41 @@ pub fn parse_character_range(state: ParseState, from: char, to: char) -> ParseRe
     }
 }
 
+/// Parse a character from a class, given as a sorted list of non-overlapping inclusive ranges.
+/// Used for Unicode property classes (`\p{...}`).
+#[inline(always)]
+pub fn parse_character_class<'a>(
+    state: ParseState<'a>,
+    ranges: &'static [(char, char)],
+    name: &'static str,
+) -> ParseResult<'a, char> {
+    let c = state.s().chars().next().filter(|&c| {
+        ranges
+            .binary_search_by(|&(from, to)| {
+                if to < c {
+                    Ordering::Less
+                } else if from > c {
+                    Ordering::Greater
+                } else {
+                    Ordering::Equal
+                }
+            })
+            .is_ok()
+    });
+    match c {
+        Some(c) => {
+            // SAFETY:
+            // Callers of this function are responsible that these preconditions are satisfied:
+            //    Indexes must lie on UTF-8 sequence boundaries.
+            //
+            // We are skipping a full character, so we should be OK.
+            let state = unsafe { state.advance(c.len_utf8()) };
+            Ok(ParseOk { result: c, state })
+        }
+        None => Err(state.report_error(ParseErrorSpecifics::ExpectedCharacterClass { name })),
+    }
+}
+
 #[inline(always)]
 pub fn parse_string_literal_insensitive<'a>(
     state: ParseState<'a>,
@@ -162,6 +205,31 @@ pub fn parse_string_literal_insensitive<'a>(
     }
 }
 
+/// Case insensitive match of a non-ASCII literal. `variants` has the characters matching each
+/// character of the literal, according to Unicode simple case folding.
+#[inline(always)]
+pub fn parse_string_literal_case_folded<'a>(
+    state: ParseState<'a>,
+    s: &'static str,
+    variants: &'static [&'static [char]],
+) -> ParseResult<'a, &'static str> {
+    let mut length = 0;
+    let mut chars = state.s().chars();
+    for char_variants in variants {
+        match chars.next() {
+            Some(c) if char_variants.contains(&c) => length += c.len_utf8(),
+            _ => return Err(state.report_error(ParseErrorSpecifics::ExpectedString { s })),
+        }
+    }
+    // SAFETY:
+    // Callers of this function are responsible that these preconditions are satisfied:
+    //    Indexes must lie on UTF-8 sequence boundaries.
+    //
+    // We are skipping full characters, so we should be OK.
+    let state = unsafe { state.advance(length) };
+    Ok(ParseOk { result: s, state })
+}
+
 #[inline(always)]
 pub fn parse_character_literal_insensitive(state: ParseState, c: char) -> ParseResult<char> {
     // ASCII Only !
@@ -179,10 +247,91 @@ pub fn parse_character_literal_insensitive(state: ParseState, c: char) -> ParseR
 }
 
 #[inline(always)]
-pub fn parse_end_of_input(state: ParseState) -> ParseResult<()> {
+pub fn parse_end_of_input<I: ?Sized + ParseInput>(state: ParseState<I>) -> ParseResult<(), I> {
     if state.is_empty() {
-        Ok(ParseOk { result: (), state })
+        Ok(ParseOk {
+            result: (),
+            state: state.examine(1),
+        })
     } else {
         Err(state.report_error(ParseErrorSpecifics::ExpectedEoi))
     }
 }
+
+/// Hand-written 'rule parser' for parsing a single byte, when parsing byte slices.
+///
+/// Should always look just like all the other generated parse functions.
+#[inline(always)]
+pub fn parse_byte<_GT>(state: ParseState<[u8]>, _global: _GT) -> ParseResult<u8, [u8]> {
+    let result = *state.s().first().ok_or_else(|| {
+        state
+            .clone()
+            .report_error(ParseErrorSpecifics::ExpectedAnyByte)
+    })?;
+    let state = state.advance_safe(1);
+    Ok(ParseOk { result, state })
+}
+
+#[inline(always)]
+pub fn parse_byte_literal(state: ParseState<[u8]>, b: u8) -> ParseResult<u8, [u8]> {
+    if state.s().first() != Some(&b) {
+        Err(state.report_error(ParseErrorSpecifics::ExpectedByte { b }))
+    } else {
+        let state = state.advance_safe(1);
+        Ok(ParseOk { result: b, state })
+    }
+}
+
+#[inline(always)]
+pub fn parse_byte_literal_insensitive(state: ParseState<[u8]>, b: u8) -> ParseResult<u8, [u8]> {
+    // ASCII Only !
+    match state.s().first() {
+        Some(first) if first.to_ascii_lowercase() == b => {
+            let state = state.advance_safe(1);
+            Ok(ParseOk { result: b, state })
+        }
+        _ => Err(state.report_error(ParseErrorSpecifics::ExpectedByte { b })),
+    }
+}
+
+#[inline(always)]
+pub fn parse_byte_range(state: ParseState<[u8]>, from: u8, to: u8) -> ParseResult<u8, [u8]> {
+    match state.s().first() {
+        Some(&b) if b >= from && b <= to => {
+            let state = state.advance_safe(1);
+            Ok(ParseOk { result: b, state })
+        }
+        _ => Err(state.report_error(ParseErrorSpecifics::ExpectedByteRange { from, to })),
+    }
+}
+
+#[inline(always)]
+pub fn parse_byte_string_literal<'a>(
+    state: ParseState<'a, [u8]>,
+    s: &'static [u8],
+) -> ParseResult<'a, &'static [u8], [u8]> {
+    if !state.s().starts_with(s) {
+        Err(state.report_error(ParseErrorSpecifics::ExpectedByteString { s }))
+    } else {
+        let state = state.advance_safe(s.len());
+        Ok(ParseOk { result: s, state })
+    }
+}
+
+#[inline(always)]
+pub fn parse_byte_string_literal_insensitive<'a>(
+    state: ParseState<'a, [u8]>,
+    s: &'static [u8],
+) -> ParseResult<'a, &'static [u8], [u8]> {
+    let prefix = state
+        .s()
+        .iter()
+        .map(|c| c.to_ascii_lowercase())
+        .take(s.len());
+    if !s.iter().copied().eq(prefix) {
+        Err(state.report_error(ParseErrorSpecifics::ExpectedByteString { s }))
+    } else {
+        let state = state.advance_safe(s.len());
+        Ok(ParseOk { result: s, state })
+    }
+}
@@ -0,0 +1,32 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use std::borrow::Cow;
+
+/// Return types accepted from `@check` functions.
+///
+/// A check can either return a plain `bool`, or a `Result<(), E>`, where the error is converted to
+/// a message shown to the user.
+pub trait CheckResult {
+    /// Convert to a result, where `Err(None)` means a failed check without a message.
+    fn into_check_result(self) -> Result<(), Option<Cow<'static, str>>>;
+}
+
+impl CheckResult for bool {
+    #[inline]
+    fn into_check_result(self) -> Result<(), Option<Cow<'static, str>>> {
+        if self {
+            Ok(())
+        } else {
+            Err(None)
+        }
+    }
+}
+
+impl<E: Into<Cow<'static, str>>> CheckResult for Result<(), E> {
+    #[inline]
+    fn into_check_result(self) -> Result<(), Option<Cow<'static, str>>> {
+        self.map_err(|message| Some(message.into()))
+    }
+}
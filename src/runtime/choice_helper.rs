@@ -2,37 +2,48 @@
 // This file is part of peginator
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
-use super::{ParseOk, ParseResult, ParseState};
+use super::{ParseInput, ParseOk, ParseResult, ParseState};
 
-pub struct ChoiceHelper<'a, T> {
-    state: ParseState<'a>,
-    result: Option<ParseOk<'a, T>>,
+pub struct ChoiceHelper<'a, T, I: ?Sized + ParseInput = str> {
+    state: ParseState<'a, I>,
+    result: Option<ParseOk<'a, T, I>>,
+    cut: bool,
 }
 
-impl<'a, T> ChoiceHelper<'a, T> {
+impl<'a, T, I: ?Sized + ParseInput> ChoiceHelper<'a, T, I> {
     #[inline]
-    pub fn new(state: ParseState<'a>) -> Self {
+    pub fn new(state: ParseState<'a, I>) -> Self {
         Self {
             state,
             result: None,
+            cut: false,
         }
     }
 
     #[inline]
-    pub fn choice(mut self, parse_fn: impl FnOnce(ParseState<'a>) -> ParseResult<'a, T>) -> Self {
-        if self.result.is_none() {
+    pub fn choice(
+        mut self,
+        parse_fn: impl FnOnce(ParseState<'a, I>) -> ParseResult<'a, T, I>,
+    ) -> Self {
+        if self.result.is_none() && !self.cut {
             match parse_fn(self.state.clone()) {
                 Ok(ok_result) => self.result = Some(ok_result),
-                Err(err) => self.state = self.state.record_error(err),
+                Err(err) => {
+                    // An alternative that failed after a cut prevents trying the rest.
+                    self.cut = err.is_committed();
+                    self.state = self.state.record_error(err);
+                }
             }
         }
         self
     }
 
     #[inline]
-    pub fn end(self) -> ParseResult<'a, T> {
+    pub fn end(self) -> ParseResult<'a, T, I> {
         match self.result {
             Some(ok) => Ok(ok),
+            // The enclosing closures and optionals should not backtrack either.
+            None if self.cut => Err(self.state.report_farthest_error().commit()),
             None => Err(self.state.report_farthest_error()),
         }
     }
@@ -0,0 +1,254 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use std::{fmt::Write, ops::Range};
+
+use unicode_width::UnicodeWidthChar;
+
+use super::{ColumnEncoding, LineIndex, ParseError, PrettyParseErrorSettings};
+
+/// Clamp `offset` to the line, and move it back to a character boundary.
+fn clamp_to_line(line_range: &Range<usize>, line: &str, offset: usize) -> usize {
+    let mut offset = offset.saturating_sub(line_range.start).min(line.len());
+    while !line.is_char_boundary(offset) {
+        offset -= 1;
+    }
+    offset
+}
+
+/// The number of terminal columns `s` takes up, if it starts at column `start_column`.
+///
+/// Tabs are expanded to the next tab stop, wide (e.g. CJK) characters take up two columns.
+fn visual_width(s: &str, start_column: usize, tab_width: usize) -> usize {
+    let mut column = start_column;
+    for c in s.chars() {
+        if c == '\t' {
+            column += tab_width - column % tab_width;
+        } else {
+            column += c.width().unwrap_or(0);
+        }
+    }
+    column - start_column
+}
+
+/// Expand the tabs in the line to spaces, so that it lines up with the underline.
+fn expand_tabs(s: &str, tab_width: usize) -> String {
+    let mut result = String::with_capacity(s.len());
+    let mut column = 0;
+    for c in s.chars() {
+        if c == '\t' {
+            let width = tab_width - column % tab_width;
+            result.push_str(&" ".repeat(width));
+            column += width;
+        } else {
+            result.push(c);
+            column += c.width().unwrap_or(0);
+        }
+    }
+    result
+}
+
+fn json_string(s: &str) -> String {
+    let mut result = String::with_capacity(s.len() + 2);
+    result.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => result.push_str("\\\""),
+            '\\' => result.push_str("\\\\"),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c if (c as u32) < 0x20 => {
+                let _ = write!(result, "\\u{:04x}", c as u32);
+            }
+            c => result.push(c),
+        }
+    }
+    result.push('"');
+    result
+}
+
+const ANSI_RESET: &str = "\x1b[0m";
+const ANSI_MESSAGE: &str = "\x1b[1;37m";
+const ANSI_GUTTER: &str = "\x1b[1;34m";
+const ANSI_UNDERLINE: &str = "\x1b[1;31m";
+
+/// A single line of source shown in a [`Diagnostic`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiagnosticLine {
+    /// The line number (starting from 1)
+    pub line_number: usize,
+    /// The contents of the line, without the line ending, tabs expanded to spaces.
+    pub text: String,
+    /// The underlined part of the line, in terminal columns of `text` (starting from 0).
+    pub underline: Option<Range<usize>>,
+}
+
+/// A parse error prepared for showing it to a user, without any formatting.
+///
+/// Can be rendered as plain text ([`Diagnostic::render_plain`]), colored text for terminals
+/// ([`Diagnostic::render_ansi`]) or JSON ([`Diagnostic::render_json`]).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The human readable error message, see [`ParseError::message`]
+    pub message: String,
+    /// The source file name, if supplied
+    pub source_file: Option<String>,
+    /// The line of the error position (starting from 1)
+    pub line: usize,
+    /// The column of the error position in characters (starting from 1)
+    pub column: usize,
+    /// The byte range of the error in the text
+    pub span: Range<usize>,
+    /// Additional information, e.g. the rules the error happened in
+    pub note: Option<String>,
+    /// The lines of the source around the error
+    pub snippet: Vec<DiagnosticLine>,
+}
+
+impl Diagnostic {
+    /// Convert from [`ParseError`]. The parsed `text` needs to be supplied to show the context of
+    /// the error.
+    pub fn from_parse_error(
+        err: &ParseError,
+        text: &str,
+        source_file: Option<&str>,
+        settings: &PrettyParseErrorSettings,
+    ) -> Self {
+        let lines = LineIndex::new(text);
+        let span_start = err.span.start.min(text.len());
+        let span_end = err.span.end.max(span_start).min(text.len());
+        let first_line = lines.line_column(span_start, ColumnEncoding::Chars).line;
+        let position = lines.line_column(err.position.min(text.len()), ColumnEncoding::Chars);
+        let last_line = if span_end > span_start {
+            lines.line_column(span_end - 1, ColumnEncoding::Chars).line
+        } else {
+            first_line
+        };
+
+        let shown_lines = first_line.saturating_sub(settings.lines_before)
+            ..(last_line + settings.lines_after + 1).min(lines.line_count());
+        let snippet = shown_lines
+            .map(|line_index| {
+                let line_range = lines.line_range(line_index).unwrap_or_default();
+                let line = &text[line_range.clone()];
+                let underline = if line_index < first_line || line_index > last_line {
+                    None
+                } else {
+                    let underline_start = clamp_to_line(&line_range, line, span_start);
+                    let underline_end = clamp_to_line(&line_range, line, span_end);
+                    let offset = visual_width(&line[..underline_start], 0, settings.tab_width);
+                    let width = visual_width(
+                        &line[underline_start..underline_end],
+                        offset,
+                        settings.tab_width,
+                    );
+                    // Empty spans, and spans on empty lines still get a caret
+                    let width = if line_index == first_line {
+                        width.max(1)
+                    } else {
+                        width
+                    };
+                    (width > 0).then_some(offset..offset + width)
+                };
+                DiagnosticLine {
+                    line_number: line_index + 1,
+                    text: expand_tabs(line, settings.tab_width).trim_end().to_string(),
+                    underline,
+                }
+            })
+            .collect();
+
+        Self {
+            message: err.message(),
+            source_file: source_file.map(|f| f.to_string()),
+            line: position.line + 1,
+            column: position.column + 1,
+            span: span_start..span_end,
+            note: err.context_message(),
+            snippet,
+        }
+    }
+
+    /// Render as plain text, in a similar format `rustc` uses.
+    pub fn render_plain(&self) -> String {
+        self.render(false)
+    }
+
+    /// Render as text colored with ANSI escape codes, in a similar format `rustc` uses.
+    pub fn render_ansi(&self) -> String {
+        self.render(true)
+    }
+
+    fn render(&self, colored: bool) -> String {
+        let color = |code: &'static str, s: &str| {
+            if colored {
+                format!("{code}{s}{ANSI_RESET}")
+            } else {
+                s.to_string()
+            }
+        };
+        let position = if let Some(f) = &self.source_file {
+            format!("{}:{}:{}", f, self.line, self.column)
+        } else {
+            format!("Line {} character {}", self.line, self.column)
+        };
+        let pipe = color(ANSI_GUTTER, " |  ");
+        let mut result = format!(
+            "{message}\n{arrow}{position}\n{pipe}\n",
+            message = color(ANSI_MESSAGE, &self.message),
+            arrow = color(ANSI_GUTTER, "--> "),
+        );
+        for line in &self.snippet {
+            let _ = writeln!(result, "{pipe}{}", line.text);
+            if let Some(underline) = &line.underline {
+                let _ = writeln!(
+                    result,
+                    "{pipe}{}{}",
+                    " ".repeat(underline.start),
+                    color(ANSI_UNDERLINE, &"^".repeat(underline.len()))
+                );
+            }
+        }
+        if let Some(note) = &self.note {
+            let _ = writeln!(result, "{} {note}", color(ANSI_GUTTER, " = note:"));
+        }
+        result
+    }
+
+    /// Render as a single JSON object.
+    ///
+    /// The fields are `message`, `file`, `line`, `column`, `span` (an object with `start` and
+    /// `end`), `note`, and `snippet` (a list of objects with `line`, `text` and `underline`).
+    /// Missing values are `null`.
+    pub fn render_json(&self) -> String {
+        let optional_string = |s: &Option<String>| match s {
+            Some(s) => json_string(s),
+            None => "null".to_string(),
+        };
+        let range = |r: &Range<usize>| format!("{{\"start\":{},\"end\":{}}}", r.start, r.end);
+        let snippet: Vec<String> = self
+            .snippet
+            .iter()
+            .map(|line| {
+                format!(
+                    "{{\"line\":{},\"text\":{},\"underline\":{}}}",
+                    line.line_number,
+                    json_string(&line.text),
+                    line.underline.as_ref().map_or("null".to_string(), range),
+                )
+            })
+            .collect();
+        format!(
+            "{{\"message\":{},\"file\":{},\"line\":{},\"column\":{},\"span\":{},\"note\":{},\"snippet\":[{}]}}",
+            json_string(&self.message),
+            optional_string(&self.source_file),
+            self.line,
+            self.column,
+            range(&self.span),
+            optional_string(&self.note),
+            snippet.join(","),
+        )
+    }
+}
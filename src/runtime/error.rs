@@ -2,12 +2,13 @@
 // This file is part of peginator
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
-use std::error::Error;
+use std::{borrow::Cow, error::Error, ops::Range};
 
-use colored::*;
+use super::{Diagnostic, ParseInput};
 
 /// The type and specifics of the atomic match, used by [`ParseError`].
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ParseErrorSpecifics {
     /// Expected any character, but found end of input.
     ExpectedAnyCharacter,
@@ -24,6 +25,21 @@ pub enum ParseErrorSpecifics {
     ExpectedString {
         s: &'static str,
     },
+    /// Expected any byte, but found end of input.
+    ExpectedAnyByte,
+    /// Expected a specific byte.
+    ExpectedByte {
+        b: u8,
+    },
+    /// Expected a byte from a specific range.
+    ExpectedByteRange {
+        from: u8,
+        to: u8,
+    },
+    /// Expected a specific byte string.
+    ExpectedByteString {
+        s: &'static [u8],
+    },
     /// Expected to match a @char rule.
     ExpectedCharacterClass {
         name: &'static str,
@@ -35,10 +51,29 @@ pub enum ParseErrorSpecifics {
     /// A custom check function failed
     CheckFunctionFailed {
         function_name: &'static str,
+        /// The error message returned by the check function, if any.
+        message: Option<Cow<'static, str>>,
     },
     /// A custom extern rule failed
     ExternRuleFailed {
-        error_string: &'static str,
+        error_string: Cow<'static, str>,
+    },
+    /// Expected to match a rule with a `@name` directive.
+    ExpectedRule {
+        name: &'static str,
+    },
+    /// A keyword literal matched, but it was followed by a word character.
+    ExpectedKeyword {
+        keyword: &'static str,
+    },
+    /// A `@not_keyword` rule matched a keyword.
+    UnexpectedKeyword {
+        keyword: &'static str,
+    },
+    /// A bounded closure (`{expression}{n,m}`) matched fewer times than its minimum.
+    ExpectedRepetitions {
+        min: usize,
+        matched: usize,
     },
     LeftRecursionSentinel,
 
@@ -46,34 +81,179 @@ pub enum ParseErrorSpecifics {
     Other,
 }
 
-impl ToString for ParseErrorSpecifics {
-    fn to_string(&self) -> String {
+impl std::fmt::Display for ParseErrorSpecifics {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
             ParseErrorSpecifics::ExpectedAnyCharacter => {
-                "expected any character (found end of input)".to_string()
+                write!(f, "expected any character (found end of input)")
             }
-            ParseErrorSpecifics::ExpectedCharacter { c } => format!("expected character '{c}'"),
+            ParseErrorSpecifics::ExpectedCharacter { c } => write!(f, "expected character '{c}'"),
             ParseErrorSpecifics::ExpectedCharacterRange { from, to } => {
-                format!("expected character from range '{from}'-'{to}'")
+                write!(f, "expected character from range '{from}'-'{to}'")
             }
             ParseErrorSpecifics::ExpectedCharacterClass { name } => {
-                format!("expected character from character class {name}")
+                write!(f, "expected character from character class {name}")
+            }
+            ParseErrorSpecifics::ExpectedString { s } => write!(f, "expected string \"{s}\""),
+            ParseErrorSpecifics::ExpectedAnyByte => {
+                write!(f, "expected any byte (found end of input)")
+            }
+            ParseErrorSpecifics::ExpectedByte { b } => {
+                write!(f, "expected byte b'{}'", b.escape_ascii())
+            }
+            ParseErrorSpecifics::ExpectedByteRange { from, to } => write!(
+                f,
+                "expected byte from range b'{}'-b'{}'",
+                from.escape_ascii(),
+                to.escape_ascii()
+            ),
+            ParseErrorSpecifics::ExpectedByteString { s } => {
+                write!(f, "expected byte string b\"{}\"", s.escape_ascii())
             }
-            ParseErrorSpecifics::ExpectedString { s } => format!("expected string \"{s}\""),
-            ParseErrorSpecifics::ExpectedEoi => "expected end of input".to_string(),
+            ParseErrorSpecifics::ExpectedEoi => write!(f, "expected end of input"),
             ParseErrorSpecifics::NegativeLookaheadFailed => {
-                "negative lookahead condition failed".to_string()
+                write!(f, "negative lookahead condition failed")
             }
-            ParseErrorSpecifics::CheckFunctionFailed { function_name } => {
-                format!("check function '{function_name}' failed")
+            ParseErrorSpecifics::CheckFunctionFailed {
+                function_name,
+                message: None,
+            } => {
+                write!(f, "check function '{function_name}' failed")
             }
+            ParseErrorSpecifics::CheckFunctionFailed {
+                message: Some(message),
+                ..
+            } => write!(f, "{message}"),
             ParseErrorSpecifics::ExternRuleFailed { error_string } => {
-                format!("extern function failed with '{error_string}'")
+                write!(f, "extern function failed with '{error_string}'")
+            }
+            ParseErrorSpecifics::ExpectedRule { name } => write!(f, "expected {name}"),
+            ParseErrorSpecifics::ExpectedKeyword { keyword } => {
+                write!(f, "expected keyword \"{keyword}\"")
+            }
+            ParseErrorSpecifics::UnexpectedKeyword { keyword } => {
+                write!(f, "unexpected keyword \"{keyword}\"")
+            }
+            ParseErrorSpecifics::ExpectedRepetitions { min, matched } => {
+                write!(f, "expected at least {min} repetitions, matched {matched}")
             }
             ParseErrorSpecifics::LeftRecursionSentinel => {
-                "Left recursion sentinel reached, will probably retry.".to_string()
+                write!(f, "Left recursion sentinel reached, will probably retry.")
             }
-            ParseErrorSpecifics::Other => "Unknown error. Sorry :(".to_string(),
+            ParseErrorSpecifics::Other => write!(f, "Unknown error. Sorry :("),
+        }
+    }
+}
+
+impl ParseErrorSpecifics {
+    /// The expected item without the "expected" prefix, if this is a simple expectation.
+    ///
+    /// Used to merge multiple expectations into a single "expected one of ..." message.
+    fn expected_item(&self) -> Option<String> {
+        match self {
+            ParseErrorSpecifics::ExpectedAnyCharacter => Some("any character".to_string()),
+            ParseErrorSpecifics::ExpectedCharacter { c } => Some(format!("'{c}'")),
+            ParseErrorSpecifics::ExpectedCharacterRange { from, to } => {
+                Some(format!("character from range '{from}'-'{to}'"))
+            }
+            ParseErrorSpecifics::ExpectedCharacterClass { name } => {
+                Some(format!("character from character class {name}"))
+            }
+            ParseErrorSpecifics::ExpectedString { s } => Some(format!("\"{s}\"")),
+            ParseErrorSpecifics::ExpectedAnyByte => Some("any byte".to_string()),
+            ParseErrorSpecifics::ExpectedByte { b } => Some(format!("b'{}'", b.escape_ascii())),
+            ParseErrorSpecifics::ExpectedByteRange { from, to } => Some(format!(
+                "byte from range b'{}'-b'{}'",
+                from.escape_ascii(),
+                to.escape_ascii()
+            )),
+            ParseErrorSpecifics::ExpectedByteString { s } => {
+                Some(format!("b\"{}\"", s.escape_ascii()))
+            }
+            ParseErrorSpecifics::ExpectedEoi => Some("end of input".to_string()),
+            ParseErrorSpecifics::ExpectedRule { name } => Some(name.to_string()),
+            ParseErrorSpecifics::ExpectedKeyword { keyword } => {
+                Some(format!("keyword \"{keyword}\""))
+            }
+            _ => None,
+        }
+    }
+}
+
+/// What was found at the position of a [`ParseError`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ParseErrorFound {
+    /// The input ended at the error position.
+    EndOfInput,
+    /// The token at the error position: a run of alphanumeric characters and underscores if the
+    /// input continues with one, or a single character otherwise.
+    ///
+    /// Tokens longer than 32 characters are cut, and end in `...`.
+    Token(String),
+    /// A single byte that is not an ASCII alphanumeric character, when parsing bytes.
+    Byte(u8),
+}
+
+/// The maximum number of characters in a [`ParseErrorFound::Token`].
+const MAX_FOUND_TOKEN_CHARS: usize = 32;
+
+impl ParseErrorFound {
+    /// Determine what was found at the beginning of `s`
+    pub fn from_input(s: &str) -> Self {
+        Self::from_input_with_len(s).0
+    }
+
+    /// Determine what was found at the beginning of `s`, and its length in bytes.
+    pub(crate) fn from_input_with_len(s: &str) -> (Self, usize) {
+        let is_word_char = |c: char| c.is_alphanumeric() || c == '_';
+        match s.chars().next() {
+            None => (ParseErrorFound::EndOfInput, 0),
+            Some(c) if is_word_char(c) => {
+                let (length, is_cut) = s
+                    .char_indices()
+                    .enumerate()
+                    .find(|(n, (_, c))| *n == MAX_FOUND_TOKEN_CHARS || !is_word_char(*c))
+                    .map_or((s.len(), false), |(_, (i, c))| (i, is_word_char(c)));
+                (Self::token(&s[..length], is_cut), length)
+            }
+            Some(c) => (ParseErrorFound::Token(c.to_string()), c.len_utf8()),
+        }
+    }
+
+    /// Determine what was found at the beginning of `bytes`, and its length.
+    pub(crate) fn from_bytes_with_len(bytes: &[u8]) -> (Self, usize) {
+        let is_word_byte = |b: u8| b.is_ascii_alphanumeric() || b == b'_';
+        match bytes.first() {
+            None => (ParseErrorFound::EndOfInput, 0),
+            Some(&b) if is_word_byte(b) => {
+                let (length, is_cut) = bytes
+                    .iter()
+                    .enumerate()
+                    .find(|(n, b)| *n == MAX_FOUND_TOKEN_CHARS || !is_word_byte(**b))
+                    .map_or((bytes.len(), false), |(i, b)| (i, is_word_byte(*b)));
+                let token = String::from_utf8_lossy(&bytes[..length]);
+                (Self::token(&token, is_cut), length)
+            }
+            Some(&b) => (ParseErrorFound::Byte(b), 1),
+        }
+    }
+
+    fn token(token: &str, is_cut: bool) -> Self {
+        if is_cut {
+            ParseErrorFound::Token(format!("{token}..."))
+        } else {
+            ParseErrorFound::Token(token.to_string())
+        }
+    }
+}
+
+impl std::fmt::Display for ParseErrorFound {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            ParseErrorFound::EndOfInput => write!(f, "end of input"),
+            ParseErrorFound::Token(token) => write!(f, "'{}'", token.escape_debug()),
+            ParseErrorFound::Byte(b) => write!(f, "b'{}'", b.escape_ascii()),
         }
     }
 }
@@ -81,87 +261,197 @@ impl ToString for ParseErrorSpecifics {
 /// An error happened during parsing (compact version).
 ///
 /// During parsing, the parser records the furthest it got without encountering a match failure. The
-/// error will contain both this furthest position and the first unmatched atomic matcher at this
+/// error will contain both this furthest position and all the unmatched atomic matchers at this
 /// position.
 ///
 /// Convert to [`PrettyParseError`] before showing it to a user.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
 pub struct ParseError {
     /// The byte-position of the furthest match failure.
     pub position: usize,
-    /// The atomic match that was unsuccessful at the furthest parsing position.
-    pub specifics: ParseErrorSpecifics,
+    /// The byte range the error is about, from the start of the innermost rule the error happened
+    /// in, to the end of the found token (see `found`).
+    ///
+    /// The start of the rule is known for rules with `@memoize`, `@leftrec`, `@name` or
+    /// `@recover`, and for every rule if
+    /// [`ParseSettings::record_context`](super::ParseSettings::record_context) is set. Otherwise
+    /// the span starts at `position`. During parsing, the span ends at `position`.
+    pub span: Range<usize>,
+    /// The atomic matches that were unsuccessful at the furthest parsing position, in the order
+    /// they were tried, without duplicates.
+    pub specifics: Vec<ParseErrorSpecifics>,
+    /// The names of the rules the error happened in, innermost first.
+    ///
+    /// Only recorded if [`ParseSettings::record_context`](super::ParseSettings::record_context)
+    /// is set, empty otherwise.
+    pub context: Vec<&'static str>,
+    /// What was found at the error position, filled in at the end of parsing.
+    pub found: Option<ParseErrorFound>,
+    /// The end of the input range that was looked at before the match failed.
+    ///
+    /// Used to decide which memoized results can be reused after an edit (see
+    /// [`TextEdit`](super::TextEdit)), and whether more input could change the error.
+    pub examined_until: usize,
+}
+
+impl ParseError {
+    /// An error at `position`, with an empty span and no context.
+    pub fn new(position: usize, specifics: Vec<ParseErrorSpecifics>) -> Self {
+        Self {
+            position,
+            span: position..position,
+            specifics,
+            context: Vec::new(),
+            found: None,
+            examined_until: position,
+        }
+    }
+
+    /// Replace the specifics with [`ParseErrorSpecifics::ExpectedRule`] if the error happened at
+    /// `position`, used for `@name` rules.
+    #[inline]
+    pub fn name_at(self, position: usize, name: &'static str) -> Self {
+        if self.position == position {
+            Self {
+                specifics: vec![ParseErrorSpecifics::ExpectedRule { name }],
+                ..self
+            }
+        } else {
+            self
+        }
+    }
+
+    /// Fill in what was found at the error position. `text` is the whole parsed input.
+    pub fn with_found<I: ParseInput + ?Sized>(self, text: &I) -> Self {
+        self.with_found_in(text, 0)
+    }
+
+    /// Fill in what was found at the error position, when `text` is the part of the input that
+    /// starts `base` bytes into it.
+    pub fn with_found_in<I: ParseInput + ?Sized>(self, text: &I, base: usize) -> Self {
+        let (found, length) = text.slice_from(self.position - base).found();
+        Self {
+            span: self.span.start.min(self.position)..self.position + length,
+            found: Some(found),
+            ..self
+        }
+    }
+
+    /// Record that the error happened inside the rule `rule_name`.
+    #[inline]
+    pub fn push_context(mut self, rule_name: &'static str) -> Self {
+        self.context.push(rule_name);
+        self
+    }
+
+    /// Description of the rules the error happened in, in a "while parsing X in Y" form.
+    pub fn context_message(&self) -> Option<String> {
+        if self.context.is_empty() {
+            None
+        } else {
+            Some(format!("while parsing {}", self.context.join(" in ")))
+        }
+    }
+
+    /// Merge the specifics of an other error at the same position into this one.
+    ///
+    /// Only the outer rules common to both errors are kept in the context.
+    pub fn merge(&mut self, other: ParseError) {
+        for specifics in other.specifics {
+            if !self.specifics.contains(&specifics) {
+                self.specifics.push(specifics);
+            }
+        }
+        let common_context = self
+            .context
+            .iter()
+            .rev()
+            .zip(other.context.iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        self.context.drain(..self.context.len() - common_context);
+    }
+
+    /// Human readable description of the error, without the position.
+    ///
+    /// If there were multiple possible matches at the error position, all of them are listed
+    /// in an "expected one of ..." form, followed by what was found instead, if known.
+    pub fn message(&self) -> String {
+        if self.specifics.len() == 1 && self.found.is_none() {
+            return self.specifics[0].to_string();
+        }
+        let mut expected_items = Vec::new();
+        let mut other_messages = Vec::new();
+        for specifics in &self.specifics {
+            match specifics.expected_item() {
+                Some(item) => expected_items.push(item),
+                None => other_messages.push(specifics.to_string()),
+            }
+        }
+        let mut parts = Vec::new();
+        let found = match &self.found {
+            Some(found) => format!(" but found {found}"),
+            None => String::new(),
+        };
+        match expected_items.len() {
+            0 => (),
+            1 => parts.push(format!("expected {}{found}", expected_items[0])),
+            _ => parts.push(format!(
+                "expected one of {}{found}",
+                expected_items.join(", ")
+            )),
+        }
+        parts.extend(other_messages);
+        parts.join("; ")
+    }
 }
 
 impl std::fmt::Display for ParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        let specifics = self.specifics.to_string();
         write!(
             f,
             "Parse error on byte position {} while parsing: {}",
-            self.position, specifics
+            self.position,
+            self.message()
         )
     }
 }
 
 impl Error for ParseError {}
 
+/// Settings for rendering a [`PrettyParseError`] or a [`Diagnostic`]
 #[derive(Debug, Clone)]
-struct IndexedStringLine<'a> {
-    pub s: &'a str,
-    pub lineno: usize,
-    pub start_offset: usize,
-    pub end_offset: usize,
-}
-
-struct IndexedStringLineIterator<'a> {
-    source: &'a str,
-    lineno: usize,
-    byte_offset: usize,
+#[non_exhaustive]
+pub struct PrettyParseErrorSettings {
+    /// Number of lines shown before the first line of the error.
+    pub lines_before: usize,
+    /// Number of lines shown after the last line of the error.
+    pub lines_after: usize,
+    /// Tab stop distance used when aligning the underline.
+    pub tab_width: usize,
 }
 
-impl<'a> IndexedStringLineIterator<'a> {
-    fn new(s: &'a str) -> IndexedStringLineIterator<'a> {
-        IndexedStringLineIterator {
-            source: s,
-            lineno: 0,
-            byte_offset: 0,
+impl Default for PrettyParseErrorSettings {
+    fn default() -> Self {
+        Self {
+            lines_before: 0,
+            lines_after: 0,
+            tab_width: 4,
         }
     }
 }
 
-impl<'a> Iterator for IndexedStringLineIterator<'a> {
-    type Item = IndexedStringLine<'a>;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        if self.byte_offset >= self.source.bytes().len() {
-            return None;
-        }
-        let next_offset = self.source[self.byte_offset..]
-            .bytes()
-            .position(|b| b == b'\n')
-            .map(|p| p + self.byte_offset)
-            .unwrap_or_else(|| self.source.bytes().len())
-            + 1;
-        let result = IndexedStringLine {
-            s: &self.source[self.byte_offset..next_offset - 1],
-            lineno: self.lineno,
-            start_offset: self.byte_offset,
-            end_offset: next_offset,
-        };
-        self.lineno += 1;
-        self.byte_offset = next_offset;
-        Some(result)
-    }
-}
-
 /// An error happened during parsing (pretty version).
 ///
 /// Converted from [`ParseError`], produces a very pretty, colored error message when printed with
-/// regular [`std::fmt::Display`].
+/// regular [`std::fmt::Display`]. Colors are only used if the `colored` crate allows it (e.g.
+/// the output is a terminal).
+///
+/// The structured form of the error is available with [`PrettyParseError::diagnostic`].
 #[derive(Debug, Clone)]
 pub struct PrettyParseError {
-    err_string: String,
+    diagnostic: Diagnostic,
 }
 
 impl PrettyParseError {
@@ -171,46 +461,42 @@ impl PrettyParseError {
     ///
     /// The `source_file` parameter is used to print the error with the same format `rustc` does.
     pub fn from_parse_error(err: &ParseError, text: &str, source_file: Option<&str>) -> Self {
-        let target_line = IndexedStringLineIterator::new(text)
-            .find(|l| l.start_offset <= err.position && l.end_offset >= err.position)
-            .unwrap();
-        let character_position = target_line
-            .s
-            .char_indices()
-            .map(|(cp, _c)| cp)
-            .position(|cp| cp == err.position - target_line.start_offset)
-            .unwrap_or(0);
-        let position = if let Some(f) = source_file {
-            format!(
-                "{}:{:?}:{:?}",
-                f,
-                target_line.lineno + 1,
-                character_position + 1
-            )
-        } else {
-            format!(
-                "Line {} character {}",
-                target_line.lineno + 1,
-                character_position + 1
-            )
-        };
-        let err_string = format!(
-            "{err}\n{arrow}{position}\n{pipe}\n{pipe}{the_line}\n{pipe}{caret:>caret_offset$}\n",
-            err = err.specifics.to_string().bold().white(),
-            position = position,
-            the_line = target_line.s.trim_end(),
-            caret = "^".bold().red(),
-            caret_offset = character_position + 1,
-            arrow = "--> ".bold().blue(),
-            pipe = " |  ".bold().blue(),
-        );
-        Self { err_string }
+        Self::from_parse_error_with_settings(
+            err,
+            text,
+            source_file,
+            &PrettyParseErrorSettings::default(),
+        )
+    }
+
+    /// Convert from [`ParseError`], with custom rendering settings.
+    ///
+    /// The whole span of the error is underlined, with the configured amount of context lines
+    /// around it.
+    pub fn from_parse_error_with_settings(
+        err: &ParseError,
+        text: &str,
+        source_file: Option<&str>,
+        settings: &PrettyParseErrorSettings,
+    ) -> Self {
+        Self {
+            diagnostic: Diagnostic::from_parse_error(err, text, source_file, settings),
+        }
+    }
+
+    /// The structured form of the error, which can be rendered in different formats.
+    pub fn diagnostic(&self) -> &Diagnostic {
+        &self.diagnostic
     }
 }
 
 impl std::fmt::Display for PrettyParseError {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
-        write!(f, "{}", self.err_string)
+        if colored::control::SHOULD_COLORIZE.should_colorize() {
+            write!(f, "{}", self.diagnostic.render_ansi())
+        } else {
+            write!(f, "{}", self.diagnostic.render_plain())
+        }
     }
 }
 
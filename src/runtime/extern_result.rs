@@ -0,0 +1,48 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use std::borrow::Cow;
+
+/// Return types accepted from `@extern` functions.
+///
+/// A function can either return a plain `Result<(T, usize), E>`, or a tuple of that and the
+/// number of bytes of the input it looked at. The error is converted to a message shown to the
+/// user.
+pub trait ExternResult {
+    type Output;
+
+    /// The number of bytes of the input the function looked at, if it reported it.
+    fn examined_length(&self) -> Option<usize>;
+
+    /// Convert to the result with the number of bytes consumed, or the error message.
+    fn into_extern_result(self) -> Result<(Self::Output, usize), Cow<'static, str>>;
+}
+
+impl<T, E: Into<Cow<'static, str>>> ExternResult for Result<(T, usize), E> {
+    type Output = T;
+
+    #[inline]
+    fn examined_length(&self) -> Option<usize> {
+        None
+    }
+
+    #[inline]
+    fn into_extern_result(self) -> Result<(T, usize), Cow<'static, str>> {
+        self.map_err(Into::into)
+    }
+}
+
+impl<T, E: Into<Cow<'static, str>>> ExternResult for (Result<(T, usize), E>, usize) {
+    type Output = T;
+
+    #[inline]
+    fn examined_length(&self) -> Option<usize> {
+        Some(self.1)
+    }
+
+    #[inline]
+    fn into_extern_result(self) -> Result<(T, usize), Cow<'static, str>> {
+        self.0.map_err(Into::into)
+    }
+}
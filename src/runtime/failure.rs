@@ -0,0 +1,354 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use std::rc::Rc;
+
+use super::{ParseError, ParseErrorSpecifics, ParseInput};
+
+#[derive(Debug)]
+struct SpecificsNode {
+    specifics: ParseErrorSpecifics,
+    /// The number of items in the list starting at this node.
+    len: usize,
+    next: SpecificsList,
+}
+
+/// The specifics of a [`FarthestError`], newest first.
+///
+/// The lists of the errors recorded while backtracking share their common part, so adding an
+/// item to one of them does not copy the others.
+#[derive(Debug, Clone, Default)]
+struct SpecificsList(Option<Rc<SpecificsNode>>);
+
+impl SpecificsList {
+    fn len(&self) -> usize {
+        self.0.as_ref().map_or(0, |node| node.len)
+    }
+
+    fn iter(&self) -> impl Iterator<Item = &ParseErrorSpecifics> {
+        let mut list = self;
+        std::iter::from_fn(move || {
+            let node = list.0.as_ref()?;
+            list = &node.next;
+            Some(&node.specifics)
+        })
+    }
+
+    fn push(&mut self, specifics: ParseErrorSpecifics) {
+        let next = std::mem::take(self);
+        *self = SpecificsList(Some(Rc::new(SpecificsNode {
+            specifics,
+            len: next.len() + 1,
+            next,
+        })));
+    }
+
+    fn contains(&self, specifics: &ParseErrorSpecifics) -> bool {
+        self.iter().any(|s| s == specifics)
+    }
+
+    /// Add `specifics` if it's not in the list yet.
+    fn insert(&mut self, specifics: ParseErrorSpecifics) {
+        if !self.contains(&specifics) {
+            self.push(specifics)
+        }
+    }
+
+    /// Whether this list was made by adding items to `other`.
+    fn extends(&self, other: &SpecificsList) -> bool {
+        let mut list = self;
+        loop {
+            match (&list.0, &other.0) {
+                (_, None) => return true,
+                (Some(node), Some(other_node)) if node.len > other_node.len => list = &node.next,
+                (Some(node), Some(other_node)) => return Rc::ptr_eq(node, other_node),
+                (None, Some(_)) => return false,
+            }
+        }
+    }
+
+    /// The items in the order they were added.
+    fn to_vec(&self) -> Vec<ParseErrorSpecifics> {
+        let mut result: Vec<_> = self.iter().cloned().collect();
+        result.reverse();
+        result
+    }
+
+    fn from_vec(specifics: Vec<ParseErrorSpecifics>) -> Self {
+        let mut result = Self::default();
+        for specifics in specifics {
+            result.push(specifics);
+        }
+        result
+    }
+}
+
+/// The farthest error recorded while parsing, converted to a [`ParseError`] at the end of the
+/// parse.
+///
+/// Cheap to clone, as the parse states that are tried while backtracking all have a copy.
+#[derive(Debug, Clone)]
+pub struct FarthestError(Rc<FarthestErrorData>);
+
+#[derive(Debug, Clone)]
+struct FarthestErrorData {
+    position: usize,
+    /// The start of the innermost rule the error happened in, if it is known yet.
+    rule_start: Option<usize>,
+    specifics: SpecificsList,
+    context: Vec<&'static str>,
+    examined_until: usize,
+}
+
+impl FarthestError {
+    #[inline]
+    pub(crate) fn new(
+        position: usize,
+        specifics: ParseErrorSpecifics,
+        examined_until: usize,
+    ) -> Self {
+        let mut data = FarthestErrorData {
+            position,
+            rule_start: None,
+            specifics: SpecificsList::default(),
+            context: Vec::new(),
+            examined_until,
+        };
+        data.specifics.push(specifics);
+        Self(Rc::new(data))
+    }
+
+    #[inline]
+    pub(crate) fn position(&self) -> usize {
+        self.0.position
+    }
+
+    /// Same as merging an error at the same position, with only `specifics`, no context and no
+    /// rule start.
+    #[inline]
+    pub(crate) fn add_specifics(&mut self, specifics: ParseErrorSpecifics) {
+        if !self.0.specifics.contains(&specifics)
+            || !self.0.context.is_empty()
+            || self.0.rule_start.is_some()
+        {
+            let data = Rc::make_mut(&mut self.0);
+            data.specifics.insert(specifics);
+            data.context.clear();
+            data.rule_start = None;
+        }
+    }
+
+    /// Merge an other error at the same position into this one, see [`ParseError::merge`].
+    pub(crate) fn merge(&mut self, other: &FarthestError) {
+        if Rc::ptr_eq(&self.0, &other.0) {
+            return;
+        }
+        if other.0.specifics.extends(&self.0.specifics)
+            && self.0.context.ends_with(&other.0.context)
+            && self.0.rule_start == other.0.rule_start
+        {
+            // Usually the case, when `other` was recorded by a clone of the state this error is
+            // in: it has the same items in the same order, plus the new ones.
+            *self = other.clone();
+        } else {
+            Rc::make_mut(&mut self.0).merge(&other.0);
+        }
+    }
+
+    /// Record that the error happened inside the rule `rule_name`.
+    #[inline]
+    pub(crate) fn push_context(&mut self, rule_name: &'static str) {
+        Rc::make_mut(&mut self.0).context.push(rule_name);
+    }
+
+    /// Record that the error happened inside a rule starting at `rule_start`, unless it is
+    /// already known to be in a rule inside that one.
+    #[inline]
+    pub(crate) fn set_rule_start(&mut self, rule_start: usize) {
+        if self.0.rule_start.is_none() {
+            Rc::make_mut(&mut self.0).rule_start = Some(rule_start);
+        }
+    }
+
+    /// Modify the error. The start of the rule is kept if the position did not change.
+    pub(crate) fn map(self, f: impl FnOnce(ParseError) -> ParseError) -> Self {
+        let rule_start = self.0.rule_start;
+        let mut result = Self::from_error(f(self.to_error()));
+        if result.0.position == self.0.position && result.0.rule_start != rule_start {
+            Rc::make_mut(&mut result.0).rule_start = rule_start;
+        }
+        result
+    }
+
+    pub(crate) fn to_error(&self) -> ParseError {
+        self.0.to_error()
+    }
+
+    pub(crate) fn from_error(error: ParseError) -> Self {
+        Self(Rc::new(FarthestErrorData {
+            position: error.position,
+            rule_start: (error.span.start < error.position).then_some(error.span.start),
+            specifics: SpecificsList::from_vec(error.specifics),
+            context: error.context,
+            examined_until: error.examined_until,
+        }))
+    }
+}
+
+impl FarthestErrorData {
+    fn merge(&mut self, other: &FarthestErrorData) {
+        if other.specifics.extends(&self.specifics) {
+            // Usually the case, when `other` was recorded by a clone of the state this error is
+            // in, and has the same items in the same order, plus the new ones.
+            self.specifics = other.specifics.clone();
+        } else {
+            for specifics in other.specifics.to_vec() {
+                self.specifics.insert(specifics);
+            }
+        }
+        if self.rule_start != other.rule_start {
+            // The errors happened in different rules, the innermost common one is not closed yet.
+            self.rule_start = None;
+        }
+        let common_context = self
+            .context
+            .iter()
+            .rev()
+            .zip(other.context.iter().rev())
+            .take_while(|(a, b)| a == b)
+            .count();
+        self.context.drain(..self.context.len() - common_context);
+    }
+
+    fn to_error(&self) -> ParseError {
+        ParseError {
+            position: self.position,
+            span: self.rule_start.unwrap_or(self.position)..self.position,
+            specifics: self.specifics.to_vec(),
+            context: self.context.clone(),
+            found: None,
+            examined_until: self.examined_until,
+        }
+    }
+}
+
+/// A failed match during parsing, converted to a [`ParseError`] at the end of the parse.
+#[derive(Debug, Clone)]
+pub struct ParseFailure {
+    error: FarthestError,
+    /// The match failed after a cut (`~`), so no other alternatives should be tried.
+    committed: bool,
+    examined_until: usize,
+}
+
+impl ParseFailure {
+    #[inline]
+    pub(crate) fn new(error: FarthestError, examined_until: usize) -> Self {
+        Self {
+            examined_until: examined_until.max(error.0.examined_until),
+            error,
+            committed: false,
+        }
+    }
+
+    #[inline]
+    pub(crate) fn set_rule_start(mut self, rule_start: usize) -> Self {
+        self.error.set_rule_start(rule_start);
+        self
+    }
+
+    #[inline]
+    pub(crate) fn into_farthest_error(self) -> (FarthestError, usize) {
+        (self.error, self.examined_until)
+    }
+
+    /// The error of the failed match.
+    pub fn error(&self) -> ParseError {
+        self.error.to_error()
+    }
+
+    /// The byte-position of the furthest match failure.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.error.position()
+    }
+
+    /// The end of the input range that was looked at before the match failed.
+    #[inline]
+    pub fn examined_until(&self) -> usize {
+        self.examined_until
+    }
+
+    /// Record that the input was looked at until `examined_until`.
+    #[inline]
+    pub fn merge_examined(mut self, examined_until: usize) -> Self {
+        self.examined_until = self.examined_until.max(examined_until);
+        self
+    }
+
+    /// Mark the failure as one that happened after a cut.
+    #[inline]
+    pub fn commit(self) -> Self {
+        Self {
+            committed: true,
+            ..self
+        }
+    }
+
+    /// Clear the cut marker, used at the boundary of the construct the cut applies to.
+    #[inline]
+    pub fn uncommit(self) -> Self {
+        Self {
+            committed: false,
+            ..self
+        }
+    }
+
+    /// Whether the failure happened after a cut, so no other alternatives should be tried.
+    #[inline]
+    pub fn is_committed(&self) -> bool {
+        self.committed
+    }
+
+    /// Modify the error.
+    pub fn map_error(self, f: impl FnOnce(ParseError) -> ParseError) -> Self {
+        Self {
+            error: self.error.map(f),
+            ..self
+        }
+    }
+
+    /// Record that the error happened inside the rule `rule_name`.
+    #[inline]
+    pub fn push_context(mut self, rule_name: &'static str) -> Self {
+        self.error.push_context(rule_name);
+        self
+    }
+
+    /// The error to return to the user.
+    pub fn into_error(self) -> ParseError {
+        ParseError {
+            examined_until: self.examined_until,
+            ..self.error.to_error()
+        }
+    }
+
+    /// Convert to a [`ParseError`], and fill in what was found at the error position. `text` is
+    /// the whole parsed input.
+    pub fn with_found<I: ParseInput + ?Sized>(self, text: &I) -> ParseError {
+        self.into_error().with_found(text)
+    }
+
+    /// Convert to a [`ParseError`], and fill in what was found at the error position, when
+    /// `text` is the part of the input that starts `base` bytes into it.
+    pub fn with_found_in<I: ParseInput + ?Sized>(self, text: &I, base: usize) -> ParseError {
+        self.into_error().with_found_in(text, base)
+    }
+}
+
+impl From<ParseError> for ParseFailure {
+    fn from(error: ParseError) -> Self {
+        Self::new(FarthestError::from_error(error), 0)
+    }
+}
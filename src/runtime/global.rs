@@ -2,21 +2,115 @@
 // This file is part of peginator
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
-use super::ParseTracer;
+use std::collections::HashMap;
+
+use super::{ParseError, ParseFailure, ParseInput, ParseResult, ParseSettings, ParseTracer};
 
 #[derive(Debug, Clone)]
 pub struct ParseGlobal<TT: ParseTracer, TC, TUD> {
     pub tracer: TT,
     pub cache: TC,
     pub user_defined: TUD,
+    /// Enables `@recover` rules to skip erroneous input instead of failing
+    pub recover: bool,
+    /// Errors that were recovered from by `@recover` rules, in the order they happened
+    pub recovered_errors: Vec<ParseError>,
+    /// The errors recovered from inside memoized rules, by rule name and cache key, so that they
+    /// are recorded again on cache hits
+    pub memoized_recovered_errors: HashMap<(&'static str, usize), Vec<ParseError>>,
+    /// Record the names of the rules the farthest error happened in
+    pub record_context: bool,
 }
 
 impl<TT: ParseTracer, TC, TUD> ParseGlobal<TT, TC, TUD> {
-    pub fn new(cache: TC, user_defined: TUD) -> Self {
+    pub fn new(cache: TC, user_defined: TUD, settings: &ParseSettings) -> Self {
         Self {
             tracer: TT::new(),
             cache,
             user_defined,
+            recover: false,
+            recovered_errors: Vec::new(),
+            memoized_recovered_errors: HashMap::new(),
+            record_context: settings.record_context,
         }
     }
+
+    pub fn record_recovered_error(&mut self, error: ParseError) {
+        if !self.recovered_errors.contains(&error) {
+            self.recovered_errors.push(error);
+        }
+    }
+
+    /// Run a parser whose result is thrown away if it fails, e.g. an alternative of a choice or
+    /// an iteration of a closure. The errors recovered from during a failed attempt are not part
+    /// of the parsed tree, so they are forgotten.
+    #[inline]
+    pub fn attempt<T>(
+        &mut self,
+        parse: impl FnOnce(&mut Self) -> Result<T, ParseFailure>,
+    ) -> Result<T, ParseFailure> {
+        let recovered_errors = self.recovered_errors.len();
+        let result = parse(self);
+        if result.is_err() {
+            self.recovered_errors.truncate(recovered_errors);
+        }
+        result
+    }
+
+    /// Run a lookahead parser, forgetting the errors recovered from during it, as its result is
+    /// always thrown away.
+    #[inline]
+    pub fn lookahead<T>(&mut self, parse: impl FnOnce(&mut Self) -> T) -> T {
+        let recovered_errors = self.recovered_errors.len();
+        let result = parse(self);
+        self.recovered_errors.truncate(recovered_errors);
+        result
+    }
+
+    /// Remember the errors recovered from since there were `recovered_errors` of them, as the ones
+    /// of the memoized rule `rule_name` at `cache_key`.
+    #[inline]
+    pub fn memoize_recovered_errors(
+        &mut self,
+        rule_name: &'static str,
+        cache_key: usize,
+        recovered_errors: usize,
+    ) {
+        if self.recovered_errors.len() > recovered_errors {
+            self.memoized_recovered_errors.insert(
+                (rule_name, cache_key),
+                self.recovered_errors[recovered_errors..].to_vec(),
+            );
+        }
+    }
+
+    /// Record the errors recovered from inside the memoized rule `rule_name` at `cache_key` again,
+    /// when its result is taken from the cache.
+    #[inline]
+    pub fn replay_recovered_errors(&mut self, rule_name: &'static str, cache_key: usize) {
+        if let Some(errors) = self.memoized_recovered_errors.get(&(rule_name, cache_key)) {
+            for error in errors.clone() {
+                self.record_recovered_error(error);
+            }
+        }
+    }
+
+    /// Convert the result of the top level rule to the result of a parse with error recovery.
+    ///
+    /// `text` is the whole parsed input, used to fill in what was found at the position of the
+    /// error the parse failed with.
+    pub fn into_recovery_result<T, I: ?Sized + ParseInput>(
+        mut self,
+        result: ParseResult<T, I>,
+        text: &I,
+    ) -> (Option<T>, Vec<ParseError>) {
+        let result = match result {
+            Ok(ok) => Some(ok.result),
+            Err(err) => {
+                self.record_recovered_error(err.into_error().with_found(text));
+                None
+            }
+        };
+        (result, self.recovered_errors)
+    }
 }
@@ -0,0 +1,329 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use std::{collections::HashMap, ops::Range};
+
+use nohash_hasher::BuildNoHashHasher;
+
+use super::{
+    CacheEntries, NoopTracer, ParseError, ParseFailure, ParseInput, ParseOk, ParseResult,
+    ParseSettings, ParseState, ParseTracer,
+};
+
+/// A replacement of a range of the input, used for incremental reparsing.
+///
+/// The default is an empty edit at the start of the input, which changes nothing.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextEdit {
+    /// The replaced range in the old input, in bytes.
+    pub range: Range<usize>,
+    /// The length of the replacement in bytes.
+    pub new_length: usize,
+}
+
+impl TextEdit {
+    pub fn new<I: ?Sized + ParseInput>(range: Range<usize>, replacement: &I) -> Self {
+        Self {
+            range,
+            new_length: replacement.len(),
+        }
+    }
+
+    /// How much the positions after the edit move.
+    pub fn offset(&self) -> isize {
+        self.new_length as isize - self.range.len() as isize
+    }
+
+    /// The position in the old input that corresponds to `position` of the edited input, unless
+    /// it's inside the replacement.
+    fn old_position(&self, position: usize) -> Option<usize> {
+        if position < self.range.start {
+            Some(position)
+        } else if position >= self.range.start + self.new_length {
+            Some(position - self.new_length + self.range.len())
+        } else {
+            None
+        }
+    }
+
+    /// Whether a memoized result can be reused after the edit.
+    ///
+    /// `position` is where the result was parsed from, and `examined_until` is the end of the
+    /// input range that was looked at while parsing it, both in the old input. Returns the
+    /// offset of the positions in the result, if it's still valid.
+    fn reusable_offset(&self, position: usize, examined_until: usize) -> Option<isize> {
+        if position < self.range.start && examined_until <= self.range.start {
+            Some(0)
+        } else if position >= self.range.end {
+            Some(self.offset())
+        } else {
+            None
+        }
+    }
+}
+
+/// Types that contain absolute input positions, that have to be moved when the input is edited.
+///
+/// Implemented by the types generated with `CodegenSettings::incremental`.
+pub trait ShiftPositions {
+    fn shift_positions(&mut self, offset: isize);
+}
+
+fn shift_position(position: usize, offset: isize) -> usize {
+    position
+        .checked_add_signed(offset)
+        .expect("Position shifted before the start of the input")
+}
+
+impl ShiftPositions for Range<usize> {
+    fn shift_positions(&mut self, offset: isize) {
+        self.start = shift_position(self.start, offset);
+        self.end = shift_position(self.end, offset);
+    }
+}
+
+impl<T: ShiftPositions> ShiftPositions for Vec<T> {
+    fn shift_positions(&mut self, offset: isize) {
+        for item in self {
+            item.shift_positions(offset);
+        }
+    }
+}
+
+impl<T: ShiftPositions> ShiftPositions for Option<T> {
+    fn shift_positions(&mut self, offset: isize) {
+        if let Some(item) = self {
+            item.shift_positions(offset);
+        }
+    }
+}
+
+impl<T: ShiftPositions> ShiftPositions for Box<T> {
+    fn shift_positions(&mut self, offset: isize) {
+        self.as_mut().shift_positions(offset);
+    }
+}
+
+/// The results of `@recover` rules.
+impl<T: ShiftPositions> ShiftPositions for Result<T, ParseError> {
+    fn shift_positions(&mut self, offset: isize) {
+        match self {
+            Ok(item) => item.shift_positions(offset),
+            Err(error) => error.shift_positions(offset),
+        }
+    }
+}
+
+impl ShiftPositions for ParseError {
+    fn shift_positions(&mut self, offset: isize) {
+        self.position = shift_position(self.position, offset);
+        self.span.shift_positions(offset);
+        self.examined_until = shift_position(self.examined_until, offset);
+    }
+}
+
+/// A [`ParseState`] that does not reference the input, so that it can be kept between parses.
+#[derive(Debug, Clone)]
+pub struct DetachedState {
+    pub(super) position: usize,
+    pub(super) farthest_error: Option<ParseError>,
+    pub(super) examined_until: usize,
+}
+
+impl ShiftPositions for DetachedState {
+    fn shift_positions(&mut self, offset: isize) {
+        self.position = shift_position(self.position, offset);
+        self.farthest_error.shift_positions(offset);
+        self.examined_until = shift_position(self.examined_until, offset);
+    }
+}
+
+/// [`CacheEntries`] that do not reference the input, kept between parses.
+pub type DetachedCacheEntries<T> =
+    HashMap<usize, Result<(T, DetachedState), ParseError>, BuildNoHashHasher<usize>>;
+
+/// The memoized results of a rule while parsing an edited input: the results of this parse, and
+/// the ones of the previous parse, which are only checked against the edit and moved over when
+/// they are first looked up.
+///
+/// Only the results that were parsed or looked up are kept for the next reparse, so the cost of
+/// a reparse does not depend on the number of results of the previous parse that were not needed,
+/// e.g. the ones inside reused results.
+pub struct IncrementalCacheEntries<'a, T, I: ?Sized + ParseInput = str> {
+    entries: CacheEntries<'a, T, I>,
+    previous: Option<PreviousCacheEntries<'a, T, I>>,
+}
+
+struct PreviousCacheEntries<'a, T, I: ?Sized> {
+    entries: DetachedCacheEntries<T>,
+    text: &'a I,
+    edit: TextEdit,
+    /// Moves the positions in a result parsed after the edit. A no-op for types without
+    /// positions.
+    shift: fn(&mut T, isize),
+}
+
+impl<'a, T, I: ?Sized + ParseInput> Default for IncrementalCacheEntries<'a, T, I> {
+    fn default() -> Self {
+        Self {
+            entries: CacheEntries::default(),
+            previous: None,
+        }
+    }
+}
+
+impl<'a, T, I: ?Sized + ParseInput> IncrementalCacheEntries<'a, T, I> {
+    /// Reuse the results of the previous parse of the input, before `edit` was applied to it.
+    /// `text` is the edited input.
+    pub fn attach(
+        entries: DetachedCacheEntries<T>,
+        text: &'a I,
+        edit: &TextEdit,
+        shift: fn(&mut T, isize),
+    ) -> Self {
+        Self {
+            entries: CacheEntries::default(),
+            previous: Some(PreviousCacheEntries {
+                entries,
+                text,
+                edit: edit.clone(),
+                shift,
+            }),
+        }
+    }
+
+    /// Detach the results of this parse from the input. The results of the previous parse that
+    /// were not looked up are dropped.
+    pub fn detach(self) -> DetachedCacheEntries<T> {
+        self.entries
+            .into_iter()
+            .map(|(position, entry)| {
+                let entry = entry
+                    .map(|ParseOk { result, state }| (result, state.detach()))
+                    .map_err(ParseFailure::into_error);
+                (position, entry)
+            })
+            .collect()
+    }
+
+    /// The memoized result at `position` of the edited input, if there is one.
+    #[inline]
+    pub fn get(&mut self, position: &usize) -> Option<&ParseResult<'a, T, I>> {
+        if !self.entries.contains_key(position) {
+            let entry = self.previous.as_mut()?.take(*position)?;
+            self.entries.insert(*position, entry);
+        }
+        self.entries.get(position)
+    }
+
+    #[inline]
+    pub fn insert(&mut self, position: usize, result: ParseResult<'a, T, I>) {
+        self.entries.insert(position, result);
+    }
+}
+
+impl<'a, T, I: ?Sized + ParseInput> PreviousCacheEntries<'a, T, I> {
+    /// Take out the result of the previous parse that was parsed from `position` of the edited
+    /// input, if it can be reused.
+    fn take(&mut self, position: usize) -> Option<ParseResult<'a, T, I>> {
+        let old_position = self.edit.old_position(position)?;
+        let entry = self.entries.remove(&old_position)?;
+        let examined_until = match &entry {
+            Ok((_, state)) => state.examined_until,
+            Err(err) => err.examined_until,
+        };
+        let offset = self.edit.reusable_offset(old_position, examined_until)?;
+        Some(match entry {
+            Ok((mut result, mut state)) => {
+                if offset != 0 {
+                    (self.shift)(&mut result, offset);
+                    state.shift_positions(offset);
+                }
+                Ok(ParseOk {
+                    result,
+                    state: ParseState::attach(self.text, state),
+                })
+            }
+            Err(mut err) => {
+                err.shift_positions(offset);
+                Err(err.into())
+            }
+        })
+    }
+}
+
+/// The result of a parse, together with the memoized results needed to reparse it after an edit.
+///
+/// See [`PegIncrementalParser`].
+#[derive(Debug, Clone)]
+pub struct IncrementalParse<T, TC> {
+    /// The parsed AST, or the error.
+    pub result: Result<T, ParseError>,
+    /// The memoized results of the parse, detached from the input.
+    pub cache: TC,
+}
+
+/// Parse an input, and then reparse it after edits, reusing the results of `@memoize` and
+/// `@leftrec` rules that were not affected by the edit.
+///
+/// Implemented by `@export`-ed rules of grammars compiled with `CodegenSettings::incremental`.
+/// `I` is `[u8]` for grammars compiled with byte input.
+pub trait PegIncrementalParser<I: ?Sized + ParseInput = str>: Sized {
+    /// The memoized results of a parse, detached from the input.
+    type Cache: Default;
+
+    /// Parse `s`, reusing the results in `cache` that are still valid after `edit`.
+    ///
+    /// Prefer [`PegIncrementalParser::parse_incremental`] and [`PegIncrementalParser::reparse`].
+    fn parse_with_cache(
+        s: &I,
+        cache: Self::Cache,
+        edit: &TextEdit,
+    ) -> IncrementalParse<Self, Self::Cache>;
+
+    /// Parse the input, keeping the memoized results for later reparses.
+    fn parse_incremental(s: &I) -> IncrementalParse<Self, Self::Cache> {
+        Self::parse_with_cache(s, Self::Cache::default(), &TextEdit::default())
+    }
+
+    /// Parse `s`, which is the input of `previous` with `edit` applied.
+    fn reparse(
+        previous: IncrementalParse<Self, Self::Cache>,
+        s: &I,
+        edit: &TextEdit,
+    ) -> IncrementalParse<Self, Self::Cache> {
+        Self::parse_with_cache(s, previous.cache, edit)
+    }
+}
+
+impl<I: ?Sized + ParseInput, T: PegIncrementalParserAdvanced<(), I>> PegIncrementalParser<I> for T {
+    type Cache = T::Cache;
+
+    fn parse_with_cache(
+        s: &I,
+        cache: Self::Cache,
+        edit: &TextEdit,
+    ) -> IncrementalParse<Self, Self::Cache> {
+        Self::parse_advanced_with_cache::<NoopTracer>(s, cache, edit, &ParseSettings::default(), ())
+    }
+}
+
+// Internal trait for generated code
+pub trait PegIncrementalParserAdvanced<TUD, I: ?Sized + ParseInput = str>: Sized {
+    /// The memoized results of a parse, detached from the input.
+    type Cache: Default;
+
+    /// Internal function that is actually generated by the grammar compiler, used by the more
+    /// friendly functions. Same as `PegParserAdvanced::parse_advanced`, but reuses the results
+    /// in `cache` that are still valid after `edit`, and returns the results of this parse.
+    ///
+    /// Start with `Self::Cache::default()` and `TextEdit::default()` for the first parse.
+    fn parse_advanced_with_cache<TT: ParseTracer>(
+        s: &I,
+        cache: Self::Cache,
+        edit: &TextEdit,
+        settings: &ParseSettings,
+        user_defined: TUD,
+    ) -> IncrementalParse<Self, Self::Cache>;
+}
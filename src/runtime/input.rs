@@ -0,0 +1,163 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use super::ParseErrorFound;
+
+mod private {
+    pub trait Sealed {}
+
+    impl Sealed for str {}
+    impl Sealed for [u8] {}
+}
+
+/// The types generated parsers can work on: `str` (the default), and `[u8]` for binary or non-UTF-8
+/// inputs.
+///
+/// This trait is sealed, it cannot be implemented outside of peginator.
+pub trait ParseInput: private::Sealed {
+    /// Length in bytes
+    fn len(&self) -> usize;
+
+    #[inline]
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn as_bytes(&self) -> &[u8];
+
+    /// The input starting from byte `from`. Panics if `from` is not on a character boundary.
+    fn slice_from(&self, from: usize) -> &Self;
+
+    /// The input starting from byte `from`, without any checks.
+    ///
+    /// # Safety
+    /// `from` must be in bounds, and on a character boundary.
+    unsafe fn slice_from_unchecked(&self, from: usize) -> &Self;
+
+    /// The input up until byte `to`. Panics if `to` is not on a character boundary.
+    fn slice_to(&self, to: usize) -> &Self;
+
+    /// The length of the first unit (character or byte) in bytes, if there is one.
+    fn first_unit_len(&self) -> Option<usize>;
+
+    /// Quoted, human readable form of the first `n` units, used for tracing.
+    fn debug_prefix(&self, n: usize) -> String;
+
+    /// What is at the beginning of the input, and its length in bytes, used for error messages.
+    fn found(&self) -> (ParseErrorFound, usize);
+
+    /// Whether the input starts with an alphanumeric character or an underscore.
+    fn starts_with_word_char(&self) -> bool;
+
+    /// The longest prefix of `bytes` that is a valid input, used for streaming.
+    fn valid_prefix(bytes: &[u8]) -> &Self;
+}
+
+impl ParseInput for str {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self.as_bytes()
+    }
+
+    #[inline]
+    fn slice_from(&self, from: usize) -> &Self {
+        &self[from..]
+    }
+
+    #[inline]
+    unsafe fn slice_from_unchecked(&self, from: usize) -> &Self {
+        // SAFETY: the preconditions are the same as for this function
+        unsafe { self.get_unchecked(from..) }
+    }
+
+    #[inline]
+    fn slice_to(&self, to: usize) -> &Self {
+        &self[..to]
+    }
+
+    #[inline]
+    fn first_unit_len(&self) -> Option<usize> {
+        self.chars().next().map(char::len_utf8)
+    }
+
+    fn debug_prefix(&self, n: usize) -> String {
+        format!("{:?}", self.chars().take(n).collect::<String>())
+    }
+
+    fn found(&self) -> (ParseErrorFound, usize) {
+        ParseErrorFound::from_input_with_len(self)
+    }
+
+    #[inline]
+    fn starts_with_word_char(&self) -> bool {
+        self.chars()
+            .next()
+            .is_some_and(|c| c.is_alphanumeric() || c == '_')
+    }
+
+    fn valid_prefix(bytes: &[u8]) -> &Self {
+        match std::str::from_utf8(bytes) {
+            Ok(s) => s,
+            // SAFETY: the bytes before `valid_up_to` were checked to be valid UTF-8
+            Err(e) => unsafe { std::str::from_utf8_unchecked(&bytes[..e.valid_up_to()]) },
+        }
+    }
+}
+
+impl ParseInput for [u8] {
+    #[inline]
+    fn len(&self) -> usize {
+        self.len()
+    }
+
+    #[inline]
+    fn as_bytes(&self) -> &[u8] {
+        self
+    }
+
+    #[inline]
+    fn slice_from(&self, from: usize) -> &Self {
+        &self[from..]
+    }
+
+    #[inline]
+    unsafe fn slice_from_unchecked(&self, from: usize) -> &Self {
+        // SAFETY: the preconditions are the same as for this function
+        unsafe { self.get_unchecked(from..) }
+    }
+
+    #[inline]
+    fn slice_to(&self, to: usize) -> &Self {
+        &self[..to]
+    }
+
+    #[inline]
+    fn first_unit_len(&self) -> Option<usize> {
+        (!self.is_empty()).then_some(1)
+    }
+
+    fn debug_prefix(&self, n: usize) -> String {
+        format!("b\"{}\"", self[..n.min(self.len())].escape_ascii())
+    }
+
+    fn found(&self) -> (ParseErrorFound, usize) {
+        ParseErrorFound::from_bytes_with_len(self)
+    }
+
+    #[inline]
+    fn starts_with_word_char(&self) -> bool {
+        self.first()
+            .is_some_and(|b| b.is_ascii_alphanumeric() || *b == b'_')
+    }
+
+    #[inline]
+    fn valid_prefix(bytes: &[u8]) -> &Self {
+        bytes
+    }
+}
@@ -0,0 +1,75 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use super::{ParseErrorSpecifics, ParseInput, ParseOk, ParseResult, ParseState};
+
+/// Match a keyword literal with `parse_literal`, and check that it is not followed by a word
+/// character, so that e.g. the keyword `if` does not match the start of `iffy`.
+#[inline(always)]
+pub fn parse_keyword<'a, T, I: ?Sized + ParseInput>(
+    state: ParseState<'a, I>,
+    keyword: &'static str,
+    parse_literal: impl FnOnce(ParseState<'a, I>) -> ParseResult<'a, T, I>,
+    followed_by_word_char: impl FnOnce(&ParseState<'a, I>) -> bool,
+) -> ParseResult<'a, T, I> {
+    let ParseOk {
+        result,
+        state: after,
+    } = parse_literal(state.clone())?;
+    let after = after.examine(1);
+    if followed_by_word_char(&after) {
+        Err(state
+            .merge_examined(after.examined_until())
+            .report_error(ParseErrorSpecifics::ExpectedKeyword { keyword }))
+    } else {
+        Ok(ParseOk {
+            result,
+            state: after,
+        })
+    }
+}
+
+/// The default keyword boundary check: whether the input continues with an alphanumeric
+/// character or an underscore.
+#[inline(always)]
+pub fn followed_by_word_char<I: ?Sized + ParseInput>(state: &ParseState<I>) -> bool {
+    state.s().starts_with_word_char()
+}
+
+/// Fail if a `@not_keyword` rule matched exactly one of the keywords of the grammar.
+///
+/// `insensitive_keywords` are the lowercase values of the case insensitive keywords.
+#[inline]
+pub fn check_not_keyword<'a, T, I: ?Sized + ParseInput>(
+    rule_start_state: &ParseState<'a, I>,
+    result: ParseResult<'a, T, I>,
+    keywords: &'static [&'static str],
+    insensitive_keywords: &'static [&'static str],
+) -> ParseResult<'a, T, I> {
+    let ok = result?;
+    let text = rule_start_state.slice_until(&ok.state).as_bytes();
+    let keyword = keywords
+        .iter()
+        .find(|keyword| keyword.as_bytes() == text)
+        .or_else(|| {
+            insensitive_keywords
+                .iter()
+                .find(|keyword| matches_insensitive(keyword, text))
+        });
+    match keyword {
+        Some(keyword) => Err(rule_start_state
+            .clone()
+            .merge_examined(ok.state.examined_until())
+            .report_error(ParseErrorSpecifics::UnexpectedKeyword { keyword })),
+        None => Ok(ok),
+    }
+}
+
+fn matches_insensitive(keyword: &str, text: &[u8]) -> bool {
+    if keyword.is_ascii() {
+        text.eq_ignore_ascii_case(keyword.as_bytes())
+    } else {
+        std::str::from_utf8(text).is_ok_and(|text| text.to_lowercase() == keyword)
+    }
+}
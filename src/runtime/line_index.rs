@@ -0,0 +1,137 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use std::ops::Range;
+
+/// The unit columns are counted in by [`LineIndex`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ColumnEncoding {
+    /// Unicode scalar values (Rust `char`s)
+    Chars,
+    /// UTF-8 bytes
+    Utf8,
+    /// UTF-16 code units, used e.g. by the Language Server Protocol
+    Utf16,
+}
+
+impl ColumnEncoding {
+    #[inline]
+    fn char_len(self, c: char) -> usize {
+        match self {
+            ColumnEncoding::Chars => 1,
+            ColumnEncoding::Utf8 => c.len_utf8(),
+            ColumnEncoding::Utf16 => c.len_utf16(),
+        }
+    }
+}
+
+/// A line-column position in a text. Both the line and the column start from 0.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct LineColumn {
+    pub line: usize,
+    pub column: usize,
+}
+
+/// Converts between byte offsets (used everywhere in peginator, e.g. [`super::ParseError`] and
+/// [`super::PegPosition`]) and line-column positions.
+///
+/// Lines are separated by `\n`, a `\r` before it is considered part of the line ending.
+#[derive(Debug, Clone)]
+pub struct LineIndex<'a> {
+    text: &'a str,
+    /// Byte offsets of the start of the lines. Always contains at least one element (0).
+    line_starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    /// Index the lines of `text`.
+    pub fn new(text: &'a str) -> Self {
+        let line_starts = std::iter::once(0)
+            .chain(text.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self { text, line_starts }
+    }
+
+    /// The indexed text
+    pub fn text(&self) -> &'a str {
+        self.text
+    }
+
+    /// Number of lines. A trailing line ending starts a new empty line.
+    pub fn line_count(&self) -> usize {
+        self.line_starts.len()
+    }
+
+    /// The byte range of the line, without the line ending.
+    pub fn line_range(&self, line: usize) -> Option<Range<usize>> {
+        let start = *self.line_starts.get(line)?;
+        let end = match self.line_starts.get(line + 1) {
+            Some(next_start) => next_start - 1,
+            None => self.text.len(),
+        };
+        let end = if self.text[start..end].ends_with('\r') {
+            end - 1
+        } else {
+            end
+        };
+        Some(start..end)
+    }
+
+    /// The contents of the line, without the line ending.
+    pub fn line_text(&self, line: usize) -> Option<&'a str> {
+        self.line_range(line).map(|range| &self.text[range])
+    }
+
+    /// Convert a byte offset to a line-column position.
+    ///
+    /// Offsets past the end of the text are clamped to the end, offsets inside a character are
+    /// moved back to its start.
+    pub fn line_column(&self, offset: usize, encoding: ColumnEncoding) -> LineColumn {
+        let mut offset = offset.min(self.text.len());
+        while !self.text.is_char_boundary(offset) {
+            offset -= 1;
+        }
+        let line = self.line_starts.partition_point(|&start| start <= offset) - 1;
+        let column = self.text[self.line_starts[line]..offset]
+            .chars()
+            .map(|c| encoding.char_len(c))
+            .sum();
+        LineColumn { line, column }
+    }
+
+    /// Convert a byte range to a line-column range. See [`LineIndex::line_column`]
+    pub fn line_column_range(
+        &self,
+        range: Range<usize>,
+        encoding: ColumnEncoding,
+    ) -> Range<LineColumn> {
+        self.line_column(range.start, encoding)..self.line_column(range.end, encoding)
+    }
+
+    /// Convert a line-column position to a byte offset.
+    ///
+    /// Columns past the end of the line are clamped to the end of the line (before the line
+    /// ending), columns inside a character are moved back to its start. Returns `None` if the
+    /// line does not exist.
+    pub fn offset(&self, position: LineColumn, encoding: ColumnEncoding) -> Option<usize> {
+        let line_range = self.line_range(position.line)?;
+        let mut column = 0;
+        for (i, c) in self.text[line_range.clone()].char_indices() {
+            column += encoding.char_len(c);
+            if column > position.column {
+                return Some(line_range.start + i);
+            }
+        }
+        Some(line_range.end)
+    }
+
+    /// Convert a line-column range to a byte range. See [`LineIndex::offset`]
+    pub fn offset_range(
+        &self,
+        range: Range<LineColumn>,
+        encoding: ColumnEncoding,
+    ) -> Option<Range<usize>> {
+        Some(self.offset(range.start, encoding)?..self.offset(range.end, encoding)?)
+    }
+}
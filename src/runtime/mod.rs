@@ -7,28 +7,60 @@ use std::collections::HashMap;
 use nohash_hasher::BuildNoHashHasher;
 
 mod builtin_parsers;
+mod check;
 mod choice_helper;
+mod diagnostic;
 mod error;
+mod extern_result;
+mod failure;
 mod global;
+mod incremental;
+mod input;
+mod keyword;
+mod line_index;
+mod parse_iter;
 mod parse_result;
 mod peg_parser;
+mod recovery;
 mod state;
+mod stream;
 mod trace;
 
 pub use builtin_parsers::{
-    parse_Whitespace, parse_char, parse_character_literal, parse_character_literal_insensitive,
+    parse_Whitespace, parse_byte, parse_byte_literal, parse_byte_literal_insensitive,
+    parse_byte_range, parse_byte_string_literal, parse_byte_string_literal_insensitive, parse_char,
+    parse_character_class, parse_character_literal, parse_character_literal_insensitive,
     parse_character_range, parse_end_of_input, parse_string_literal,
-    parse_string_literal_insensitive,
+    parse_string_literal_case_folded, parse_string_literal_insensitive,
 };
+pub use check::CheckResult;
 pub use choice_helper::ChoiceHelper;
-pub use error::{ParseError, ParseErrorSpecifics, PrettyParseError};
+pub use diagnostic::{Diagnostic, DiagnosticLine};
+pub use error::{
+    ParseError, ParseErrorFound, ParseErrorSpecifics, PrettyParseError, PrettyParseErrorSettings,
+};
+pub use extern_result::ExternResult;
+pub use failure::{FarthestError, ParseFailure};
 pub use global::ParseGlobal;
+pub use incremental::{
+    DetachedCacheEntries, DetachedState, IncrementalCacheEntries, IncrementalParse,
+    PegIncrementalParser, PegIncrementalParserAdvanced, ShiftPositions, TextEdit,
+};
+pub use input::ParseInput;
+pub use keyword::{check_not_keyword, followed_by_word_char, parse_keyword};
+pub use line_index::{ColumnEncoding, LineColumn, LineIndex};
+pub use parse_iter::{ParseIter, ParsedItem};
 pub use parse_result::{ParseOk, ParseResult, ParseResultExtras};
-pub use peg_parser::{ParseSettings, PegParser, PegParserAdvanced};
+pub use peg_parser::{
+    ParseSettings, PegBorrowedParser, PegByteParser, PegParser, PegParserAdvanced,
+};
+pub use recovery::recover_from_error;
 pub use state::ParseState;
+pub use stream::{ChunkSource, ReadSource, StreamParseError, StreamParseIter, StreamSource};
 pub use trace::{IndentedTracer, NoopTracer, ParseTracer};
 
-pub type CacheEntries<'a, T> = HashMap<usize, ParseResult<'a, T>, BuildNoHashHasher<usize>>;
+pub type CacheEntries<'a, T, I = str> =
+    HashMap<usize, ParseResult<'a, T, I>, BuildNoHashHasher<usize>>;
 
 /// Helper trait to get the parse position of the parsed rule
 ///
@@ -0,0 +1,78 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use std::{marker::PhantomData, ops::Range};
+
+use super::{NoopTracer, ParseError, ParseInput, ParseSettings, PegParserAdvanced};
+
+/// An item parsed by [`PegParserAdvanced::parse_advanced_item`].
+#[derive(Debug, Clone)]
+pub struct ParsedItem<T> {
+    pub result: T,
+    /// The range of the item in the input, without the whitespace before it.
+    pub range: Range<usize>,
+    /// The end of the input range that was looked at while parsing the item.
+    pub examined_until: usize,
+}
+
+/// Iterator over the items of an input, parsed one by one with the same rule.
+///
+/// Created by [`PegParser::parse_iter`](super::PegParser::parse_iter).
+pub struct ParseIter<'a, T, I: ?Sized + ParseInput = str> {
+    s: &'a I,
+    position: usize,
+    finished: bool,
+    _result: PhantomData<T>,
+}
+
+impl<'a, T, I: ?Sized + ParseInput> ParseIter<'a, T, I> {
+    pub fn new(s: &'a I) -> Self {
+        Self {
+            s,
+            position: 0,
+            finished: false,
+            _result: PhantomData,
+        }
+    }
+
+    /// The position where the next item will be parsed from, in bytes.
+    pub fn position(&self) -> usize {
+        self.position
+    }
+}
+
+impl<'a, T: PegParserAdvanced<'a, (), I>, I: ?Sized + ParseInput + 'a> Iterator
+    for ParseIter<'a, T, I>
+{
+    type Item = Result<T, ParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        // A new parse is started for every item, so the memoization cache does not grow.
+        let result = T::parse_advanced_item::<NoopTracer>(
+            self.s.slice_from(self.position),
+            self.position,
+            &ParseSettings::default(),
+            (),
+        );
+        match result {
+            None => {
+                self.finished = true;
+                None
+            }
+            Some(Ok(item)) => {
+                // An empty item would be parsed forever.
+                self.finished = item.range.is_empty();
+                self.position = item.range.end;
+                Some(Ok(item.result))
+            }
+            Some(Err(err)) => {
+                self.finished = true;
+                Some(Err(err))
+            }
+        }
+    }
+}
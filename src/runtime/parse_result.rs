@@ -2,58 +2,118 @@
 // This file is part of peginator
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
-use super::{ParseError, ParseState};
+use super::{ParseError, ParseFailure, ParseInput, ParseState};
 
-#[derive(Debug, Clone)]
-pub struct ParseOk<'a, T> {
+#[derive(Debug)]
+pub struct ParseOk<'a, T, I: ?Sized + ParseInput = str> {
     pub result: T,
-    pub state: ParseState<'a>,
+    pub state: ParseState<'a, I>,
 }
 
-impl<'a, T> ParseOk<'a, T> {
+impl<'a, T: Clone, I: ?Sized + ParseInput> Clone for ParseOk<'a, T, I> {
     #[inline]
-    pub fn map<T2, F>(self, f: F) -> ParseOk<'a, T2>
+    fn clone(&self) -> Self {
+        Self {
+            result: self.result.clone(),
+            state: self.state.clone(),
+        }
+    }
+}
+
+impl<'a, T, I: ?Sized + ParseInput> ParseOk<'a, T, I> {
+    #[inline]
+    pub fn map<T2, F>(self, f: F) -> ParseOk<'a, T2, I>
     where
         F: Fn(T) -> T2,
     {
-        ParseOk::<T2> {
+        ParseOk {
             result: f(self.result),
             state: self.state,
         }
     }
 
     #[inline]
-    pub fn map_with_state<T2, F>(self, f: F) -> ParseOk<'a, T2>
+    pub fn map_with_state<T2, F>(self, f: F) -> ParseOk<'a, T2, I>
     where
-        F: Fn(T, &ParseState) -> T2,
+        F: Fn(T, &ParseState<I>) -> T2,
     {
-        ParseOk::<T2> {
+        ParseOk {
             result: f(self.result, &self.state),
             state: self.state,
         }
     }
 }
 
-pub type ParseResult<'a, T> = Result<ParseOk<'a, T>, ParseError>;
+pub type ParseResult<'a, T, I = str> = Result<ParseOk<'a, T, I>, ParseFailure>;
 
-pub trait ParseResultExtras<'a, T> {
-    fn discard_result(self) -> ParseResult<'a, ()>;
-    fn map_inner<T2, F>(self, f: F) -> ParseResult<'a, T2>
+pub trait ParseResultExtras<'a, T, I: ?Sized + ParseInput = str> {
+    fn discard_result(self) -> ParseResult<'a, (), I>;
+    fn map_inner<T2, F>(self, f: F) -> ParseResult<'a, T2, I>
     where
         F: Fn(T) -> T2;
+    /// Modify the farthest error, both in the error and in the successful cases.
+    fn map_farthest_error(self, f: impl FnOnce(ParseError) -> ParseError) -> ParseResult<'a, T, I>;
+    /// Record that the farthest error happened inside the rule `rule_name`, both in the error and
+    /// in the successful cases.
+    fn push_error_context(self, rule_name: &'static str) -> ParseResult<'a, T, I>;
+    /// The end of the input range that was looked at, both in the error and in the successful cases.
+    fn examined_until(&self) -> usize;
+    /// Record that the input was looked at until `examined_until`.
+    fn merge_examined(self, examined_until: usize) -> ParseResult<'a, T, I>;
 }
 
-impl<'a, T> ParseResultExtras<'a, T> for ParseResult<'a, T> {
+impl<'a, T, I: ?Sized + ParseInput> ParseResultExtras<'a, T, I> for ParseResult<'a, T, I> {
     #[inline]
-    fn discard_result(self) -> ParseResult<'a, ()> {
+    fn discard_result(self) -> ParseResult<'a, (), I> {
         self.map_inner(|_| ())
     }
 
     #[inline]
-    fn map_inner<T2, F>(self, f: F) -> ParseResult<'a, T2>
+    fn map_inner<T2, F>(self, f: F) -> ParseResult<'a, T2, I>
     where
         F: Fn(T) -> T2,
     {
         self.map(|ok| ok.map(f))
     }
+
+    #[inline]
+    fn map_farthest_error(self, f: impl FnOnce(ParseError) -> ParseError) -> ParseResult<'a, T, I> {
+        match self {
+            Ok(ParseOk { result, state }) => Ok(ParseOk {
+                result,
+                state: state.map_farthest_error(f),
+            }),
+            Err(err) => Err(err.map_error(f)),
+        }
+    }
+
+    #[inline]
+    fn push_error_context(self, rule_name: &'static str) -> ParseResult<'a, T, I> {
+        match self {
+            Ok(ParseOk { result, state }) => Ok(ParseOk {
+                result,
+                state: state.push_error_context(rule_name),
+            }),
+            Err(err) => Err(err.push_context(rule_name)),
+        }
+    }
+
+    #[inline]
+    fn examined_until(&self) -> usize {
+        match self {
+            Ok(ok) => ok.state.examined_until(),
+            Err(err) => err.examined_until(),
+        }
+    }
+
+    #[inline]
+    fn merge_examined(self, examined_until: usize) -> ParseResult<'a, T, I> {
+        match self {
+            Ok(ParseOk { result, state }) => Ok(ParseOk {
+                result,
+                state: state.merge_examined(examined_until),
+            }),
+            Err(err) => Err(err.merge_examined(examined_until)),
+        }
+    }
 }
@@ -2,7 +2,12 @@
 // This file is part of peginator
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
-use super::{IndentedTracer, NoopTracer, ParseError, ParseTracer};
+use std::io::Read;
+
+use super::{
+    ChunkSource, IndentedTracer, NoopTracer, ParseError, ParseInput, ParseIter, ParseTracer,
+    ParsedItem, ReadSource, StreamParseIter,
+};
 
 /// The main trait for interfacing with peginator. Implemented by `@export`-ed rules.
 pub trait PegParser: Sized {
@@ -13,29 +18,261 @@ pub trait PegParser: Sized {
     ///
     /// The printing happens with regular `eprintln!()`.
     fn parse_with_trace(s: &str) -> Result<Self, ParseError>;
+
+    /// Parse a string into the AST, recovering from errors in `@recover` rules.
+    ///
+    /// Returns the best-effort AST (if the parse could be finished at all), and all errors that
+    /// happened during parsing. The parse was fully successful if the error list is empty.
+    fn parse_with_recovery(s: &str) -> (Option<Self>, Vec<ParseError>);
+
+    /// Parse the beginning of a string into the AST, and return it with the length of the
+    /// parsed part in bytes.
+    ///
+    /// Whitespace after the parsed part is not consumed.
+    fn parse_prefix(s: &str) -> Result<(Self, usize), ParseError>;
+
+    /// Parse a string into the AST, starting `offset` bytes into it, and return it with the
+    /// position where the parse ended.
+    ///
+    /// All positions (in the AST, the error and the returned one) are relative to the start of
+    /// `s`. Panics if `offset` is out of bounds, or not on a character boundary.
+    fn parse_at(s: &str, offset: usize) -> Result<(Self, usize), ParseError>;
+
+    /// Parse a string as a sequence of items, lazily.
+    ///
+    /// The rule is applied repeatedly (skipping whitespace before the items), until the end of the
+    /// input, or the first error, which is the last item returned. The memoization cache is
+    /// cleared between items, so memory usage only depends on the size of a single item.
+    fn parse_iter(s: &str) -> ParseIter<'_, Self>;
+
+    /// Parse the items of a streamed input lazily, like [`PegParser::parse_iter`].
+    ///
+    /// Only the input needed for parsing the current item is kept in memory.
+    fn parse_reader<R: Read>(reader: R) -> StreamParseIter<Self, ReadSource<R>>;
+
+    /// Parse the items of an input that arrives in chunks lazily, like
+    /// [`PegParser::parse_reader`].
+    ///
+    /// Characters may be split between chunks.
+    fn parse_chunks<C: AsRef<[u8]>, It: IntoIterator<Item = C>>(
+        chunks: It,
+    ) -> StreamParseIter<Self, ChunkSource<It::IntoIter>>;
 }
 
-impl<T: PegParserAdvanced<()>> PegParser for T {
+impl<T: for<'a> PegParserAdvanced<'a, ()>> PegParser for T {
     fn parse(s: &str) -> Result<Self, ParseError> {
         Self::parse_advanced::<NoopTracer>(s, &ParseSettings::default(), ())
     }
     fn parse_with_trace(s: &str) -> Result<Self, ParseError> {
         Self::parse_advanced::<IndentedTracer>(s, &ParseSettings::default(), ())
     }
+    fn parse_with_recovery(s: &str) -> (Option<Self>, Vec<ParseError>) {
+        Self::parse_advanced_with_recovery::<NoopTracer>(s, &ParseSettings::default(), ())
+    }
+    fn parse_prefix(s: &str) -> Result<(Self, usize), ParseError> {
+        Self::parse_advanced_at::<NoopTracer>(s, 0, &ParseSettings::default(), ())
+    }
+    fn parse_at(s: &str, offset: usize) -> Result<(Self, usize), ParseError> {
+        Self::parse_advanced_at::<NoopTracer>(s, offset, &ParseSettings::default(), ())
+    }
+    fn parse_iter(s: &str) -> ParseIter<'_, Self> {
+        ParseIter::new(s)
+    }
+    fn parse_reader<R: Read>(reader: R) -> StreamParseIter<Self, ReadSource<R>> {
+        StreamParseIter::new(ReadSource(reader))
+    }
+    fn parse_chunks<C: AsRef<[u8]>, It: IntoIterator<Item = C>>(
+        chunks: It,
+    ) -> StreamParseIter<Self, ChunkSource<It::IntoIter>> {
+        StreamParseIter::new(ChunkSource(chunks.into_iter()))
+    }
+}
+
+/// The main trait for interfacing with peginator for grammars compiled with byte input (see
+/// `CodegenSettings::byte_input`). Implemented by `@export`-ed rules.
+pub trait PegByteParser: Sized {
+    /// Parse a byte slice into the AST.
+    fn parse(s: &[u8]) -> Result<Self, ParseError>;
+
+    /// Parse a byte slice into the AST, print a colored trace of the parse process.
+    ///
+    /// The printing happens with regular `eprintln!()`.
+    fn parse_with_trace(s: &[u8]) -> Result<Self, ParseError>;
+
+    /// Parse a byte slice into the AST, recovering from errors in `@recover` rules.
+    ///
+    /// See [`PegParser::parse_with_recovery`].
+    fn parse_with_recovery(s: &[u8]) -> (Option<Self>, Vec<ParseError>);
+
+    /// Parse the beginning of a byte slice into the AST, and return it with the length of the
+    /// parsed part.
+    ///
+    /// See [`PegParser::parse_prefix`].
+    fn parse_prefix(s: &[u8]) -> Result<(Self, usize), ParseError>;
+
+    /// Parse a byte slice into the AST, starting `offset` bytes into it, and return it with the
+    /// position where the parse ended.
+    ///
+    /// See [`PegParser::parse_at`].
+    fn parse_at(s: &[u8], offset: usize) -> Result<(Self, usize), ParseError>;
+
+    /// Parse a byte slice as a sequence of items, lazily.
+    ///
+    /// See [`PegParser::parse_iter`].
+    fn parse_iter(s: &[u8]) -> ParseIter<'_, Self, [u8]>;
+
+    /// Parse the items of a streamed input lazily.
+    ///
+    /// See [`PegParser::parse_reader`].
+    fn parse_reader<R: Read>(reader: R) -> StreamParseIter<Self, ReadSource<R>, [u8]>;
+
+    /// Parse the items of an input that arrives in chunks lazily.
+    ///
+    /// See [`PegParser::parse_chunks`].
+    fn parse_chunks<C: AsRef<[u8]>, It: IntoIterator<Item = C>>(
+        chunks: It,
+    ) -> StreamParseIter<Self, ChunkSource<It::IntoIter>, [u8]>;
+}
+
+impl<T: for<'a> PegParserAdvanced<'a, (), [u8]>> PegByteParser for T {
+    fn parse(s: &[u8]) -> Result<Self, ParseError> {
+        Self::parse_advanced::<NoopTracer>(s, &ParseSettings::default(), ())
+    }
+    fn parse_with_trace(s: &[u8]) -> Result<Self, ParseError> {
+        Self::parse_advanced::<IndentedTracer>(s, &ParseSettings::default(), ())
+    }
+    fn parse_with_recovery(s: &[u8]) -> (Option<Self>, Vec<ParseError>) {
+        Self::parse_advanced_with_recovery::<NoopTracer>(s, &ParseSettings::default(), ())
+    }
+    fn parse_prefix(s: &[u8]) -> Result<(Self, usize), ParseError> {
+        Self::parse_advanced_at::<NoopTracer>(s, 0, &ParseSettings::default(), ())
+    }
+    fn parse_at(s: &[u8], offset: usize) -> Result<(Self, usize), ParseError> {
+        Self::parse_advanced_at::<NoopTracer>(s, offset, &ParseSettings::default(), ())
+    }
+    fn parse_iter(s: &[u8]) -> ParseIter<'_, Self, [u8]> {
+        ParseIter::new(s)
+    }
+    fn parse_reader<R: Read>(reader: R) -> StreamParseIter<Self, ReadSource<R>, [u8]> {
+        StreamParseIter::new(ReadSource(reader))
+    }
+    fn parse_chunks<C: AsRef<[u8]>, It: IntoIterator<Item = C>>(
+        chunks: It,
+    ) -> StreamParseIter<Self, ChunkSource<It::IntoIter>, [u8]> {
+        StreamParseIter::new(ChunkSource(chunks.into_iter()))
+    }
+}
+
+/// The main trait for interfacing with peginator for grammars compiled with borrowed strings (see
+/// `CodegenSettings::borrowed_strings`), where the parsed AST borrows from the input.
+///
+/// Implemented by all `@export`-ed rules, `I` is `[u8]` for grammars compiled with byte input.
+/// The methods are named differently from the ones of [`PegParser`] and [`PegByteParser`], which
+/// the rules without borrowed strings implement too, so that calls are never ambiguous.
+pub trait PegBorrowedParser<'a, I: ?Sized + ParseInput + 'a = str>: Sized {
+    /// Parse the input into the AST.
+    fn parse_borrowed(s: &'a I) -> Result<Self, ParseError>;
+
+    /// Parse the input into the AST, print a colored trace of the parse process.
+    ///
+    /// The printing happens with regular `eprintln!()`.
+    fn parse_borrowed_with_trace(s: &'a I) -> Result<Self, ParseError>;
+
+    /// Parse the input into the AST, recovering from errors in `@recover` rules.
+    ///
+    /// See [`PegParser::parse_with_recovery`].
+    fn parse_borrowed_with_recovery(s: &'a I) -> (Option<Self>, Vec<ParseError>);
+
+    /// Parse the beginning of the input into the AST, and return it with the length of the
+    /// parsed part in bytes.
+    ///
+    /// See [`PegParser::parse_prefix`].
+    fn parse_borrowed_prefix(s: &'a I) -> Result<(Self, usize), ParseError>;
+
+    /// Parse the input into the AST, starting `offset` bytes into it, and return it with the
+    /// position where the parse ended.
+    ///
+    /// See [`PegParser::parse_at`].
+    fn parse_borrowed_at(s: &'a I, offset: usize) -> Result<(Self, usize), ParseError>;
+
+    /// Parse the input as a sequence of items, lazily.
+    ///
+    /// See [`PegParser::parse_iter`].
+    fn parse_borrowed_iter(s: &'a I) -> ParseIter<'a, Self, I>;
+}
+
+impl<'a, I: ?Sized + ParseInput + 'a, T: PegParserAdvanced<'a, (), I>> PegBorrowedParser<'a, I>
+    for T
+{
+    fn parse_borrowed(s: &'a I) -> Result<Self, ParseError> {
+        Self::parse_advanced::<NoopTracer>(s, &ParseSettings::default(), ())
+    }
+    fn parse_borrowed_with_trace(s: &'a I) -> Result<Self, ParseError> {
+        Self::parse_advanced::<IndentedTracer>(s, &ParseSettings::default(), ())
+    }
+    fn parse_borrowed_with_recovery(s: &'a I) -> (Option<Self>, Vec<ParseError>) {
+        Self::parse_advanced_with_recovery::<NoopTracer>(s, &ParseSettings::default(), ())
+    }
+    fn parse_borrowed_prefix(s: &'a I) -> Result<(Self, usize), ParseError> {
+        Self::parse_advanced_at::<NoopTracer>(s, 0, &ParseSettings::default(), ())
+    }
+    fn parse_borrowed_at(s: &'a I, offset: usize) -> Result<(Self, usize), ParseError> {
+        Self::parse_advanced_at::<NoopTracer>(s, offset, &ParseSettings::default(), ())
+    }
+    fn parse_borrowed_iter(s: &'a I) -> ParseIter<'a, Self, I> {
+        ParseIter::new(s)
+    }
 }
 
 // Internal trait for generated code
-pub trait PegParserAdvanced<TUD>: Sized {
-    /// Internal function that is actually generated by the grammar compiler, used by the more
-    /// friendly functions.
+pub trait PegParserAdvanced<'a, TUD, I: ?Sized + ParseInput + 'a = str>: Sized {
+    /// Internal function used by the more friendly functions.
     fn parse_advanced<TT: ParseTracer>(
-        s: &str,
+        s: &'a I,
         settings: &ParseSettings,
         user_defined: TUD,
-    ) -> Result<Self, ParseError>;
+    ) -> Result<Self, ParseError> {
+        Ok(Self::parse_advanced_at::<TT>(s, 0, settings, user_defined)?.0)
+    }
+
+    /// Internal function that is actually generated by the grammar compiler, used by the more
+    /// friendly functions. Starts parsing `offset` bytes into `s`, and also returns the position
+    /// where the parse ended.
+    fn parse_advanced_at<TT: ParseTracer>(
+        s: &'a I,
+        offset: usize,
+        settings: &ParseSettings,
+        user_defined: TUD,
+    ) -> Result<(Self, usize), ParseError>;
+
+    /// Internal function that is actually generated by the grammar compiler, used by
+    /// [`ParseIter`]. Parses a single item from the start of `s` (after skipping whitespace),
+    /// which is the part of the input that starts `base` bytes into it. Returns `None` if there
+    /// is nothing left to parse.
+    fn parse_advanced_item<TT: ParseTracer>(
+        s: &'a I,
+        base: usize,
+        settings: &ParseSettings,
+        user_defined: TUD,
+    ) -> Option<Result<ParsedItem<Self>, ParseError>>;
+
+    /// Internal function that is actually generated by the grammar compiler, used by the more
+    /// friendly functions. Same as `parse_advanced`, but with error recovery enabled.
+    fn parse_advanced_with_recovery<TT: ParseTracer>(
+        s: &'a I,
+        settings: &ParseSettings,
+        user_defined: TUD,
+    ) -> (Option<Self>, Vec<ParseError>);
 }
 
-/// Parse settings (for future compatibility)
+/// Parse settings
+///
+/// Start from `ParseSettings::default()` and modify the fields as needed.
 #[derive(Debug, Default)]
 #[non_exhaustive]
-pub struct ParseSettings {}
+pub struct ParseSettings {
+    /// Record the stack of rules the farthest error happened in, see [`ParseError::context`].
+    ///
+    /// Makes parsing slower, as the errors of every rule have to be handled separately.
+    pub record_context: bool,
+}
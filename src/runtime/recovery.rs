@@ -0,0 +1,63 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use super::{
+    ParseError, ParseFailure, ParseGlobal, ParseInput, ParseOk, ParseResult, ParseState,
+    ParseTracer,
+};
+
+/// Error recovery for `@recover` rules.
+///
+/// Skips the input starting at `state` until the `sync` parser matches (and consumes the match),
+/// or until the end of input. The error is recorded, and returned as the result of the failed
+/// rule, marking the skipped part in the parsed tree. The errors recovered from during the failed
+/// rule (i.e. since there were `recovered_errors` of them) and the `sync` parses are inside the
+/// skipped input, so they are forgotten.
+///
+/// If no input could be skipped, the original error is returned.
+///
+/// `sync` is tried at every character (or byte) after `state`, so a recovery costs the length of
+/// the skipped input times the cost of a failed `sync` parse. Sync rules are meant to be short,
+/// like a single token, to keep this cheap.
+#[inline]
+pub fn recover_from_error<'a, T, S, TT, TC, TUD, I>(
+    state: ParseState<'a, I>,
+    recovered_errors: usize,
+    error: ParseFailure,
+    global: &mut ParseGlobal<TT, TC, TUD>,
+    sync: impl Fn(ParseState<'a, I>, &mut ParseGlobal<TT, TC, TUD>) -> ParseResult<'a, S, I>,
+) -> ParseResult<'a, Result<T, ParseError>, I>
+where
+    TT: ParseTracer,
+    I: ?Sized + ParseInput,
+{
+    let mut skip_state = state.clone();
+    let end_state = loop {
+        if let Ok(ParseOk {
+            state: sync_end, ..
+        }) = global.lookahead(|global| sync(skip_state.clone(), global))
+        {
+            if sync_end.is_further_than(&state) {
+                break sync_end;
+            }
+        }
+        let Some(skip_length) = skip_state.s().first_unit_len() else {
+            if skip_state.is_further_than(&state) {
+                break skip_state;
+            }
+            return Err(error);
+        };
+        skip_state = skip_state.advance_safe(skip_length);
+    };
+    global.tracer.print_informative("Recovered from error");
+    global.recovered_errors.truncate(recovered_errors);
+    let error = error
+        .into_error()
+        .with_found_in(state.s(), state.position());
+    global.record_recovered_error(error.clone());
+    Ok(ParseOk {
+        result: Err(error),
+        state: end_state.discard_errors(),
+    })
+}
@@ -2,27 +2,83 @@
 // This file is part of peginator
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 
-use super::{ParseError, ParseErrorSpecifics, ParseSettings};
+use super::{
+    DetachedState, FarthestError, ParseError, ParseErrorSpecifics, ParseFailure, ParseInput,
+    ParseOk, ParseResult, ParseResultExtras, ParseSettings,
+};
 
-#[derive(Debug, Clone)]
-pub struct ParseState<'a> {
-    partial_string: &'a str,
+#[derive(Debug)]
+pub struct ParseState<'a, I: ?Sized + ParseInput = str> {
+    partial_string: &'a I,
     start_index: usize,
-    farthest_error: Option<ParseError>,
+    farthest_error: Option<FarthestError>,
+    /// The end of the input range that was looked at by the parse so far.
+    examined_until: usize,
 }
 
-impl<'a> ParseState<'a> {
+impl<'a, I: ?Sized + ParseInput> Clone for ParseState<'a, I> {
     #[inline]
-    pub fn new(s: &'a str, _settings: &ParseSettings) -> ParseState<'a> {
+    fn clone(&self) -> Self {
+        Self {
+            partial_string: self.partial_string,
+            start_index: self.start_index,
+            farthest_error: self.farthest_error.clone(),
+            examined_until: self.examined_until,
+        }
+    }
+}
+
+impl<'a, I: ?Sized + ParseInput> ParseState<'a, I> {
+    #[inline]
+    pub fn new(s: &'a I, settings: &ParseSettings) -> Self {
+        Self::new_at(s, 0, settings)
+    }
+
+    /// Start parsing `s`, which is the part of the input that starts `base` bytes into it. The
+    /// positions are relative to the start of the whole input.
+    #[inline]
+    pub fn new_with_base(s: &'a I, base: usize, _settings: &ParseSettings) -> Self {
         Self {
             partial_string: s,
-            start_index: 0,
+            start_index: base,
             farthest_error: None,
+            examined_until: base,
         }
     }
 
+    /// Start parsing at `offset` bytes into `s`. The positions are still relative to the start of
+    /// `s`. Panics if `offset` is out of bounds, or not on a character boundary.
     #[inline]
-    pub fn s(&self) -> &str {
+    pub fn new_at(s: &'a I, offset: usize, _settings: &ParseSettings) -> Self {
+        Self {
+            partial_string: s.slice_from(offset),
+            start_index: offset,
+            farthest_error: None,
+            examined_until: offset,
+        }
+    }
+
+    /// Detach the state from the input, see [`DetachedState`].
+    pub(super) fn detach(self) -> DetachedState {
+        DetachedState {
+            position: self.start_index,
+            farthest_error: self.farthest_error.map(|err| err.to_error()),
+            examined_until: self.examined_until,
+        }
+    }
+
+    /// Reattach a state to the (possibly edited) input.
+    pub(super) fn attach(s: &'a I, detached: DetachedState) -> Self {
+        Self {
+            partial_string: s.slice_from(detached.position),
+            start_index: detached.position,
+            farthest_error: detached.farthest_error.map(FarthestError::from_error),
+            examined_until: detached.examined_until,
+        }
+    }
+
+    #[inline]
+    pub fn s(&self) -> &'a I {
         self.partial_string
     }
 
@@ -48,12 +104,13 @@ impl<'a> ParseState<'a> {
         };
         Self {
             start_index: self.start_index + length,
+            examined_until: self.examined_until.max(self.start_index + length),
             // SAFETY:
             // Callers of this function are responsible that these preconditions are satisfied:
             //    Indexes must lie on UTF-8 sequence boundaries.
             //    The starting index must not exceed the ending index;
             //    Indexes must be within bounds of the original slice;
-            partial_string: unsafe { self.partial_string.get_unchecked(length..) },
+            partial_string: unsafe { self.partial_string.slice_from_unchecked(length) },
             ..self
         }
     }
@@ -67,59 +124,218 @@ impl<'a> ParseState<'a> {
         };
         Self {
             start_index: self.start_index + length,
-            partial_string: &self.partial_string[length..],
+            examined_until: self.examined_until.max(self.start_index + length),
+            partial_string: self.partial_string.slice_from(length),
             ..self
         }
     }
 
     #[inline]
-    pub fn slice_until(&self, other: &ParseState) -> &str {
-        &self.partial_string[..(other.start_index - self.start_index)]
+    pub fn slice_until(&self, other: &ParseState<I>) -> &'a I {
+        self.partial_string
+            .slice_to(other.start_index - self.start_index)
     }
 
     #[inline]
-    pub fn range_until(&self, other: &ParseState) -> std::ops::Range<usize> {
+    pub fn range_until(&self, other: &ParseState<I>) -> std::ops::Range<usize> {
         self.start_index..other.start_index
     }
 
+    /// The current parsing position in bytes.
+    #[inline]
+    pub fn position(&self) -> usize {
+        self.start_index
+    }
+
     #[inline]
     pub fn cache_key(&self) -> usize {
         self.start_index
     }
 
     #[inline]
-    pub fn report_error(self, specifics: ParseErrorSpecifics) -> ParseError {
+    pub fn report_error(self, specifics: ParseErrorSpecifics) -> ParseFailure {
         let position = self.start_index;
-        self.record_error(ParseError {
-            position,
-            specifics,
-        })
-        .report_farthest_error()
+        let examined_until = position + examined_length(&specifics);
+        let mut state = self.merge_examined(examined_until);
+        match &mut state.farthest_error {
+            Some(farthest_error) if farthest_error.position() > position => (),
+            Some(farthest_error) if farthest_error.position() == position => {
+                farthest_error.add_specifics(specifics)
+            }
+            _ => {
+                state.farthest_error = Some(FarthestError::new(position, specifics, examined_until))
+            }
+        }
+        state.report_farthest_error()
     }
 
     #[inline]
-    pub fn record_error(mut self, error: ParseError) -> Self {
+    pub fn record_error(self, failure: ParseFailure) -> Self {
+        let (error, examined_until) = failure.into_farthest_error();
+        self.merge_examined(examined_until)
+            .record_farthest_error(error)
+    }
+
+    #[inline]
+    fn record_farthest_error(mut self, error: FarthestError) -> Self {
         match &mut self.farthest_error {
-            Some(farthest_error) => {
-                if farthest_error.position <= error.position {
-                    *farthest_error = error
-                }
+            Some(farthest_error) if farthest_error.position() < error.position() => {
+                *farthest_error = error
             }
+            Some(farthest_error) if farthest_error.position() == error.position() => {
+                farthest_error.merge(&error)
+            }
+            Some(_) => (),
             None => self.farthest_error = Some(error),
         }
         self
     }
 
     #[inline]
-    pub fn report_farthest_error(self) -> ParseError {
-        self.farthest_error.unwrap_or(ParseError {
-            position: self.start_index,
-            specifics: ParseErrorSpecifics::Other,
-        })
+    pub fn report_farthest_error(self) -> ParseFailure {
+        let error = self
+            .farthest_error
+            .unwrap_or_else(|| FarthestError::new(self.start_index, ParseErrorSpecifics::Other, 0));
+        ParseFailure::new(error, self.examined_until)
     }
 
-    pub fn first_n_chars(&self, n: usize) -> String {
-        self.s().chars().take(n).collect()
+    /// The end of the input range that was looked at by the parse so far.
+    #[inline]
+    pub fn examined_until(&self) -> usize {
+        self.examined_until
+    }
+
+    /// Record that the next `length` bytes of the input were looked at.
+    #[inline]
+    pub fn examine(self, length: usize) -> Self {
+        let examined_until = self.start_index + length;
+        self.merge_examined(examined_until)
+    }
+
+    /// Record that the input was looked at until `examined_until`, e.g. by a lookahead.
+    #[inline]
+    pub fn merge_examined(mut self, examined_until: usize) -> Self {
+        // Updated in place: rebuilding the whole state here made recording errors measurably
+        // slower.
+        self.examined_until = self.examined_until.max(examined_until);
+        self
+    }
+
+    /// Start recording the examined input range from the current position. Used by memoized
+    /// rules, so that the cached results only depend on the input they looked at.
+    #[inline]
+    pub fn reset_examined(self) -> Self {
+        Self {
+            examined_until: self.start_index,
+            ..self
+        }
+    }
+
+    /// Forget the recorded farthest error, e.g. after recovering from it.
+    #[inline]
+    pub fn discard_errors(self) -> Self {
+        Self {
+            farthest_error: None,
+            ..self
+        }
+    }
+
+    /// Take out the recorded farthest error, so that the errors of a rule can be handled
+    /// separately. Put it back with [`ParseState::restore_farthest_error`].
+    #[inline]
+    pub fn take_farthest_error(self) -> (Self, Option<FarthestError>) {
+        (
+            Self {
+                farthest_error: None,
+                ..self
+            },
+            self.farthest_error,
+        )
+    }
+
+    /// Merge the farthest error taken out with [`ParseState::take_farthest_error`] back into the
+    /// result of the rule that started at this state.
+    pub fn restore_farthest_error<T>(
+        self,
+        result: ParseResult<'a, T, I>,
+        outer_error: Option<FarthestError>,
+    ) -> ParseResult<'a, T, I> {
+        let outer_examined_until = self.examined_until;
+        let rule_start = self.start_index;
+        let outer_state = Self {
+            farthest_error: outer_error,
+            ..self
+        };
+        match result {
+            Ok(ParseOk { result, state }) => {
+                let farthest_error = match state.farthest_error {
+                    Some(mut err) => {
+                        err.set_rule_start(rule_start);
+                        outer_state.record_farthest_error(err).farthest_error
+                    }
+                    None => outer_state.farthest_error,
+                };
+                Ok(ParseOk {
+                    result,
+                    state: Self {
+                        farthest_error,
+                        examined_until: state.examined_until.max(outer_examined_until),
+                        ..state
+                    },
+                })
+            }
+            Err(err) => Err(outer_state
+                .record_error(err.set_rule_start(rule_start))
+                .report_farthest_error()),
+        }
+    }
+
+    /// Parse a rule with `parse`. If `record_context` is set, the errors of the rule are
+    /// separated from the ones recorded before it, so that `rule_name` is only added to the
+    /// context of the former.
+    #[inline]
+    pub fn parse_rule<T>(
+        self,
+        rule_name: &'static str,
+        record_context: bool,
+        parse: impl FnOnce(Self) -> ParseResult<'a, T, I>,
+    ) -> ParseResult<'a, T, I> {
+        if !record_context {
+            return parse(self);
+        }
+        let (state, outer_error) = self.take_farthest_error();
+        let rule_start_state = state.clone();
+        let result = parse(state).push_error_context(rule_name);
+        rule_start_state.restore_farthest_error(result, outer_error)
+    }
+
+    /// Modify the recorded farthest error, if any.
+    #[inline]
+    pub fn map_farthest_error(self, f: impl FnOnce(ParseError) -> ParseError) -> Self {
+        Self {
+            farthest_error: self.farthest_error.map(|err| err.map(f)),
+            ..self
+        }
+    }
+
+    /// Record that the farthest error, if any, happened inside the rule `rule_name`.
+    #[inline]
+    pub fn push_error_context(mut self, rule_name: &'static str) -> Self {
+        if let Some(err) = &mut self.farthest_error {
+            err.push_context(rule_name);
+        }
+        self
+    }
+
+    /// Go back to the position of `earlier`, keeping the errors recorded and the input range
+    /// examined since then.
+    #[inline]
+    pub fn rewind_to(self, earlier: &Self) -> Self {
+        Self {
+            partial_string: earlier.partial_string,
+            start_index: earlier.start_index,
+            ..self
+        }
     }
 
     #[inline]
@@ -127,3 +343,19 @@ impl<'a> ParseState<'a> {
         self.start_index > other.start_index
     }
 }
+
+impl<'a> ParseState<'a> {
+    pub fn first_n_chars(&self, n: usize) -> String {
+        self.s().chars().take(n).collect()
+    }
+}
+
+/// How much of the input is looked at by a failed atomic match.
+fn examined_length(specifics: &ParseErrorSpecifics) -> usize {
+    match specifics {
+        ParseErrorSpecifics::ExpectedString { s } => s.len().max(1),
+        ParseErrorSpecifics::ExpectedByteString { s } => s.len().max(1),
+        ParseErrorSpecifics::ExpectedCharacter { c } => c.len_utf8(),
+        _ => 1,
+    }
+}
@@ -0,0 +1,208 @@
+// Copyright (C) 2022, Alex Badics
+// This file is part of peginator
+// Licensed under the MIT license. See LICENSE file in the project root for details.
+
+use std::{
+    error::Error,
+    io::{self, Read},
+    marker::PhantomData,
+};
+
+use super::{NoopTracer, ParseError, ParseInput, ParseSettings, PegParserAdvanced};
+
+/// The minimum number of bytes read from a [`Read`] at once.
+const READ_CHUNK_SIZE: usize = 8192;
+
+/// A source of input chunks for [`StreamParseIter`].
+pub trait StreamSource {
+    /// Append the next chunk of the input to `buffer`. Returns the number of appended bytes, 0 at
+    /// the end of the input.
+    fn read_chunk(&mut self, buffer: &mut Vec<u8>) -> io::Result<usize>;
+}
+
+/// [`StreamSource`] reading from a [`Read`].
+pub struct ReadSource<R>(pub R);
+
+impl<R: Read> StreamSource for ReadSource<R> {
+    fn read_chunk(&mut self, buffer: &mut Vec<u8>) -> io::Result<usize> {
+        let old_len = buffer.len();
+        // Items that need more input are reparsed from the start, so the reads grow with the
+        // buffer to keep the number of reparses low.
+        buffer.resize(old_len + old_len.max(READ_CHUNK_SIZE), 0);
+        let result = loop {
+            match self.0.read(&mut buffer[old_len..]) {
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+                result => break result,
+            }
+        };
+        buffer.truncate(old_len + *result.as_ref().unwrap_or(&0));
+        result
+    }
+}
+
+/// [`StreamSource`] going through an iterator of chunks.
+pub struct ChunkSource<It>(pub It);
+
+impl<C: AsRef<[u8]>, It: Iterator<Item = C>> StreamSource for ChunkSource<It> {
+    /// Appends chunks until the buffer doubles, or at least one chunk if the buffer was empty.
+    fn read_chunk(&mut self, buffer: &mut Vec<u8>) -> io::Result<usize> {
+        // Items that need more input are reparsed from the start, so the buffer is grown
+        // geometrically, even if the chunks are small.
+        let old_len = buffer.len();
+        let target_len = old_len + old_len.max(1);
+        for chunk in &mut self.0 {
+            buffer.extend_from_slice(chunk.as_ref());
+            if buffer.len() >= target_len {
+                break;
+            }
+        }
+        Ok(buffer.len() - old_len)
+    }
+}
+
+/// Error of a streamed parse: either reading the input, or parsing it failed.
+#[derive(Debug)]
+pub enum StreamParseError {
+    Io(io::Error),
+    Parse(ParseError),
+}
+
+impl std::fmt::Display for StreamParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            StreamParseError::Io(err) => write!(f, "I/O error: {err}"),
+            StreamParseError::Parse(err) => err.fmt(f),
+        }
+    }
+}
+
+impl Error for StreamParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            StreamParseError::Io(err) => Some(err),
+            StreamParseError::Parse(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for StreamParseError {
+    fn from(err: io::Error) -> Self {
+        StreamParseError::Io(err)
+    }
+}
+
+impl From<ParseError> for StreamParseError {
+    fn from(err: ParseError) -> Self {
+        StreamParseError::Parse(err)
+    }
+}
+
+enum Step<T> {
+    Item(Result<T, StreamParseError>),
+    NeedInput,
+    End,
+}
+
+/// Iterator over the items of a streamed input, parsed one by one with the same rule.
+///
+/// Only the input of the current item is kept in memory: it's read until the parser has all the
+/// input it looked at, and dropped after the item is parsed.
+///
+/// Created by [`PegParser::parse_reader`](super::PegParser::parse_reader) and
+/// [`PegParser::parse_chunks`](super::PegParser::parse_chunks).
+pub struct StreamParseIter<T, S, I: ?Sized + ParseInput = str> {
+    source: S,
+    buffer: Vec<u8>,
+    /// The position of the start of the buffer in the whole input.
+    base: usize,
+    end_of_input: bool,
+    finished: bool,
+    _result: PhantomData<fn(&I) -> T>,
+}
+
+impl<T, S, I: ?Sized + ParseInput> StreamParseIter<T, S, I> {
+    pub fn new(source: S) -> Self {
+        Self {
+            source,
+            buffer: Vec::new(),
+            base: 0,
+            end_of_input: false,
+            finished: false,
+            _result: PhantomData,
+        }
+    }
+
+    /// The position where the next item will be parsed from, in bytes.
+    pub fn position(&self) -> usize {
+        self.base
+    }
+}
+
+impl<T: for<'a> PegParserAdvanced<'a, (), I>, S: StreamSource, I: ?Sized + ParseInput>
+    StreamParseIter<T, S, I>
+{
+    fn parse_buffered(&mut self) -> Step<T> {
+        let input = I::valid_prefix(&self.buffer);
+        let input_end = self.base + input.len();
+        let invalid_tail = input.len() < self.buffer.len();
+        let result =
+            T::parse_advanced_item::<NoopTracer>(input, self.base, &ParseSettings::default(), ());
+        match result {
+            None if !self.end_of_input => Step::NeedInput,
+            None if invalid_tail => Step::Item(Err(invalid_data_error(input_end))),
+            None => Step::End,
+            Some(Ok(item)) if self.end_of_input || item.examined_until <= input_end => {
+                self.buffer.drain(..item.range.end - self.base);
+                self.base = item.range.end;
+                // An empty item would be parsed forever.
+                self.finished = item.range.is_empty();
+                Step::Item(Ok(item.result))
+            }
+            Some(Err(err)) if self.end_of_input || err.examined_until <= input_end => {
+                if invalid_tail && err.position >= input_end {
+                    Step::Item(Err(invalid_data_error(input_end)))
+                } else {
+                    Step::Item(Err(err.into()))
+                }
+            }
+            // The parser looked at the end of the buffered input, so the result may change
+            // with more input.
+            Some(_) => Step::NeedInput,
+        }
+    }
+}
+
+fn invalid_data_error(position: usize) -> StreamParseError {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("invalid input data at position {position}"),
+    )
+    .into()
+}
+
+impl<T: for<'a> PegParserAdvanced<'a, (), I>, S: StreamSource, I: ?Sized + ParseInput> Iterator
+    for StreamParseIter<T, S, I>
+{
+    type Item = Result<T, StreamParseError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.finished {
+            match self.parse_buffered() {
+                Step::Item(result) => {
+                    self.finished |= result.is_err();
+                    return Some(result);
+                }
+                Step::End => self.finished = true,
+                Step::NeedInput => match self.source.read_chunk(&mut self.buffer) {
+                    Ok(0) => self.end_of_input = true,
+                    Ok(_) => (),
+                    Err(err) => {
+                        self.finished = true;
+                        return Some(Err(err.into()));
+                    }
+                },
+            }
+        }
+        None
+    }
+}
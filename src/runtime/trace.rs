@@ -3,16 +3,16 @@
 // Licensed under the MIT license. See LICENSE file in the project root for details.
 use colored::*;
 
-use super::{ParseResult, ParseState};
+use super::{ParseInput, ParseResult, ParseState};
 
 pub trait ParseTracer: Clone + Copy {
     fn print_informative(&mut self, s: &str) {
         let _ = s;
     }
-    fn print_trace_start(&mut self, state: &ParseState, name: &str) {
+    fn print_trace_start<I: ?Sized + ParseInput>(&mut self, state: &ParseState<I>, name: &str) {
         let _ = (state, name);
     }
-    fn print_trace_result<T>(&mut self, result: &ParseResult<T>) {
+    fn print_trace_result<T, I: ?Sized + ParseInput>(&mut self, result: &ParseResult<T, I>) {
         let _ = result;
     }
 
@@ -32,26 +32,22 @@ impl ParseTracer for IndentedTracer {
     }
 
     #[inline]
-    fn print_trace_start(&mut self, state: &ParseState, name: &str) {
+    fn print_trace_start<I: ?Sized + ParseInput>(&mut self, state: &ParseState<I>, name: &str) {
         let indentation = "    ".repeat(self.indentation_level);
-        eprintln!("{indentation}{:?}", state.first_n_chars(50));
+        eprintln!("{indentation}{}", state.s().debug_prefix(50));
         eprintln!("{indentation}{}?", name.yellow());
         self.indentation_level += 1;
     }
 
     #[inline]
-    fn print_trace_result<T>(&mut self, result: &ParseResult<T>) {
+    fn print_trace_result<T, I: ?Sized + ParseInput>(&mut self, result: &ParseResult<T, I>) {
         let indentation = "    ".repeat(self.indentation_level);
         match &result {
             Ok(ok_result) => {
-                eprintln!("{indentation}{:?}", ok_result.state.first_n_chars(50));
+                eprintln!("{indentation}{}", ok_result.state.s().debug_prefix(50));
                 eprintln!("{indentation}{}", "Ok".green());
             }
-            Err(err) => eprintln!(
-                "{indentation}{} {}",
-                "Error:".red(),
-                err.specifics.to_string()
-            ),
+            Err(err) => eprintln!("{indentation}{} {}", "Error:".red(), err.error().message()),
         };
         self.indentation_level -= 1;
     }